@@ -8,7 +8,7 @@ use redisclient::RedisClient;
 
 #[bench]
 fn bench_strings_command(b: &mut Bencher) {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     b.iter(|| {
         let key = "test_key";
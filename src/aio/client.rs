@@ -0,0 +1,1889 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::aio::pool::AsyncConnectionPool;
+use crate::client::{command, Command, LPosOptions, ListBeforeOrAfter, ListDirection};
+use crate::commands::{self, Call};
+use crate::config::{RedisConfig, ToRedisConnectionConfig};
+use crate::connection::Reply;
+use crate::pipeline::Pipeline;
+use crate::protocol::{RedisDeserializationProtocol, RedisSerializationProtocol};
+use crate::scan::AsyncScan;
+use crate::sorted_sets::{LexBound, ScoreBound, ZAddOptions, ZAggregateOptions, ZRange};
+use crate::streams::{
+    AutoClaim, ConsumerInfo, GroupInfo, PendingEntry, PendingRange, PendingSummary, ReadFrom, StreamBound, StreamEntry,
+    StreamId, StreamInfo, StreamKey, StreamTrim, XAddOptions, XReadOptions,
+};
+use crate::{DataType, RedisResult};
+
+/// The async counterpart of `RedisClient`, with a pool of connections over
+/// `tokio::net::TcpStream`. Commands are sent with `async fn`s of the same names.
+///
+/// Optimistic locking transactions aren't supported, a pipeline in transaction mode runs
+/// its commands atomically.
+pub struct AsyncRedisClient {
+    pool: Arc<AsyncConnectionPool>,
+    read_timeout: Option<Duration>,
+}
+
+impl AsyncRedisClient {
+    pub fn new() -> RedisResult<AsyncRedisClient> {
+        let config = RedisConfig::default();
+        Self::with_config(config)
+    }
+
+    /// Creates a client from a `RedisConfig` or a URL, e.g. `redis://:password@localhost:6379/1`.
+    pub fn open<C: ToRedisConnectionConfig>(config: C) -> RedisResult<AsyncRedisClient> {
+        Self::with_config(config.to_redis_connection_config()?)
+    }
+
+    /// Creates a client, no connection is opened until a command is sent.
+    pub fn with_config(config: RedisConfig) -> RedisResult<AsyncRedisClient> {
+        Ok(AsyncRedisClient {
+            read_timeout: config.read_timeout,
+            pool: Arc::new(AsyncConnectionPool::new(config)),
+        })
+    }
+
+    /// Returns a client sharing the connections of this one, like `RedisClient::with_read_timeout`.
+    pub fn with_read_timeout(&self, read_timeout: Option<Duration>) -> AsyncRedisClient {
+        AsyncRedisClient {
+            pool: self.pool.clone(),
+            read_timeout,
+        }
+    }
+
+    pub fn pipe(&self) -> Pipeline {
+        Pipeline::new()
+    }
+
+    pub fn pipe_with_capacity(&self, capacity: usize) -> Pipeline {
+        Pipeline::with_capacity(capacity)
+    }
+
+    pub async fn flushall(&self) -> RedisResult<()> {
+        self.call(commands::flushall()).await
+    }
+
+    // Connection commands
+    /// The AUTH command authenticates the current connection
+    ///
+    /// Return value: Simple string reply
+    pub async fn auth<S>(&self, username: Option<S>, password: S) -> RedisResult<()>
+    where
+        S: ToString,
+    {
+        self.call(commands::auth(username, password)).await
+    }
+
+    /// Returns message.
+    ///
+    /// Return value: Bulk string reply
+    pub async fn echo<S>(&self, message: S) -> RedisResult<String>
+    where
+        S: ToString,
+    {
+        self.call(commands::echo(message)).await
+    }
+
+    /// Returns PONG if no argument is provided, otherwise return a copy of the argument as a bulk.
+    ///
+    /// Return value: Simple string reply
+    pub async fn ping(&self) -> RedisResult<()> {
+        self.call(commands::ping()).await
+    }
+
+    /// Ask the server to close the connection.
+    ///
+    /// Return value: Simple string reply
+    pub async fn quit(&self) -> RedisResult<()> {
+        self.call(commands::quit()).await
+    }
+    /// Select the Redis logical database having the specified zero-based numeric index.
+    ///
+    /// Every connection of the client switches to it, not only the one the command is sent on.
+    ///
+    /// Return value: Simple string reply
+    pub async fn select(&self, index: u8) -> RedisResult<()> {
+        let mut conn = self.pool.get().await?;
+        if let Err(e) = conn.select(index).await {
+            return Err(conn.discard_if_unrecoverable(e));
+        }
+        self.pool.select(index);
+        Ok(())
+    }
+
+    // Hashes commands
+    /// Removes the specified fields from the hash stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn hdel<K, I>(&self, key: K, fields: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::hdel(key, fields)).await
+    }
+
+    /// Returns if field is an existing field in the hash stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn hexists<K, F>(&self, key: K, field: F) -> RedisResult<bool>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+    {
+        self.call(commands::hexists(key, field)).await
+    }
+
+    /// Returns the value associated with field in the hash stored at key.
+    ///
+    /// Return value: Bulk string reply
+    pub async fn hget<K, F, V>(&self, key: K, field: F) -> RedisResult<V>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::hget(key, field)).await
+    }
+
+    /// Returns all fields and values of the hash stored at key.
+    ///
+    /// Return value: Array reply
+    pub async fn hgetall<K, M>(&self, key: K) -> RedisResult<M>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisDeserializationProtocol,
+    {
+        self.call(commands::hgetall(key)).await
+    }
+
+    /// Increments the number stored at field in the hash stored at key by increment.
+    ///
+    /// Return value: Integer value
+    pub async fn hincrby<K, F>(&self, key: K, field: F, increment: i64) -> RedisResult<i64>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+    {
+        self.call(commands::hincrby(key, field, increment)).await
+    }
+
+    /// Increment the specified field of a hash stored at key, and representing a floating point number, by the specified increment.
+    ///
+    /// Return value: Bulk string reply
+    pub async fn hincrbyfloat<K, F>(&self, key: K, field: F, increment: f64) -> RedisResult<f64>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+    {
+        self.call(commands::hincrbyfloat(key, field, increment)).await
+    }
+
+    /// Returns all field names in the hash stored at key.
+    ///
+    /// Return value: Array reply
+    pub async fn hkeys<K, V>(&self, key: K) -> RedisResult<Vec<V>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::hkeys(key)).await
+    }
+
+    /// Returns the number of fields contained in the hash stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn hlen<K>(&self, key: K) -> RedisResult<u64>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::hlen(key)).await
+    }
+
+    /// Returns the values associated with the specified fields in the hash stored at key.
+    ///
+    /// Return value: Array reply
+    pub async fn hmget<K, F, V, I>(&self, key: K, fields: I) -> RedisResult<Vec<V>>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = F>,
+    {
+        self.call(commands::hmget(key, fields)).await
+    }
+
+    /// Iterates over the fields and values of the hash stored at key, e.g. as `(String, String)` pairs.
+    pub fn hscan<K, T>(&self, key: K) -> AsyncScan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        AsyncScan::new(self, command!("HSCAN"; args => key), None)
+    }
+
+    /// Iterates over the fields matching pattern and their values, see `hscan`.
+    pub fn hscan_match<K, S, T>(&self, key: K, pattern: S) -> AsyncScan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        S: ToString,
+        T: RedisDeserializationProtocol,
+    {
+        AsyncScan::new(self, command!("HSCAN"; args => key), Some(pattern.to_string()))
+    }
+
+    /// Sets the specified fields to their respective values in the hash stored at key.
+    ///
+    /// Return values: Simple string reply
+    pub async fn hmset<K, F, V>(&self, key: K, fvs: Vec<(F, V)>) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::hmset(key, fvs)).await
+    }
+
+    /// Sets the specified fields to their respective values in the hash stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn hset<K, F, V, I>(&self, key: K, fvs: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+        I: IntoIterator<Item = (F, V)>,
+    {
+        self.call(commands::hset(key, fvs)).await
+    }
+
+    /// Sets field in the hash stored at key to value, only if field does not yet exist.
+    ///
+    /// Return value: Integer value
+    pub async fn hsetnx<K, F, V>(&self, key: K, field: F, value: V) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::hsetnx(key, field, value)).await
+    }
+
+    /// Returns the string length of the value associated with field in the hash stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn hstrlen<K, F>(&self, key: K, field: F) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+    {
+        self.call(commands::hstrlen(key, field)).await
+    }
+
+    /// Returns all values in the hash stored at key.
+    ///
+    /// Return value: Array reply
+    pub async fn hvals<K, V>(&self, key: K) -> RedisResult<Vec<V>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::hvals(key)).await
+    }
+
+    // keys command
+    /// Removes the specified keys. A key is ignored if it does not exist.
+    ///
+    /// Return value: Integer reply
+    pub async fn del<K, I>(&self, keys: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::del(keys)).await
+    }
+
+    /// Returns if key exists.
+    ///
+    /// Return value: Integer reply
+    pub async fn exists<K, I>(&self, keys: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::exists(keys)).await
+    }
+
+    /// Set a timeout on key. After the timeout has expired, the key will automatically be deleted.
+    ///
+    /// Return value: Integer reply
+    pub async fn expire<K>(&self, key: K, seconds: usize) -> RedisResult<bool>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::expire(key, seconds)).await
+    }
+
+    /// Returns all keys matching pattern.
+    ///
+    /// Return value: Array reply
+    pub async fn keys<S>(&self, pattern: S) -> RedisResult<Vec<String>>
+    where
+        S: ToString,
+    {
+        self.call(commands::keys(pattern)).await
+    }
+
+    /// Remove the existing timeout on key, turning the key from volatile to persistent.
+    ///
+    /// Return value: Integer reply
+    pub async fn persist<K>(&self, key: K) -> RedisResult<bool>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::persist(key)).await
+    }
+
+    /// This command works exactly like EXPIRE but the time to live of the key is specified in milliseconds instead of seconds.
+    ///
+    /// Return value: Integer reply
+    pub async fn pexpire<K>(&self, key: K, milliseconds: u64) -> RedisResult<bool>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::pexpire(key, milliseconds)).await
+    }
+
+    /// Like TTL this command returns the remaining time to live of a key that has an expire set,
+    /// with the sole difference that TTL returns the amount of remaining time in seconds while PTTL returns it in milliseconds.
+    ///
+    /// Return value: Integer reply
+    pub async fn pttl<K>(&self, key: K) -> RedisResult<i64>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::pttl(key)).await
+    }
+
+    /// Return a random key from the currently selected database.
+    ///
+    /// Return value: Bulk string reply
+    pub async fn randomkey(&self) -> RedisResult<Option<String>> {
+        self.call(commands::randomkey()).await
+    }
+
+    /// Renames key to newkey. It returns an error when key does not exist.
+    ///
+    /// Return value: Simple string reply
+    pub async fn rename<K>(&self, key: K, newkey: K) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::rename(key, newkey)).await
+    }
+
+    /// Renames key to newkey if newkey does not yet exist. It returns an error when key does not exist.
+    ///
+    /// Return value: Integer reply
+    pub async fn renamenx<K>(&self, key: K, newkey: K) -> RedisResult<bool>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::renamenx(key, newkey)).await
+    }
+
+    /// Iterates over the keys of the database, without blocking the server like `keys` does.
+    pub fn scan<T>(&self) -> AsyncScan<'_, T>
+    where
+        T: RedisDeserializationProtocol,
+    {
+        AsyncScan::new(self, Command::new("SCAN"), None)
+    }
+
+    /// Iterates over the keys matching pattern, see `scan`.
+    pub fn scan_match<S, T>(&self, pattern: S) -> AsyncScan<'_, T>
+    where
+        S: ToString,
+        T: RedisDeserializationProtocol,
+    {
+        AsyncScan::new(self, Command::new("SCAN"), Some(pattern.to_string()))
+    }
+
+    /// Alters the last access time of a key(s). A key is ignored if it does not exist.
+    ///
+    /// Return value: Integer reply
+    pub async fn touch<K, I>(&self, keys: I) -> RedisResult<isize>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::touch(keys)).await
+    }
+
+    /// Returns the remaining time to live of a key that has a timeout.
+    ///
+    /// Return value: Integer reply
+    pub async fn ttl<K>(&self, key: K) -> RedisResult<isize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::ttl(key)).await
+    }
+
+    /// Returns the string representation of the type of the value stored at key.
+    ///
+    /// Return value: Simple string reply
+    pub async fn type_<K>(&self, key: K) -> RedisResult<DataType>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::type_(key)).await
+    }
+
+    /// This command is very similar to DEL: it removes the specified keys.
+    ///
+    /// Return value: Integer reply
+    pub async fn unlink<K, I>(&self, keys: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::unlink(keys)).await
+    }
+
+    /// BLMOVE is the blocking variant of LMOVE, it waits up to timeout for an element,
+    /// or forever if zero.
+    ///
+    /// Return value: Bulk string reply, nil when the timeout is reached
+    pub async fn blmove<K, V>(
+        &self,
+        source: K,
+        destination: K,
+        wherefrom: ListDirection,
+        whereto: ListDirection,
+        timeout: Duration,
+    ) -> RedisResult<Option<V>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::blmove(source, destination, wherefrom, whereto, timeout))
+            .await
+    }
+
+    /// BLMPOP is the blocking variant of LMPOP, it waits up to timeout for an element,
+    /// or forever if zero.
+    ///
+    /// Return value: Array reply, the key and the popped elements, nil when the timeout is reached
+    pub async fn blmpop<K, R, V, I>(
+        &self,
+        timeout: Duration,
+        keys: I,
+        direction: ListDirection,
+        count: usize,
+    ) -> RedisResult<Option<(R, Vec<V>)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::blmpop(timeout, keys, direction, count)).await
+    }
+
+    /// BLPOP is the blocking variant of LPOP, it pops from the first non-empty list of keys and
+    /// waits up to timeout for an element, or forever if zero.
+    ///
+    /// Return value: Array reply, the key and the popped element, nil when the timeout is reached
+    pub async fn blpop<K, R, V, I>(&self, keys: I, timeout: Duration) -> RedisResult<Option<(R, V)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::blpop(keys, timeout)).await
+    }
+
+    /// BRPOP is the blocking variant of RPOP, like `blpop`.
+    ///
+    /// Return value: Array reply, the key and the popped element, nil when the timeout is reached
+    pub async fn brpop<K, R, V, I>(&self, keys: I, timeout: Duration) -> RedisResult<Option<(R, V)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::brpop(keys, timeout)).await
+    }
+
+    /// BRPOPLPUSH is the blocking variant of RPOPLPUSH.
+    ///
+    /// Return value: Bulk string reply
+    pub async fn brpoplpush<K, E>(&self, source: K, destination: K, timeout: Duration) -> RedisResult<E>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisDeserializationProtocol,
+    {
+        self.call(commands::brpoplpush(source, destination, timeout)).await
+    }
+
+    // Lists commands
+    /// Returns the element at index index in the list stored at key.
+    ///
+    /// Return value: Bulk string reply
+    pub async fn lindex<K, V>(&self, key: K, index: isize) -> RedisResult<V>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::lindex(key, index)).await
+    }
+
+    /// Inserts element in the list stored at key either before or after the reference value pivot.
+    ///
+    /// Return value: Integer reply
+    pub async fn linsert<K, E>(&self, key: K, operator: ListBeforeOrAfter, pivot: E, element: E) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.call(commands::linsert(key, operator, pivot, element)).await
+    }
+
+    /// Returns the length of the list stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn llen<K>(&self, key: K) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::llen(key)).await
+    }
+
+    /// Atomically returns and removes the first or last element of the list stored at source,
+    /// and pushes it at the first or last element of the list stored at destination.
+    ///
+    /// Return value: Bulk string reply, nil when source doesn't exist
+    pub async fn lmove<K, V>(
+        &self,
+        source: K,
+        destination: K,
+        wherefrom: ListDirection,
+        whereto: ListDirection,
+    ) -> RedisResult<Option<V>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::lmove(source, destination, wherefrom, whereto))
+            .await
+    }
+
+    /// Pops up to count elements from the first non-empty list of keys.
+    ///
+    /// Return value: Array reply, the key and the popped elements, nil when all the lists are empty
+    pub async fn lmpop<K, R, V, I>(
+        &self,
+        keys: I,
+        direction: ListDirection,
+        count: usize,
+    ) -> RedisResult<Option<(R, Vec<V>)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::lmpop(keys, direction, count)).await
+    }
+
+    /// Removes and returns the first element of the list stored at key.
+    ///
+    /// Return value: Bulk string reply
+    pub async fn lpop<K, E>(&self, key: K) -> RedisResult<E>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisDeserializationProtocol,
+    {
+        self.call(commands::lpop(key)).await
+    }
+
+    /// Removes and returns up to count elements from the head of the list stored at key.
+    ///
+    /// Return value: Array reply, empty when key doesn't exist
+    pub async fn lpop_count<K, E>(&self, key: K, count: usize) -> RedisResult<Vec<E>>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisDeserializationProtocol,
+    {
+        self.call(commands::lpop_count(key, count)).await
+    }
+
+    /// Returns the index of the first element matching element in the list stored at key.
+    ///
+    /// Return value: Integer reply, nil when there is no match
+    pub async fn lpos<K, E>(&self, key: K, element: E, options: LPosOptions) -> RedisResult<Option<usize>>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.call(commands::lpos(key, element, options)).await
+    }
+
+    /// Returns the indexes of up to count elements matching element, zero meaning all of them.
+    ///
+    /// Return value: Array reply
+    pub async fn lpos_count<K, E>(
+        &self,
+        key: K,
+        element: E,
+        count: usize,
+        options: LPosOptions,
+    ) -> RedisResult<Vec<usize>>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.call(commands::lpos_count(key, element, count, options)).await
+    }
+
+    /// Insert all the specified values at the head of the list stored at key.
+    ///
+    /// Retrun value: Integer reply
+    pub async fn lpush<K, E, I>(&self, key: K, elements: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
+    {
+        self.call(commands::lpush(key, elements)).await
+    }
+
+    /// Inserts specified values at the head of the list stored at key, only if key already exists and holds a list.
+    ///
+    /// Return value: Integer value
+    pub async fn lpushx<K, E, I>(&self, key: K, elements: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
+    {
+        self.call(commands::lpushx(key, elements)).await
+    }
+
+    /// Returns the specified elements of the list stored at key.
+    ///
+    /// Return value: Array reply
+    pub async fn lrange<K, E>(&self, key: K, start: isize, end: isize) -> RedisResult<Vec<E>>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisDeserializationProtocol,
+    {
+        self.call(commands::lrange(key, start, end)).await
+    }
+
+    /// Removes the first count occurrences of elements equal to element from the list stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn lrem<K, E>(&self, key: K, count: isize, element: E) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.call(commands::lrem(key, count, element)).await
+    }
+
+    /// Sets the list element at index to element.
+    ///
+    /// Return value: Simple string reply
+    pub async fn lset<K, E>(&self, key: K, index: isize, element: E) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.call(commands::lset(key, index, element)).await
+    }
+
+    /// Trim an existing list so that it will contain only the specified range of elements specified.
+    ///
+    /// Return value: Simple string reply
+    pub async fn ltrim<K>(&self, key: K, start: isize, stop: isize) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::ltrim(key, start, stop)).await
+    }
+
+    /// Removes and returns the last element of the list stored at key.
+    ///
+    /// Return value: Bulk string reply
+    pub async fn rpop<K, E>(&self, key: K) -> RedisResult<E>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisDeserializationProtocol,
+    {
+        self.call(commands::rpop(key)).await
+    }
+
+    /// Removes and returns up to count elements from the tail of the list stored at key.
+    ///
+    /// Return value: Array reply, empty when key doesn't exist
+    pub async fn rpop_count<K, E>(&self, key: K, count: usize) -> RedisResult<Vec<E>>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisDeserializationProtocol,
+    {
+        self.call(commands::rpop_count(key, count)).await
+    }
+
+    /// Atomically returns and removes the last element (tail) of the list stored at source, and
+    /// pushes the element at the first element (head) of the list stored at destination.
+    ///
+    /// Return value: Bulk string reply
+    pub async fn rpoplpush<K, E>(&self, source: K, destination: K) -> RedisResult<E>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisDeserializationProtocol,
+    {
+        self.call(commands::rpoplpush(source, destination)).await
+    }
+
+    /// Insert all the specified values at the tail of the list stored at key.
+    ///
+    /// Return value: Integer value
+    pub async fn rpush<K, E, I>(&self, key: K, elements: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
+    {
+        self.call(commands::rpush(key, elements)).await
+    }
+
+    /// Inserts specified values at the tail of the list stored at key, only if key already exists and holds a list.
+    ///
+    /// Return value: Integer reply
+    pub async fn rpushx<K, E, I>(&self, key: K, elements: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
+    {
+        self.call(commands::rpushx(key, elements)).await
+    }
+
+    // Sets commands
+    /// Add the specified members to the set stored at key.
+    ///
+    /// Return value: Integer value
+    pub async fn sadd<K, M, I>(&self, key: K, members: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        self.call(commands::sadd(key, members)).await
+    }
+
+    /// Returns the set cardinality (number of elements) of the set stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn scard<K>(&self, key: K) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::scard(key)).await
+    }
+
+    /// Returns the members of the set resulting from the difference between the first set and all the successive sets.
+    ///
+    /// Return value: Array reply
+    pub async fn sdiff<K, M, I>(&self, keys: I) -> RedisResult<HashSet<M>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisDeserializationProtocol + Hash + Eq,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::sdiff(keys)).await
+    }
+
+    /// This command is equal to SDIFF, but instead of returning the resulting set, it is stored in destination.
+    ///
+    /// Return value: Integer reply
+    pub async fn sdiffstore<K, I>(&self, destination: K, keys: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol + Hash + Eq,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::sdiffstore(destination, keys)).await
+    }
+
+    /// Returns the members of the set resulting from the intersection of all the given sets.
+    ///
+    /// Return value: Array reply
+    pub async fn sinter<K, M, I>(&self, keys: I) -> RedisResult<HashSet<M>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisDeserializationProtocol + Hash + Eq,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::sinter(keys)).await
+    }
+
+    /// This command is equal to SINTER, but instead of returning the resulting set, it is stored in destination.
+    ///
+    /// Return value: Integer reply
+    pub async fn sinterstore<K, I>(&self, destination: K, keys: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::sinterstore(destination, keys)).await
+    }
+
+    /// Returns if member is a member of the set stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn sismember<K, M>(&self, key: K, member: M) -> RedisResult<bool>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol + Hash + Eq,
+    {
+        self.call(commands::sismember(key, member)).await
+    }
+
+    /// Returns all the members of the set value stored at key.
+    ///
+    /// Return value: Array reply
+    pub async fn smembers<K, M>(&self, key: K) -> RedisResult<HashSet<M>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisDeserializationProtocol + Hash + Eq,
+    {
+        self.call(commands::smembers(key)).await
+    }
+
+    /// Returns whether each member is a member of the set stored at key.
+    ///
+    /// Return value: Array reply
+    pub async fn smismember<K, M, I>(&self, key: K, members: I) -> RedisResult<Vec<bool>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        self.call(commands::smismember(key, members)).await
+    }
+
+    /// Move member from the set at source to the set at destination.
+    ///
+    /// Return value: Integer reply
+    pub async fn smove<K, M>(&self, source: K, destination: K, member: M) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol + Hash + Eq,
+    {
+        self.call(commands::smove(source, destination, member)).await
+    }
+
+    /// Removes and returns one or more random members from the set value store at key.
+    ///
+    /// Return value: Bulk string reply or Array reply
+    pub async fn spop<K, M>(&self, key: K, count: Option<usize>) -> RedisResult<M>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisDeserializationProtocol,
+    {
+        self.call(commands::spop(key, count)).await
+    }
+
+    /// When called with just the key argument, return a random element from the set value stored at key.
+    ///
+    /// Return value: Bulk string reply or Array reply
+    pub async fn srandmember<K, M>(&self, key: K, count: Option<usize>) -> RedisResult<M>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisDeserializationProtocol,
+    {
+        self.call(commands::srandmember(key, count)).await
+    }
+
+    /// Remove the specified members from the set stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn srem<K, M, I>(&self, key: K, members: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        self.call(commands::srem(key, members)).await
+    }
+
+    /// Iterates over the members of the set stored at key.
+    pub fn sscan<K, T>(&self, key: K) -> AsyncScan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        AsyncScan::new(self, command!("SSCAN"; args => key), None)
+    }
+
+    /// Iterates over the members matching pattern, see `sscan`.
+    pub fn sscan_match<K, S, T>(&self, key: K, pattern: S) -> AsyncScan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        S: ToString,
+        T: RedisDeserializationProtocol,
+    {
+        AsyncScan::new(self, command!("SSCAN"; args => key), Some(pattern.to_string()))
+    }
+
+    /// Returns the members of the set resulting from the union of all the given sets.
+    ///
+    /// Return value: Array reply
+    pub async fn sunion<K, M, I>(&self, keys: I) -> RedisResult<HashSet<M>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisDeserializationProtocol + Hash + Eq,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::sunion(keys)).await
+    }
+
+    /// This command is equal to SUNION, but instead of returning the resulting set, it is stored in destination.
+    ///
+    /// Return value: Integer reply
+    pub async fn sunionstore<K, I>(&self, destination: K, keys: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::sunionstore(destination, keys)).await
+    }
+
+    // Sorted Sets commands
+    /// BZPOPMAX is the blocking variant of ZPOPMAX, it pops from the first non-empty sorted set
+    /// of keys and waits up to timeout for a member, or forever if zero.
+    ///
+    /// Return value: Array reply, the key, the member and its score, nil when the timeout is reached
+    pub async fn bzpopmax<K, R, M, I>(&self, keys: I, timeout: Duration) -> RedisResult<Option<(R, M, f64)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        M: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::bzpopmax(keys, timeout)).await
+    }
+
+    /// BZPOPMIN is the blocking variant of ZPOPMIN, like `bzpopmax`.
+    ///
+    /// Return value: Array reply, the key, the member and its score, nil when the timeout is reached
+    pub async fn bzpopmin<K, R, M, I>(&self, keys: I, timeout: Duration) -> RedisResult<Option<(R, M, f64)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        M: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::bzpopmin(keys, timeout)).await
+    }
+
+    /// Adds all the specified members with the specified scores to the sorted set stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn zadd<K, M, I>(&self, key: K, members: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = (f64, M)>,
+    {
+        self.zadd_options(key, members, ZAddOptions::new()).await
+    }
+
+    /// Like `zadd`, with the NX, XX, GT, LT and CH options.
+    ///
+    /// Return value: Integer reply
+    pub async fn zadd_options<K, M, I>(&self, key: K, members: I, options: ZAddOptions) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = (f64, M)>,
+    {
+        self.call(commands::zadd_options(key, members, options)).await
+    }
+
+    /// Increments the score of member like `zincrby`, unless the options prevent it.
+    ///
+    /// Return value: Bulk string reply, the new score or nil if the options prevented the update
+    pub async fn zadd_incr<K, M>(
+        &self,
+        key: K,
+        increment: f64,
+        member: M,
+        options: ZAddOptions,
+    ) -> RedisResult<Option<f64>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zadd_incr(key, increment, member, options)).await
+    }
+
+    /// Returns the sorted set cardinality (number of elements) of the sorted set stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn zcard<K>(&self, key: K) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::zcard(key)).await
+    }
+
+    /// Returns the number of elements in the sorted set at key with a score between min and max.
+    ///
+    /// Return value: Integer reply
+    pub async fn zcount<K, Min, Max>(&self, key: K, min: Min, max: Max) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        Min: Into<ScoreBound>,
+        Max: Into<ScoreBound>,
+    {
+        self.call(commands::zcount(key, min, max)).await
+    }
+
+    /// Returns the members of the sets resulting from the difference between the first set and all the successive sets.
+    ///
+    /// Return value: Array reply, with the scores if `with_scores`
+    pub async fn zdiff<K, T, I>(&self, keys: I, with_scores: bool) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zdiff(keys, with_scores)).await
+    }
+
+    /// Computes the difference like `zdiff` and stores the result in destination.
+    ///
+    /// Return value: Integer reply, the number of members in the resulting sorted set
+    pub async fn zdiffstore<D, K, I>(&self, destination: D, keys: I) -> RedisResult<usize>
+    where
+        D: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zdiffstore(destination, keys)).await
+    }
+
+    /// Increments the score of member in the sorted set stored at key by increment.
+    ///
+    /// Return value: Bulk string reply, the new score
+    pub async fn zincrby<K, M>(&self, key: K, increment: f64, member: M) -> RedisResult<f64>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zincrby(key, increment, member)).await
+    }
+
+    /// Returns the members of the intersection of the given sorted sets.
+    ///
+    /// Return value: Array reply, with the scores if `with_scores`
+    pub async fn zinter<K, T, I>(&self, keys: I, options: ZAggregateOptions, with_scores: bool) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zinter(keys, options, with_scores)).await
+    }
+
+    /// Computes the intersection like `zinter` and stores the result in destination.
+    ///
+    /// Return value: Integer reply, the number of members in the resulting sorted set
+    pub async fn zinterstore<D, K, I>(&self, destination: D, keys: I, options: ZAggregateOptions) -> RedisResult<usize>
+    where
+        D: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zinterstore(destination, keys, options)).await
+    }
+
+    /// Returns the scores associated with the specified members in the sorted set stored at key.
+    ///
+    /// Return value: Array reply, nil for a member which doesn't exist
+    pub async fn zmscore<K, M, I>(&self, key: K, members: I) -> RedisResult<Vec<Option<f64>>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        self.call(commands::zmscore(key, members)).await
+    }
+
+    /// Removes and returns up to count members with the highest scores in the sorted set stored at key.
+    ///
+    /// Return value: Array reply, e.g. of `(String, f64)` pairs
+    pub async fn zpopmax<K, T>(&self, key: K, count: usize) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        self.call(commands::zpopmax(key, count)).await
+    }
+
+    /// Removes and returns up to count members with the lowest scores in the sorted set stored at key.
+    ///
+    /// Return value: Array reply, e.g. of `(String, f64)` pairs
+    pub async fn zpopmin<K, T>(&self, key: K, count: usize) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        self.call(commands::zpopmin(key, count)).await
+    }
+
+    /// Returns a random member of the sorted set stored at key.
+    ///
+    /// Return value: Bulk string reply, nil when key doesn't exist
+    pub async fn zrandmember<K, M>(&self, key: K) -> RedisResult<M>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisDeserializationProtocol,
+    {
+        self.call(commands::zrandmember(key)).await
+    }
+
+    /// Returns up to count distinct random members, or count members which may repeat if it's negative.
+    ///
+    /// Return value: Array reply, with the scores if `with_scores`
+    pub async fn zrandmember_count<K, T>(&self, key: K, count: isize, with_scores: bool) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        self.call(commands::zrandmember_count(key, count, with_scores)).await
+    }
+
+    /// Returns the specified range of members in the sorted set stored at key.
+    ///
+    /// Return value: Array reply, with the scores if the range says so
+    pub async fn zrange<K, T>(&self, key: K, range: ZRange) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        self.call(commands::zrange(key, range)).await
+    }
+
+    /// Returns the rank of member in the sorted set stored at key, with the scores ordered from low to high.
+    ///
+    /// Return value: Integer reply, nil when member doesn't exist
+    pub async fn zrank<K, M>(&self, key: K, member: M) -> RedisResult<Option<usize>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zrank(key, member)).await
+    }
+
+    /// Removes the specified members from the sorted set stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn zrem<K, M, I>(&self, key: K, members: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        self.call(commands::zrem(key, members)).await
+    }
+
+    /// Removes all members in the sorted set stored at key between the lexicographical range specified by min and max.
+    ///
+    /// Return value: Integer reply
+    pub async fn zremrangebylex<K>(&self, key: K, min: LexBound, max: LexBound) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::zremrangebylex(key, min, max)).await
+    }
+
+    /// Removes all members in the sorted set stored at key with rank between start and stop.
+    ///
+    /// Return value: Integer reply
+    pub async fn zremrangebyrank<K>(&self, key: K, start: isize, stop: isize) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::zremrangebyrank(key, start, stop)).await
+    }
+
+    /// Removes all members in the sorted set stored at key with a score between min and max.
+    ///
+    /// Return value: Integer reply
+    pub async fn zremrangebyscore<K, Min, Max>(&self, key: K, min: Min, max: Max) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        Min: Into<ScoreBound>,
+        Max: Into<ScoreBound>,
+    {
+        self.call(commands::zremrangebyscore(key, min, max)).await
+    }
+
+    /// Returns the rank of member in the sorted set stored at key, with the scores ordered from high to low.
+    ///
+    /// Return value: Integer reply, nil when member doesn't exist
+    pub async fn zrevrank<K, M>(&self, key: K, member: M) -> RedisResult<Option<usize>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zrevrank(key, member)).await
+    }
+
+    /// Iterates over the members and scores of the sorted set stored at key, e.g. as `(String, f64)` pairs.
+    pub fn zscan<K, T>(&self, key: K) -> AsyncScan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        AsyncScan::new(self, command!("ZSCAN"; args => key), None)
+    }
+
+    /// Iterates over the members matching pattern and their scores, see `zscan`.
+    pub fn zscan_match<K, S, T>(&self, key: K, pattern: S) -> AsyncScan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        S: ToString,
+        T: RedisDeserializationProtocol,
+    {
+        AsyncScan::new(self, command!("ZSCAN"; args => key), Some(pattern.to_string()))
+    }
+
+    /// Returns the score of member in the sorted set at key.
+    ///
+    /// Return value: Bulk string reply, nil when member or key doesn't exist
+    pub async fn zscore<K, M>(&self, key: K, member: M) -> RedisResult<Option<f64>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zscore(key, member)).await
+    }
+
+    /// Returns the members of the union of the given sorted sets.
+    ///
+    /// Return value: Array reply, with the scores if `with_scores`
+    pub async fn zunion<K, T, I>(&self, keys: I, options: ZAggregateOptions, with_scores: bool) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zunion(keys, options, with_scores)).await
+    }
+
+    /// Computes the union like `zunion` and stores the result in destination.
+    ///
+    /// Return value: Integer reply, the number of members in the resulting sorted set
+    pub async fn zunionstore<D, K, I>(&self, destination: D, keys: I, options: ZAggregateOptions) -> RedisResult<usize>
+    where
+        D: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zunionstore(destination, keys, options)).await
+    }
+
+    // Streams commands
+    /// Removes the specified entries from the pending entries list of the consumer group.
+    ///
+    /// Return value: Integer reply, the number of entries acknowledged
+    pub async fn xack<K, G, I>(&self, key: K, group: G, ids: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+        I: IntoIterator<Item = StreamId>,
+    {
+        self.call(commands::xack(key, group, ids)).await
+    }
+
+    /// Appends an entry with the specified fields to the stream stored at key, with an ID generated by the server.
+    ///
+    /// Return value: Bulk string reply, the ID of the entry
+    pub async fn xadd<K, F, V, I>(&self, key: K, fields: I) -> RedisResult<StreamId>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+        I: IntoIterator<Item = (F, V)>,
+    {
+        self.call(commands::xadd(key, fields)).await
+    }
+
+    /// Like `xadd`, with an explicit ID, the NOMKSTREAM option or trimming.
+    ///
+    /// Return value: Bulk string reply, the ID of the entry or nil if NOMKSTREAM prevented it
+    pub async fn xadd_options<K, F, V, I>(
+        &self,
+        key: K,
+        fields: I,
+        options: XAddOptions,
+    ) -> RedisResult<Option<StreamId>>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+        I: IntoIterator<Item = (F, V)>,
+    {
+        self.call(commands::xadd_options(key, fields, options)).await
+    }
+
+    /// Transfers to consumer up to count pending entries idle for at least min_idle_time, from start on.
+    ///
+    /// Return value: Array reply, the ID to continue from and the claimed entries
+    pub async fn xautoclaim<K, G, C, V>(
+        &self,
+        key: K,
+        group: G,
+        consumer: C,
+        min_idle_time: Duration,
+        start: StreamId,
+        count: Option<usize>,
+    ) -> RedisResult<AutoClaim<V>>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+        C: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::xautoclaim(key, group, consumer, min_idle_time, start, count))
+            .await
+    }
+
+    /// Transfers to consumer the specified pending entries which are idle for at least min_idle_time.
+    ///
+    /// Return value: Array reply, the claimed entries
+    pub async fn xclaim<K, G, C, V, I>(
+        &self,
+        key: K,
+        group: G,
+        consumer: C,
+        min_idle_time: Duration,
+        ids: I,
+    ) -> RedisResult<Vec<StreamEntry<V>>>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+        C: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = StreamId>,
+    {
+        self.call(commands::xclaim(key, group, consumer, min_idle_time, ids))
+            .await
+    }
+
+    /// Removes the specified entries from the stream stored at key.
+    ///
+    /// Return value: Integer reply, the number of entries deleted
+    pub async fn xdel<K, I>(&self, key: K, ids: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = StreamId>,
+    {
+        self.call(commands::xdel(key, ids)).await
+    }
+
+    /// Creates a consumer group starting from id, and the stream if `mkstream` and it doesn't exist.
+    ///
+    /// Return value: Simple string reply
+    pub async fn xgroup_create<K, G>(&self, key: K, group: G, id: ReadFrom, mkstream: bool) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xgroup_create(key, group, id, mkstream)).await
+    }
+
+    /// Destroys a consumer group, its consumers and pending entries.
+    ///
+    /// Return value: Integer reply, whether the group existed
+    pub async fn xgroup_destroy<K, G>(&self, key: K, group: G) -> RedisResult<bool>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xgroup_destroy(key, group)).await
+    }
+
+    /// Sets the last delivered ID of a consumer group.
+    ///
+    /// Return value: Simple string reply
+    pub async fn xgroup_setid<K, G>(&self, key: K, group: G, id: ReadFrom) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xgroup_setid(key, group, id)).await
+    }
+
+    /// Returns the consumers of a consumer group.
+    ///
+    /// Return value: Array reply
+    pub async fn xinfo_consumers<K, G>(&self, key: K, group: G) -> RedisResult<Vec<ConsumerInfo>>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xinfo_consumers(key, group)).await
+    }
+
+    /// Returns the consumer groups of the stream stored at key.
+    ///
+    /// Return value: Array reply
+    pub async fn xinfo_groups<K>(&self, key: K) -> RedisResult<Vec<GroupInfo>>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::xinfo_groups(key)).await
+    }
+
+    /// Returns general information about the stream stored at key.
+    ///
+    /// Return value: Map reply
+    pub async fn xinfo_stream<K, V>(&self, key: K) -> RedisResult<StreamInfo<V>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::xinfo_stream(key)).await
+    }
+
+    /// Returns the number of entries in the stream stored at key.
+    ///
+    /// Return value: Integer reply
+    pub async fn xlen<K>(&self, key: K) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::xlen(key)).await
+    }
+
+    /// Returns the number of pending entries of a consumer group, their lowest and highest IDs and their consumers.
+    ///
+    /// Return value: Array reply
+    pub async fn xpending<K, G>(&self, key: K, group: G) -> RedisResult<PendingSummary>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xpending(key, group)).await
+    }
+
+    /// Returns the pending entries of a consumer group in the specified range.
+    ///
+    /// Return value: Array reply
+    pub async fn xpending_range<K, G>(&self, key: K, group: G, range: PendingRange) -> RedisResult<Vec<PendingEntry>>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xpending_range(key, group, range)).await
+    }
+
+    /// Returns up to count entries of the stream stored at key with an ID from start to end.
+    ///
+    /// Return value: Array reply
+    pub async fn xrange<K, V>(
+        &self,
+        key: K,
+        start: StreamBound,
+        end: StreamBound,
+        count: Option<usize>,
+    ) -> RedisResult<Vec<StreamEntry<V>>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::xrange(key, start, end, count)).await
+    }
+
+    /// Reads the entries of the specified streams with an ID greater than the one given for each of them.
+    ///
+    /// Return value: Array reply, empty when the BLOCK timeout is reached
+    pub async fn xread<K, V, I>(&self, keys: I, options: XReadOptions) -> RedisResult<Vec<StreamKey<V>>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = (K, ReadFrom)>,
+    {
+        self.call(commands::xread(keys, options)).await
+    }
+
+    /// Like `xread`, as consumer of a consumer group. `ReadFrom::Undelivered` reads the new
+    /// entries, an ID the pending entries of the consumer.
+    ///
+    /// Return value: Array reply, empty when the BLOCK timeout is reached
+    pub async fn xreadgroup<G, C, K, V, I>(
+        &self,
+        group: G,
+        consumer: C,
+        keys: I,
+        options: XReadOptions,
+    ) -> RedisResult<Vec<StreamKey<V>>>
+    where
+        G: RedisSerializationProtocol,
+        C: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = (K, ReadFrom)>,
+    {
+        self.call(commands::xreadgroup(group, consumer, keys, options)).await
+    }
+
+    /// Like `xrange`, in reverse order, so end comes before start.
+    ///
+    /// Return value: Array reply
+    pub async fn xrevrange<K, V>(
+        &self,
+        key: K,
+        end: StreamBound,
+        start: StreamBound,
+        count: Option<usize>,
+    ) -> RedisResult<Vec<StreamEntry<V>>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::xrevrange(key, end, start, count)).await
+    }
+
+    /// Trims the stream stored at key.
+    ///
+    /// Return value: Integer reply, the number of entries deleted
+    pub async fn xtrim<K>(&self, key: K, trim: StreamTrim) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::xtrim(key, trim)).await
+    }
+
+    // Strings commands
+    pub async fn append<K, V>(&self, key: K, value: V) -> RedisResult<u64>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::append(key, value)).await
+    }
+
+    /// Count the number of set bits (population counting) in a string.
+    pub async fn bitcount<K>(&self, key: K, start: Option<i64>, end: Option<i64>) -> RedisResult<u64>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::bitcount(key, start, end)).await
+    }
+
+    /// Perform a bitwise operation between multiple keys (containing string values) and store the result in the destination key.
+    pub async fn bitop<K1, K2, I>(&self, operation: &str, destkey: K1, keys: I) -> RedisResult<usize>
+    where
+        K1: RedisSerializationProtocol,
+        K2: RedisSerializationProtocol,
+        I: IntoIterator<Item = K2>,
+    {
+        self.call(commands::bitop(operation, destkey, keys)).await
+    }
+
+    /// Return the position of the first bit set to 1 or 0 in a string.
+    pub async fn bitpos<K>(&self, key: K, bit: u8, start: Option<usize>, end: Option<usize>) -> RedisResult<isize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::bitpos(key, bit, start, end)?).await
+    }
+
+    /// Decrements the number stored at key by one.
+    pub async fn decr<K>(&self, key: K) -> RedisResult<i64>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::decr(key)).await
+    }
+
+    /// Decrements the number stored at key by decrement.
+    pub async fn decrby<K>(&self, key: K, decrement: i64) -> RedisResult<i64>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::decrby(key, decrement)).await
+    }
+
+    /// Get the value of key.
+    ///
+    /// A missing key is a nil reply, use `Option<V>` to tell it apart from an error.
+    pub async fn get<K, V>(&self, key: K) -> RedisResult<V>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::get(key)).await
+    }
+
+    /// Returns the bit value at offset in the string value stored at key.
+    pub async fn getbit<K>(&self, key: K, offset: i64) -> RedisResult<u8>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::getbit(key, offset)).await
+    }
+
+    /// Returns the substring of the string value stored at key, determined by the offsets start and end (both are inclusive).
+    pub async fn getrange<K>(&self, key: K, start: i64, end: i64) -> RedisResult<String>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::getrange(key, start, end)).await
+    }
+
+    /// Atomically sets key to value and returns the old value stored at key.
+    pub async fn getset<K, V>(&self, key: K, value: V) -> RedisResult<Option<String>>
+    where
+        K: RedisSerializationProtocol,
+        V: ToString,
+    {
+        self.call(commands::getset(key, value)).await
+    }
+
+    /// Increments the number stored at key by one.
+    pub async fn incr<K>(&self, key: K) -> RedisResult<i64>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::incr(key)).await
+    }
+
+    /// Increments the number stored at key by increment.
+    pub async fn incrby<K>(&self, key: K, increment: i64) -> RedisResult<i64>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::incrby(key, increment)).await
+    }
+
+    /// Increment the string representing a floating point number stored at key by the specified increment.
+    pub async fn incrbyfloat<K>(&self, key: K, increment: f64) -> RedisResult<f64>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::incrbyfloat(key, increment)).await
+    }
+
+    /// Returns the values of all specified keys.
+    pub async fn mget<K, V, I>(&self, keys: I) -> RedisResult<Vec<V>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::mget(keys)).await
+    }
+
+    /// Sets the given keys to their respective values.
+    pub async fn mset<K, V>(&self, kvs: Vec<(K, V)>) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::mset(kvs)).await
+    }
+
+    /// Sets the given keys to their respective values.
+    pub async fn msetnx<K, V>(&self, kvs: Vec<(K, V)>) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::msetnx(kvs)).await
+    }
+
+    /// PSETEX works exactly like SETEX with the sole difference that the expire time is specified in milliseconds instead of seconds.
+    pub async fn psetex<K, V>(&self, key: K, milliseconds: u64, value: V) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::psetex(key, milliseconds, value)).await
+    }
+
+    /// Set key to hold the string value.
+    pub async fn set<K, V>(
+        &self,
+        key: K,
+        value: V,
+        ex_seconds: Option<u64>,
+        px_milliseconds: Option<u64>,
+        nx: Option<bool>,
+        xx: Option<bool>,
+    ) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::set(key, value, ex_seconds, px_milliseconds, nx, xx))
+            .await
+    }
+
+    /// Set key to hold the string value.
+    pub async fn simple_set<K, V>(&self, key: K, value: V) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.set(key, value, None, None, None, None).await
+    }
+
+    /// Sets or clears the bit at offset in the string value stored at key.
+    pub async fn setbit<K>(&self, key: K, offset: usize, value: u8) -> RedisResult<u8>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::setbit(key, offset, value)).await
+    }
+
+    /// Set key to hold the string value and set key to timeout after a given number of seconds.
+    pub async fn setex<K, V>(&self, key: K, seconds: usize, value: V) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::setex(key, seconds, value)).await
+    }
+
+    /// Set key to hold string value if key does not exist.
+    pub async fn setnx<K, V>(&self, key: K, value: V) -> RedisResult<bool>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::setnx(key, value)).await
+    }
+
+    /// Overwrites part of the string stored at key, starting at the specified offset, for the entire length of value.
+    pub async fn setrange<K, V>(&self, key: K, offset: usize, value: V) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.call(commands::setrange(key, offset, value)).await
+    }
+
+    /// Returns the length of the string value stored at key.
+    pub async fn strlen<K>(&self, key: K) -> RedisResult<u64>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::strlen(key)).await
+    }
+
+    // Sends `data` and reads the `count` replies to it on a connection from the pool, failures
+    // are retried like `RedisClient` does.
+    async fn request(
+        &self,
+        data: &[u8],
+        count: usize,
+        retryable: bool,
+        read_timeout: Option<Duration>,
+    ) -> RedisResult<Vec<Reply>> {
+        self.request_on(data, count, retryable, read_timeout, false).await
+    }
+
+    // Like `request`, on a connection dedicated to blocking commands if `blocking`.
+    async fn request_on(
+        &self,
+        data: &[u8],
+        count: usize,
+        retryable: bool,
+        read_timeout: Option<Duration>,
+        blocking: bool,
+    ) -> RedisResult<Vec<Reply>> {
+        let policy = self.pool.retry_policy();
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let conn = if blocking {
+                self.pool.get_blocking().await
+            } else {
+                self.pool.get().await
+            };
+            let (e, sent) = match conn {
+                Ok(mut conn) => match conn.request(data, count, read_timeout).await {
+                    Ok(replies) => return Ok(replies),
+                    Err(e) => (conn.discard_if_unrecoverable(e), true),
+                },
+                Err(e) => (e, false),
+            };
+
+            if (sent && !retryable) || !policy.should_retry(&e, attempts) {
+                return Err(e);
+            }
+            tokio::time::sleep(policy.backoff(attempts)).await;
+        }
+    }
+
+    // Sends a command built by `commands`, and decodes its reply.
+    async fn call<T>(&self, call: Call<T>) -> RedisResult<T> {
+        let reply = match call.block {
+            Some(block) => self.execute_blocking(call.cmd, block).await?,
+            None => self.execute(call.cmd).await?,
+        };
+        (call.decode)(reply)
+    }
+
+    pub(crate) async fn execute(&self, cmd: Command) -> RedisResult<Reply> {
+        let mut replies = self
+            .request(&cmd.to_vec(), 1, cmd.is_retryable(), self.read_timeout)
+            .await?;
+        replies.remove(0).into_result()
+    }
+
+    // Executes a command which blocks up to `block` on the server, or forever if zero, the read
+    // timeout is extended accordingly. It's sent on a connection dedicated to blocking commands,
+    // so waiting doesn't hold up the other users of the pool.
+    async fn execute_blocking(&self, cmd: Command, block: Duration) -> RedisResult<Reply> {
+        let timeout = match block {
+            Duration::ZERO => None,
+            block => self.read_timeout.map(|timeout| timeout + block),
+        };
+        let mut replies = self
+            .request_on(&cmd.to_vec(), 1, cmd.is_retryable(), timeout, true)
+            .await?;
+        replies.remove(0).into_result()
+    }
+
+    pub(crate) async fn execute_pipeline(&self, pipeline: &Pipeline) -> RedisResult<Vec<Reply>> {
+        if pipeline.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let replies = self
+            .request(
+                &pipeline.to_vec(),
+                pipeline.reply_count(),
+                pipeline.is_retryable(),
+                self.read_timeout,
+            )
+            .await?;
+        pipeline.results(replies)
+    }
+}
@@ -0,0 +1,229 @@
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::client::Command;
+use crate::config::RedisConfig;
+use crate::connection::{init_commands, parse_reply, Reply, MAX_BULK_LENGTH};
+use crate::error::{ErrorKind, RedisError};
+use crate::RedisResult;
+
+/// A connection over `tokio::net::TcpStream`, replies are parsed by the same parser as the
+/// sync connection once fully buffered.
+pub(crate) struct AsyncConnection {
+    stream: TcpStream,
+    // read but not yet parsed
+    buffer: Vec<u8>,
+    // how far the reply at the start of the buffer has been scanned
+    scanner: FrameScanner,
+    database: u8,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    // the replies which haven't been read, e.g. because a future was dropped while waiting
+    pending: usize,
+}
+
+impl AsyncConnection {
+    pub(crate) async fn connect(config: &RedisConfig) -> RedisResult<AsyncConnection> {
+        if config.address.starts_with("unix://") {
+            return Err(RedisError::custom(
+                ErrorKind::InvalidConfig,
+                "the async client doesn't support Unix domain sockets",
+            ));
+        }
+        #[cfg(feature = "tls")]
+        if config.tls.is_some() {
+            return Err(RedisError::custom(
+                ErrorKind::InvalidConfig,
+                "the async client doesn't support TLS",
+            ));
+        }
+
+        let stream = with_timeout(config.connect_timeout, TcpStream::connect(&config.address)).await?;
+        Ok(AsyncConnection {
+            stream,
+            buffer: Vec::new(),
+            scanner: FrameScanner::default(),
+            database: 0,
+            read_timeout: config.read_timeout,
+            write_timeout: config.write_timeout,
+            pending: 0,
+        })
+    }
+
+    /// Prepares a new connection according to the config, like the sync connection does.
+    pub(crate) async fn init(&mut self, config: &RedisConfig) -> RedisResult<()> {
+        for cmd in init_commands(config) {
+            self.execute(&cmd).await?;
+        }
+        self.database = config.database;
+        Ok(())
+    }
+
+    pub(crate) fn database(&self) -> u8 {
+        self.database
+    }
+
+    pub(crate) async fn select(&mut self, database: u8) -> RedisResult<()> {
+        let mut cmd = Command::new("SELECT");
+        self.execute(cmd.arg(database)).await?;
+        self.database = database;
+        Ok(())
+    }
+
+    pub(crate) async fn ping(&mut self) -> RedisResult<()> {
+        self.execute(&Command::new("PING")).await?;
+        Ok(())
+    }
+
+    /// Whether a reply is still to be read, the connection can't be reused then.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.pending > 0
+    }
+
+    async fn execute(&mut self, cmd: &Command) -> RedisResult<Reply> {
+        self.send(&cmd.to_vec(), 1).await?;
+        self.read_reply(self.read_timeout).await?.into_result()
+    }
+
+    /// Sends `data` and reads the `count` replies it's answered with, error replies included,
+    /// waiting up to `read_timeout` for each of them instead of the configured read timeout.
+    pub(crate) async fn request(
+        &mut self,
+        data: &[u8],
+        count: usize,
+        read_timeout: Option<Duration>,
+    ) -> RedisResult<Vec<Reply>> {
+        self.send(data, count).await?;
+
+        let mut replies = Vec::with_capacity(count);
+        for _ in 0..count {
+            replies.push(self.read_reply(read_timeout).await?);
+        }
+        Ok(replies)
+    }
+
+    async fn send(&mut self, data: &[u8], count: usize) -> RedisResult<()> {
+        self.pending += count;
+        with_timeout(self.write_timeout, self.stream.write_all(data)).await
+    }
+
+    // Reads one reply, skipping out of band data like the sync connection does.
+    async fn read_reply(&mut self, read_timeout: Option<Duration>) -> RedisResult<Reply> {
+        loop {
+            match with_timeout(read_timeout, self.parse_reply()).await? {
+                Reply::Push(_) => continue,
+                reply => {
+                    self.pending -= 1;
+                    return Ok(reply);
+                }
+            }
+        }
+    }
+
+    // Reads from the stream until the buffer holds a complete reply, then parses it.
+    async fn parse_reply(&mut self) -> RedisResult<Reply> {
+        loop {
+            if let Some(len) = self.scanner.scan(&self.buffer) {
+                let reply = parse_reply(&mut &self.buffer[..len]);
+                self.buffer.drain(..len);
+                return reply;
+            }
+
+            self.buffer.reserve(8 * 1024);
+            if self.stream.read_buf(&mut self.buffer).await? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "connection closed by the server").into());
+            }
+        }
+    }
+}
+
+// Finds the end of the reply at the start of the buffer from the headers of its elements,
+// resuming where the last scan stopped, so a reply arriving in many reads is only scanned once.
+#[derive(Default)]
+struct FrameScanner {
+    // the end of the elements scanned so far
+    offset: usize,
+    // the elements still to be scanned, zero before the scan starts
+    remaining: usize,
+    // where to resume looking for the end of the current header line
+    searched: usize,
+}
+
+impl FrameScanner {
+    // Returns the length of the reply once `buffer` holds all of it, and starts over then.
+    fn scan(&mut self, buffer: &[u8]) -> Option<usize> {
+        if self.remaining == 0 {
+            self.remaining = 1;
+        }
+
+        while self.remaining > 0 {
+            let start = self.searched.max(self.offset);
+            let line_end = match buffer[start..].iter().position(|&b| b == b'\n') {
+                Some(position) => start + position + 1,
+                None => {
+                    self.searched = buffer.len();
+                    return None;
+                }
+            };
+            self.searched = line_end - 1;
+
+            // a malformed header is left to the parser to report
+            let line = &buffer[self.offset..line_end];
+            if line.len() < 3 || !line.ends_with(b"\r\n") {
+                *self = FrameScanner::default();
+                return Some(line_end);
+            }
+            let len = std::str::from_utf8(&line[1..line.len() - 2])
+                .ok()
+                .and_then(|len| len.parse::<i64>().ok())
+                .unwrap_or(-1);
+
+            let end = match line[0] {
+                // an oversized bulk string is left to the parser to reject, not buffered
+                b'$' | b'!' | b'=' if len > MAX_BULK_LENGTH => {
+                    *self = FrameScanner::default();
+                    return Some(line_end);
+                }
+                b'$' | b'!' | b'=' if len >= 0 => line_end + len as usize + 2,
+                _ => line_end,
+            };
+            if end > buffer.len() {
+                // the header line is found again right away once more has been read
+                return None;
+            }
+            self.remaining -= 1;
+            self.offset = end;
+
+            match line[0] {
+                b'*' | b'~' | b'>' if len > 0 => self.remaining += len as usize,
+                b'%' if len > 0 => self.remaining += 2 * len as usize,
+                // the attributes are followed by the reply they describe
+                b'|' => self.remaining += 2 * len.max(0) as usize + 1,
+                _ => {}
+            }
+        }
+
+        let len = self.offset;
+        *self = FrameScanner::default();
+        Some(len)
+    }
+}
+
+// Awaits `future`, failing with a `TimedOut` I/O error once `timeout` has passed.
+async fn with_timeout<F, T, E>(timeout: Option<Duration>, future: F) -> RedisResult<T>
+where
+    F: Future<Output = Result<T, E>>,
+    E: Into<RedisError>,
+{
+    let result = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "operation timed out"))?,
+        None => future.await,
+    };
+    result.map_err(Into::into)
+}
@@ -0,0 +1,7 @@
+//! An async client on tokio, enabled by the `tokio` feature.
+
+mod client;
+mod connection;
+mod pool;
+
+pub use client::AsyncRedisClient;
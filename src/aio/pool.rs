@@ -0,0 +1,205 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::aio::connection::AsyncConnection;
+use crate::config::{RedisConfig, RetryPolicy};
+use crate::error::{ErrorKind, RedisError};
+use crate::pool::{elapsed, Idle};
+use crate::RedisResult;
+
+/// The async counterpart of `ConnectionPool`, a task waiting for a connection doesn't block
+/// its thread.
+pub(crate) struct AsyncConnectionPool {
+    state: Mutex<State>,
+    // one permit per connection which may be checked out
+    permits: Arc<Semaphore>,
+    // the same, for the connections dedicated to blocking commands
+    blocking_permits: Arc<Semaphore>,
+    retry: RetryPolicy,
+}
+
+struct State {
+    config: RedisConfig,
+    // the most recently used last
+    idles: Vec<Idle<AsyncConnection>>,
+    // idle connections dedicated to blocking commands, they take a blocking permit
+    blocking: Vec<Idle<AsyncConnection>>,
+}
+
+impl AsyncConnectionPool {
+    pub(crate) fn new(config: RedisConfig) -> AsyncConnectionPool {
+        let permits = Arc::new(Semaphore::new(config.pool_capacity.max(1)));
+        let blocking_permits = Arc::new(Semaphore::new(config.blocking_pool_capacity.max(1)));
+        let retry = config.retry.clone();
+        AsyncConnectionPool {
+            state: Mutex::new(State {
+                config,
+                idles: Vec::new(),
+                blocking: Vec::new(),
+            }),
+            permits,
+            blocking_permits,
+            retry,
+        }
+    }
+
+    pub(crate) fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// Sets the database new connections select, idle ones switch to it on checkout.
+    pub(crate) fn select(&self, database: u8) {
+        self.lock().config.database = database;
+    }
+
+    /// Checks out a connection, like `ConnectionPool::get` does.
+    pub(crate) async fn get(self: &Arc<Self>) -> RedisResult<AsyncPooledConnection> {
+        let permit = self.acquire(&self.permits).await?;
+        self.checkout(false, permit).await
+    }
+
+    /// Checks out a connection dedicated to commands blocking on the server, like
+    /// `ConnectionPool::get_blocking` does.
+    pub(crate) async fn get_blocking(self: &Arc<Self>) -> RedisResult<AsyncPooledConnection> {
+        let permit = self.acquire(&self.blocking_permits).await?;
+        self.checkout(true, permit).await
+    }
+
+    // Waits up to `pool_checkout_timeout` for a permit.
+    async fn acquire(&self, permits: &Arc<Semaphore>) -> RedisResult<OwnedSemaphorePermit> {
+        let checkout_timeout = self.lock().config.pool_checkout_timeout;
+        match checkout_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, permits.clone().acquire_owned())
+                .await
+                .map_err(|_| RedisError::custom(ErrorKind::PoolTimeout, "timed out waiting for a connection"))?,
+            None => permits.clone().acquire_owned().await,
+        }
+        .map_err(|_| RedisError::custom(ErrorKind::ClientError, "Connection pool closed"))
+    }
+
+    // Takes an idle connection, or opens a new one.
+    async fn checkout(
+        self: &Arc<Self>,
+        blocking: bool,
+        permit: OwnedSemaphorePermit,
+    ) -> RedisResult<AsyncPooledConnection> {
+        let (conn, created) = loop {
+            let (idle, database, health_check) = {
+                let mut state = self.lock();
+                let now = Instant::now();
+                let State {
+                    config,
+                    idles,
+                    blocking: blocking_idles,
+                } = &mut *state;
+                idles.retain(|idle| !idle.expired(config, now));
+                blocking_idles.retain(|idle| !idle.expired(config, now));
+                let idle = if blocking { blocking_idles.pop() } else { idles.pop() };
+                let health_check = idle
+                    .as_ref()
+                    .is_some_and(|idle| elapsed(idle.since, now, config.pool_health_check_after));
+                (idle, config.database, health_check)
+            };
+
+            let mut idle = match idle {
+                Some(idle) => idle,
+                None => {
+                    let config = self.lock().config.clone();
+                    let mut conn = AsyncConnection::connect(&config).await?;
+                    conn.init(&config).await?;
+                    break (conn, Instant::now());
+                }
+            };
+            // the server closed it, or it's otherwise broken
+            if health_check && idle.conn.ping().await.is_err() {
+                continue;
+            }
+            // the target database has changed since the connection was idle
+            if idle.conn.database() != database {
+                idle.conn.select(database).await?;
+            }
+            break (idle.conn, idle.created);
+        };
+
+        Ok(AsyncPooledConnection {
+            conn: Some(conn),
+            created,
+            blocking,
+            pool: self.clone(),
+            _permit: permit,
+        })
+    }
+
+    fn put(&self, conn: AsyncConnection, created: Instant, blocking: bool) {
+        let mut state = self.lock();
+        if elapsed(created, Instant::now(), state.config.pool_max_lifetime) {
+            return;
+        }
+        if blocking {
+            state.blocking.push(Idle::new(conn, created));
+        } else {
+            state.idles.push(Idle::new(conn, created));
+        }
+    }
+
+    // A panic while holding the lock can't leave the state inconsistent, so poisoning is ignored.
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A connection checked out of the async pool, it's put back when dropped unless a reply is
+/// still to be read, e.g. because the future sending the command was dropped.
+pub(crate) struct AsyncPooledConnection {
+    conn: Option<AsyncConnection>,
+    created: Instant,
+    // dedicated to blocking commands
+    blocking: bool,
+    pool: Arc<AsyncConnectionPool>,
+    // released once the connection is back in the pool
+    _permit: OwnedSemaphorePermit,
+}
+
+impl AsyncPooledConnection {
+    /// Closes the connection instead of putting it back, and returns the error.
+    pub(crate) fn discard(&mut self, e: RedisError) -> RedisError {
+        self.conn.take();
+        e
+    }
+
+    /// Closes the connection if `e` left it unusable, and returns the error.
+    pub(crate) fn discard_if_unrecoverable(&mut self, e: RedisError) -> RedisError {
+        if e.is_unrecoverable() {
+            self.discard(e)
+        } else {
+            e
+        }
+    }
+}
+
+impl Deref for AsyncPooledConnection {
+    type Target = AsyncConnection;
+
+    fn deref(&self) -> &AsyncConnection {
+        self.conn.as_ref().expect("connection discarded")
+    }
+}
+
+impl DerefMut for AsyncPooledConnection {
+    fn deref_mut(&mut self) -> &mut AsyncConnection {
+        self.conn.as_mut().expect("connection discarded")
+    }
+}
+
+impl Drop for AsyncPooledConnection {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            if !conn.is_pending() {
+                self.pool.put(conn, self.created, self.blocking);
+            }
+        }
+    }
+}
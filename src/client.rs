@@ -1,35 +1,153 @@
 use std::collections::HashSet;
 use std::hash::Hash;
-
-use crate::config::RedisConfig;
-use crate::connection::Reply;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+#[cfg(feature = "tokio")]
+use crate::aio::AsyncRedisClient;
+use crate::commands::{self, Call};
+use crate::config::{RedisConfig, ToRedisConnectionConfig};
+use crate::connection::{Connection, Reply};
 use crate::error::{ErrorKind, RedisError};
 use crate::pipeline::Pipeline;
-use crate::pool::ConnectionPool;
+use crate::pool::{ConnectionPool, PooledConnection};
 use crate::protocol::{RedisDeserializationProtocol, RedisSerializationProtocol};
+use crate::scan::Scan;
+use crate::sorted_sets::{LexBound, ScoreBound, ZAddOptions, ZAggregateOptions, ZRange};
+use crate::streams::{
+    AutoClaim, ConsumerInfo, GroupInfo, PendingEntry, PendingRange, PendingSummary, ReadFrom, StreamBound, StreamEntry,
+    StreamId, StreamInfo, StreamKey, StreamTrim, XAddOptions, XReadOptions,
+};
 use crate::{DataType, RedisResult};
 
+/// Builds a command the client doesn't wrap, e.g. a module command.
+///
+/// ```rust,no_run
+/// # use redisclient::{cmd, RedisClient, RedisResult};
+/// # fn run() -> RedisResult<()> {
+/// let client = RedisClient::new()?;
+/// let encoding: String = cmd("OBJECT").arg("ENCODING").arg("key").query(&client)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn cmd<S: ToString>(name: S) -> Command {
+    Command::new(name)
+}
+
+// The commands which may have another effect, or reply, when sent twice. Those which are only
+// with some arguments, e.g. ZADD INCR or SET NX, are marked `non_retryable` where they're built.
+const NON_IDEMPOTENT_COMMANDS: &[&str] = &[
+    "APPEND",
+    "BLMOVE",
+    "BLMPOP",
+    "BLPOP",
+    "BRPOP",
+    "BRPOPLPUSH",
+    "BZMPOP",
+    "BZPOPMAX",
+    "BZPOPMIN",
+    "DECR",
+    "DECRBY",
+    "EVAL",
+    "EVALSHA",
+    "FCALL",
+    "GETDEL",
+    "GETSET",
+    "HINCRBY",
+    "HINCRBYFLOAT",
+    "HSETNX",
+    "INCR",
+    "INCRBY",
+    "INCRBYFLOAT",
+    "LINSERT",
+    "LMOVE",
+    "LMPOP",
+    "LPOP",
+    "LPUSH",
+    "LPUSHX",
+    "LREM",
+    "MSETNX",
+    "PUBLISH",
+    "RENAME",
+    "RENAMENX",
+    "RPOP",
+    "RPOPLPUSH",
+    "RPUSH",
+    "RPUSHX",
+    "SETNX",
+    "SMOVE",
+    "SPOP",
+    "XADD",
+    "XAUTOCLAIM",
+    "XCLAIM",
+    "XREADGROUP",
+    "ZINCRBY",
+    "ZMPOP",
+    "ZPOPMAX",
+    "ZPOPMIN",
+];
+
+#[derive(Clone)]
 pub struct Command {
     cmd: String,
     args: Vec<u8>,
     count: usize,
+    retryable: bool,
 }
 
 impl Command {
-    fn new<S: ToString>(cmd: S) -> Command {
+    pub fn new<S: ToString>(cmd: S) -> Command {
         let cmd = cmd.to_string();
         let args = Vec::new();
-        Command { cmd, args, count: 1 }
+        let retryable = !NON_IDEMPOTENT_COMMANDS.contains(&cmd.to_uppercase().as_str());
+        Command {
+            cmd,
+            args,
+            count: 1,
+            retryable,
+        }
+    }
+
+    /// Marks the command as unsafe to send twice, so that it isn't retried once sent,
+    /// e.g. a script which isn't idempotent. See `RetryPolicy`.
+    pub fn non_retryable(&mut self) -> &mut Self {
+        self.retryable = false;
+        self
+    }
+
+    pub(crate) fn is_retryable(&self) -> bool {
+        self.retryable
     }
 
-    fn arg<T: RedisSerializationProtocol>(&mut self, arg: T) -> &mut Self {
+    /// Appends an argument, a collection is appended as one argument per item.
+    pub fn arg<T: RedisSerializationProtocol>(&mut self, arg: T) -> &mut Self {
         self.args.extend(arg.serialization());
-        self.count += 1;
+        self.count += arg.count();
         self
     }
 
-    fn into_vec(self) -> Vec<u8> {
-        let Command { cmd, args, count } = self;
+    /// Sends the command and deserializes its reply.
+    pub fn query<T>(&self, client: &RedisClient) -> RedisResult<T>
+    where
+        T: RedisDeserializationProtocol,
+    {
+        let reply = client.execute(self.clone())?;
+        <T>::deserialization(reply)
+    }
+
+    /// Sends the command with the async client and deserializes its reply.
+    #[cfg(feature = "tokio")]
+    pub async fn query_async<T>(&self, client: &AsyncRedisClient) -> RedisResult<T>
+    where
+        T: RedisDeserializationProtocol,
+    {
+        let reply = client.execute(self.clone()).await?;
+        <T>::deserialization(reply)
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<u8> {
+        let Command { cmd, args, count, .. } = self;
 
         let mut buf = Vec::new();
         buf.extend(Vec::from(format!("*{}\r\n", count)));
@@ -49,14 +167,64 @@ macro_rules! command {
     };
 }
 
-// TODO: remove
+pub(crate) use command;
+
+/// Where LINSERT inserts the element, relative to the pivot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ListBeforeOrAfter {
     Before,
     After,
 }
 
+/// The end of a list elements are popped from or pushed to, `Left` being the head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListDirection {
+    Left,
+    Right,
+}
+
+/// The RANK and MAXLEN options of LPOS.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LPosOptions {
+    rank: Option<isize>,
+    maxlen: Option<usize>,
+}
+
+impl LPosOptions {
+    pub fn new() -> LPosOptions {
+        LPosOptions::default()
+    }
+
+    /// Skips the first `rank - 1` matches, a negative rank searches from the tail.
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = Some(rank);
+        self
+    }
+
+    /// Compares at most maxlen elements, zero meaning all of them.
+    pub fn maxlen(mut self, maxlen: usize) -> Self {
+        self.maxlen = Some(maxlen);
+        self
+    }
+
+    pub(crate) fn args(&self) -> Vec<Vec<u8>> {
+        let mut args = Vec::new();
+        if let Some(rank) = self.rank {
+            args.extend(vec!["RANK".serialization(), rank.serialization()]);
+        }
+        if let Some(maxlen) = self.maxlen {
+            args.extend(vec!["MAXLEN".serialization(), maxlen.serialization()]);
+        }
+        args
+    }
+}
+
+/// A client backed by a pool of connections, it can be shared between threads.
 pub struct RedisClient {
-    pool: ConnectionPool,
+    pool: Arc<ConnectionPool>,
+    // the connection used by every command of a client returned by `pin`
+    pinned: Option<Arc<Mutex<PooledConnection>>>,
+    read_timeout: Option<Duration>,
 }
 
 impl RedisClient {
@@ -66,331 +234,379 @@ impl RedisClient {
         Self::with_config(config)
     }
 
+    /// Creates a client from a `RedisConfig` or a URL, e.g. `redis://:password@localhost:6379/1`.
+    pub fn open<C: ToRedisConnectionConfig>(config: C) -> RedisResult<RedisClient> {
+        Self::with_config(config.to_redis_connection_config()?)
+    }
+
+    /// Creates a client, every connection of its pool authenticates, names itself and
+    /// selects the database as the config says when it's opened. The TLS config is checked
+    /// here, so that a certificate file which can't be read is reported right away.
     pub fn with_config(config: RedisConfig) -> RedisResult<RedisClient> {
-        let RedisConfig {
-            address,
-            database,
-            username,
-            password,
-            pool_capacity,
-        } = config;
-
-        let mut client = RedisClient {
-            pool: ConnectionPool::new(pool_capacity, address),
-        };
+        Ok(RedisClient {
+            read_timeout: config.read_timeout,
+            pool: Arc::new(ConnectionPool::new(config)?),
+            pinned: None,
+        })
+    }
 
-        if let Some(password) = password {
-            client.auth(username, password)?;
+    /// Checks out a connection, and returns a client sending every command over it, e.g. to
+    /// rely on per connection state. The connection goes back to the pool when it's dropped.
+    pub fn pin(&self) -> RedisResult<RedisClient> {
+        if self.pinned.is_some() {
+            return Err(RedisError::custom(
+                ErrorKind::ClientError,
+                "client is already pinned to a connection",
+            ));
         }
 
-        if database > 0 {
-            client.select(database)?;
-        }
+        Ok(RedisClient {
+            pool: self.pool.clone(),
+            pinned: Some(Arc::new(Mutex::new(self.pool.get()?))),
+            read_timeout: self.read_timeout,
+        })
+    }
 
-        Ok(client)
+    /// Returns a client sharing the connections of this one, whose commands wait for their reply
+    /// up to `read_timeout` rather than the configured one, e.g. to bound a blocking command.
+    ///
+    /// A blocking command, like `brpoplpush`, still gets its timeout on top of it.
+    pub fn with_read_timeout(&self, read_timeout: Option<Duration>) -> RedisClient {
+        RedisClient {
+            pool: self.pool.clone(),
+            pinned: self.pinned.clone(),
+            read_timeout,
+        }
     }
 
     pub fn pipe(&self) -> Pipeline {
-        Pipeline::new(self)
+        Pipeline::new()
     }
 
     pub fn pipe_with_capacity(&self, capacity: usize) -> Pipeline {
-        Pipeline::with_capacity(self, capacity)
+        Pipeline::with_capacity(capacity)
     }
 
-    // TODO
-    pub fn flushall(&mut self) -> RedisResult<()> {
-        let cmd = Command::new("FLUSHALL");
+    /// Runs an optimistic locking transaction.
+    ///
+    /// The keys are watched on a dedicated connection, which `func` uses to read the current
+    /// state and to fill the transactional pipeline. The pipeline is then executed, and the
+    /// whole process is retried if one of the watched keys has been modified meanwhile.
+    ///
+    /// ```rust,no_run
+    /// # use redisclient::{RedisClient, RedisResult};
+    /// # fn run() -> RedisResult<()> {
+    /// let client = RedisClient::new()?;
+    /// let (value,): (i64,) = client.transaction(vec!["key"], |conn, pipe| {
+    ///     let value: i64 = conn.get("key")?;
+    ///     pipe.simple_set("key", value + 1).get("key");
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transaction<K, T, F, I>(&self, keys: I, mut func: F) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+        F: FnMut(&RedisClient, &mut Pipeline) -> RedisResult<()>,
+        I: IntoIterator<Item = K>,
+    {
+        if self.pinned.is_some() {
+            return Err(RedisError::custom(
+                ErrorKind::ClientError,
+                "transaction can't be nested",
+            ));
+        }
 
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        let mut watch = Command::new("WATCH");
+        for key in keys {
+            watch.arg(key);
+        }
+
+        let conn = self.pin()?;
+        // a failed transaction may leave keys watched, the connection is dropped in that case
+        conn.run_transaction(&watch, &mut func).map_err(|e| conn.discard(e))
+    }
+
+    fn run_transaction<T, F>(&self, watch: &Command, func: &mut F) -> RedisResult<T>
+    where
+        T: RedisDeserializationProtocol,
+        F: FnMut(&RedisClient, &mut Pipeline) -> RedisResult<()>,
+    {
+        loop {
+            let reply = self.execute(watch.clone())?;
+            <()>::deserialization(reply)?;
+
+            let mut pipe = Pipeline::new();
+            pipe.transaction_mode();
+            func(self, &mut pipe)?;
+
+            if pipe.is_empty() {
+                self.unwatch()?;
+            }
+            match pipe.query(self) {
+                Err(e) if e.kind() == ErrorKind::TransactionAborted => continue,
+                result => return result,
+            }
+        }
+    }
+
+    // TODO
+    pub fn flushall(&self) -> RedisResult<()> {
+        self.call(commands::flushall())
     }
 
     // Connection commands
     /// The AUTH command authenticates the current connection
     ///
     /// Return value: Simple string reply
-    pub fn auth<S>(&mut self, username: Option<S>, password: S) -> RedisResult<()>
+    pub fn auth<S>(&self, username: Option<S>, password: S) -> RedisResult<()>
     where
         S: ToString,
     {
-        let mut cmd = Command::new("AUTH");
-        if let Some(username) = username {
-            cmd.arg(username.to_string());
-        }
-        cmd.arg(password.to_string());
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::auth(username, password))
     }
 
     /// Returns message.
     ///
     /// Return value: Bulk string reply
-    pub fn echo<S>(&mut self, message: S) -> RedisResult<String>
+    pub fn echo<S>(&self, message: S) -> RedisResult<String>
     where
         S: ToString,
     {
-        let cmd = command!("ECHO"; args => message.to_string());
-        let reply = self.execute(cmd)?;
-        <String>::deserialization(reply)
+        self.call(commands::echo(message))
     }
 
     /// Returns PONG if no argument is provided, otherwise return a copy of the argument as a bulk.
     ///
     /// Return value: Simple string reply
-    pub fn ping(&mut self) -> RedisResult<()> {
-        let cmd = Command::new("PING");
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+    pub fn ping(&self) -> RedisResult<()> {
+        self.call(commands::ping())
     }
 
     /// Ask the server to close the connection.
     ///
     /// Return value: Simple string reply
-    pub fn quit(&mut self) -> RedisResult<()> {
-        let cmd = Command::new("QUIT");
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+    pub fn quit(&self) -> RedisResult<()> {
+        self.call(commands::quit())
     }
 
     /// Select the Redis logical database having the specified zero-based numeric index.
     ///
+    /// Every connection of the client switches to it, not only the one the command is sent on.
+    ///
     /// Return value: Simple string reply
-    pub fn select(&mut self, index: u8) -> RedisResult<()> {
-        let cmd = command!("SELECT"; args => index);
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+    pub fn select(&self, index: u8) -> RedisResult<()> {
+        self.with_connection(true, |conn| conn.select(index))?;
+        self.pool.select(index);
+        Ok(())
     }
 
     // Hashes commands
     /// Removes the specified fields from the hash stored at key.
     ///
     /// Return value: Integer reply
-    pub fn hdel<K>(&mut self, key: K, fields: Vec<K>) -> RedisResult<usize>
+    pub fn hdel<K, I>(&self, key: K, fields: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = command!("HDEL"; args => key);
-        for field in fields {
-            cmd.arg(field);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::hdel(key, fields))
     }
 
     /// Returns if field is an existing field in the hash stored at key.
     ///
     /// Return value: Integer reply
-    pub fn hexists<K, F>(&mut self, key: K, field: F) -> RedisResult<bool>
+    pub fn hexists<K, F>(&self, key: K, field: F) -> RedisResult<bool>
     where
         K: RedisSerializationProtocol,
         F: RedisSerializationProtocol,
     {
-        let cmd = command!("HEXISTS"; args => key, field);
-
-        let reply = self.execute(cmd)?;
-        <bool>::deserialization(reply)
+        self.call(commands::hexists(key, field))
     }
 
     /// Returns the value associated with field in the hash stored at key.
     ///
     /// Return value: Bulk string reply
-    pub fn hget<K, F, V>(&mut self, key: K, field: F) -> RedisResult<V>
+    pub fn hget<K, F, V>(&self, key: K, field: F) -> RedisResult<V>
     where
         K: RedisSerializationProtocol,
         F: RedisSerializationProtocol,
         V: RedisDeserializationProtocol,
     {
-        let cmd = command!("HGET"; args => key, field);
-        let reply = self.execute(cmd)?;
-        <V>::deserialization(reply)
+        self.call(commands::hget(key, field))
     }
 
     /// Returns all fields and values of the hash stored at key.
     ///
     /// Return value: Array reply
-    pub fn hgetall<K, M>(&mut self, key: K) -> RedisResult<M>
+    pub fn hgetall<K, M>(&self, key: K) -> RedisResult<M>
     where
         K: RedisSerializationProtocol,
         M: RedisDeserializationProtocol,
     {
-        let cmd = command!("HGETALL"; args => key);
-        let reply = self.execute(cmd)?;
-        <M>::deserialization(reply)
+        self.call(commands::hgetall(key))
     }
 
     /// Increments the number stored at field in the hash stored at key by increment.
     ///
     /// Return value: Integer value
-    pub fn hincrby<K, F>(&mut self, key: K, field: F, increment: i64) -> RedisResult<i64>
+    pub fn hincrby<K, F>(&self, key: K, field: F, increment: i64) -> RedisResult<i64>
     where
         K: RedisSerializationProtocol,
         F: RedisSerializationProtocol,
     {
-        let cmd = command!("HINCRBY"; args => key, field, increment);
-        let reply = self.execute(cmd)?;
-        <i64>::deserialization(reply)
+        self.call(commands::hincrby(key, field, increment))
     }
 
     /// Increment the specified field of a hash stored at key, and representing a floating point number, by the specified increment.
     ///
     /// Return value: Bulk string reply
-    pub fn hincrbyfloat<K, F>(&mut self, key: K, field: F, increment: f64) -> RedisResult<f64>
+    pub fn hincrbyfloat<K, F>(&self, key: K, field: F, increment: f64) -> RedisResult<f64>
     where
         K: RedisSerializationProtocol,
         F: RedisSerializationProtocol,
     {
-        let cmd = command!("HINCRBYFLOAT"; args => key, field, increment);
-        let reply = self.execute(cmd)?;
-        <f64>::deserialization(reply)
+        self.call(commands::hincrbyfloat(key, field, increment))
     }
 
     /// Returns all field names in the hash stored at key.
     ///
     /// Return value: Array reply
-    pub fn hkeys<K, V>(&mut self, key: K) -> RedisResult<Vec<V>>
+    pub fn hkeys<K, V>(&self, key: K) -> RedisResult<Vec<V>>
     where
         K: RedisSerializationProtocol,
         V: RedisDeserializationProtocol,
     {
-        let cmd = command!("HKEYS"; args => key);
-        let reply = self.execute(cmd)?;
-        <Vec<V>>::deserialization(reply)
+        self.call(commands::hkeys(key))
     }
 
     /// Returns the number of fields contained in the hash stored at key.
     ///
     /// Return value: Integer reply
-    pub fn hlen<K>(&mut self, key: K) -> RedisResult<u64>
+    pub fn hlen<K>(&self, key: K) -> RedisResult<u64>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("HLEN"; args => key);
-        let reply = self.execute(cmd)?;
-        <u64>::deserialization(reply)
+        self.call(commands::hlen(key))
     }
 
     /// Returns the values associated with the specified fields in the hash stored at key.
     ///
     /// Return value: Array reply
-    pub fn hmget<K, F, V>(&mut self, key: K, fields: Vec<F>) -> RedisResult<Vec<V>>
+    pub fn hmget<K, F, V, I>(&self, key: K, fields: I) -> RedisResult<Vec<V>>
     where
         K: RedisSerializationProtocol,
         F: RedisSerializationProtocol,
         V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = F>,
     {
-        let mut cmd = command!("HMGET"; args => key);
-        for field in fields {
-            cmd.arg(field);
-        }
-        let reply = self.execute(cmd)?;
-        <Vec<V>>::deserialization(reply)
+        self.call(commands::hmget(key, fields))
     }
 
     /// Sets the specified fields to their respective values in the hash stored at key.
     ///
     /// Return values: Simple string reply
-    pub fn hmset<K, F, V>(&mut self, key: K, fvs: Vec<(F, V)>) -> RedisResult<()>
+    pub fn hmset<K, F, V>(&self, key: K, fvs: Vec<(F, V)>) -> RedisResult<()>
     where
         K: RedisSerializationProtocol,
         F: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let mut cmd = command!("HMSET"; args => key);
-        for (field, value) in fvs {
-            cmd.arg(field).arg(value);
-        }
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::hmset(key, fvs))
+    }
+
+    /// Iterates over the fields and values of the hash stored at key, e.g. as `(String, String)` pairs.
+    pub fn hscan<K, T>(&self, key: K) -> Scan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        Scan::new(self, command!("HSCAN"; args => key), None)
     }
 
-    pub fn hscan(&mut self) {
-        todo!();
+    /// Iterates over the fields matching pattern and their values, see `hscan`.
+    pub fn hscan_match<K, S, T>(&self, key: K, pattern: S) -> Scan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        S: ToString,
+        T: RedisDeserializationProtocol,
+    {
+        Scan::new(self, command!("HSCAN"; args => key), Some(pattern.to_string()))
     }
 
-    /// Sets field in the hash stored at key to value.
+    /// Sets the specified fields to their respective values in the hash stored at key.
     ///
     /// Return value: Integer reply
-    pub fn hset<K, F, V>(&mut self, key: K, field: F, value: V) -> RedisResult<usize>
+    pub fn hset<K, F, V, I>(&self, key: K, fvs: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         F: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
+        I: IntoIterator<Item = (F, V)>,
     {
-        let cmd = command!("HSET"; args => key, field, value);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::hset(key, fvs))
     }
 
     /// Sets field in the hash stored at key to value, only if field does not yet exist.
     ///
     /// Return value: Integer value
-    pub fn hsetnx<K, F, V>(&mut self, key: K, field: F, value: V) -> RedisResult<usize>
+    pub fn hsetnx<K, F, V>(&self, key: K, field: F, value: V) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         F: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let cmd = command!("HSETNX"; args => key, field, value);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::hsetnx(key, field, value))
     }
 
     /// Returns the string length of the value associated with field in the hash stored at key.
     ///
     /// Return value: Integer reply
-    pub fn hstrlen<K, F>(&mut self, key: K, field: F) -> RedisResult<usize>
+    pub fn hstrlen<K, F>(&self, key: K, field: F) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         F: RedisSerializationProtocol,
     {
-        let cmd = command!("HSTRLEN"; args => key, field);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::hstrlen(key, field))
     }
 
     /// Returns all values in the hash stored at key.
     ///
     /// Return value: Array reply
-    pub fn hvals<K, V>(&mut self, key: K) -> RedisResult<Vec<V>>
+    pub fn hvals<K, V>(&self, key: K) -> RedisResult<Vec<V>>
     where
         K: RedisSerializationProtocol,
         V: RedisDeserializationProtocol,
     {
-        let cmd = command!("HVALS"; args => key);
-        let reply = self.execute(cmd)?;
-        <Vec<V>>::deserialization(reply)
+        self.call(commands::hvals(key))
     }
 
     // keys command
     /// This command copies the value stored at the source key to the destination key.
     ///
     /// Return value: Integer reply
-    pub fn copy(&mut self) -> RedisResult<()> {
+    pub fn copy(&self) -> RedisResult<()> {
         todo!()
     }
 
     /// Removes the specified keys. A key is ignored if it does not exist.
     ///
     /// Return value: Integer reply
-    pub fn del<K>(&mut self, keys: Vec<K>) -> RedisResult<usize>
+    pub fn del<K, I>(&self, keys: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = Command::new("DEL");
-        for key in keys {
-            cmd.arg(key);
-        }
-
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::del(keys))
     }
 
     /// Serialize the value stored at key in a Redis-specific format and return it to the user.
     ///
     /// Return value: Bulk string reply
     #[allow(unused_variables)]
-    pub fn dump<K>(&mut self, key: K) -> RedisResult<String>
+    pub fn dump<K>(&self, key: K) -> RedisResult<String>
     where
         K: RedisSerializationProtocol,
     {
@@ -405,36 +621,29 @@ impl RedisClient {
     /// Returns if key exists.
     ///
     /// Return value: Integer reply
-    pub fn exists<K>(&mut self, keys: Vec<K>) -> RedisResult<usize>
+    pub fn exists<K, I>(&self, keys: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = Command::new("EXISTS");
-        for key in keys {
-            cmd.arg(key);
-        }
-
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::exists(keys))
     }
 
     /// Set a timeout on key. After the timeout has expired, the key will automatically be deleted.
     ///
     /// Return value: Integer reply
-    pub fn expire<K>(&mut self, key: K, seconds: usize) -> RedisResult<bool>
+    pub fn expire<K>(&self, key: K, seconds: usize) -> RedisResult<bool>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("EXPIRE"; args => key, seconds);
-        let reply = self.execute(cmd)?;
-        <bool>::deserialization(reply)
+        self.call(commands::expire(key, seconds))
     }
 
     /// EXPIREAT has the same effect and semantic as EXPIRE, but instead of specifying the number of seconds representing the TTL, it takes an absolute Unix timestamp.
     ///
     /// Return value: Integer reply
     #[allow(unused_variables)]
-    pub fn expireat<K>(&mut self, key: K, timestamp: u64) -> RedisResult<bool>
+    pub fn expireat<K>(&self, key: K, timestamp: u64) -> RedisResult<bool>
     where
         K: RedisSerializationProtocol,
     {
@@ -444,841 +653,1378 @@ impl RedisClient {
     /// Returns all keys matching pattern.
     ///
     /// Return value: Array reply
-    pub fn keys<S>(&mut self, pattern: S) -> RedisResult<Vec<String>>
+    pub fn keys<S>(&self, pattern: S) -> RedisResult<Vec<String>>
     where
         S: ToString,
     {
-        let cmd = command!("KEYS"; args => pattern.to_string());
-        let reply = self.execute(cmd)?;
-        <Vec<String>>::deserialization(reply)
+        self.call(commands::keys(pattern))
     }
 
     /// Remove the existing timeout on key, turning the key from volatile to persistent.
     ///
     /// Return value: Integer reply
-    pub fn persist<K>(&mut self, key: K) -> RedisResult<bool>
+    pub fn persist<K>(&self, key: K) -> RedisResult<bool>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("PERSIST"; args => key);
-        let reply = self.execute(cmd)?;
-        <bool>::deserialization(reply)
+        self.call(commands::persist(key))
     }
 
     /// This command works exactly like EXPIRE but the time to live of the key is specified in milliseconds instead of seconds.
     ///
     /// Return value: Integer reply
-    pub fn pexpire<K>(&mut self, key: K, milliseconds: u64) -> RedisResult<bool>
+    pub fn pexpire<K>(&self, key: K, milliseconds: u64) -> RedisResult<bool>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("PEXPIRE"; args => key, milliseconds);
-        let reply = self.execute(cmd)?;
-        <bool>::deserialization(reply)
+        self.call(commands::pexpire(key, milliseconds))
     }
 
     /// Like TTL this command returns the remaining time to live of a key that has an expire set,
     /// with the sole difference that TTL returns the amount of remaining time in seconds while PTTL returns it in milliseconds.
     ///
     /// Return value: Integer reply
-    pub fn pttl<K>(&mut self, key: K) -> RedisResult<i64>
+    pub fn pttl<K>(&self, key: K) -> RedisResult<i64>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("PTTL"; args => key);
-        let reply = self.execute(cmd)?;
-        <i64>::deserialization(reply)
+        self.call(commands::pttl(key))
     }
 
     /// Return a random key from the currently selected database.
     ///
     /// Return value: Bulk string reply
-    pub fn randomkey(&mut self) -> RedisResult<String> {
-        let cmd = Command::new("RANDOMKEY");
-        let reply = self.execute(cmd)?;
-        <String>::deserialization(reply)
+    pub fn randomkey(&self) -> RedisResult<Option<String>> {
+        self.call(commands::randomkey())
     }
 
     /// Renames key to newkey. It returns an error when key does not exist.
     ///
     /// Return value: Simple string reply
-    pub fn rename<K>(&mut self, key: K, newkey: K) -> RedisResult<()>
+    pub fn rename<K>(&self, key: K, newkey: K) -> RedisResult<()>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("RENAME"; args => key, newkey);
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::rename(key, newkey))
     }
 
     /// Renames key to newkey if newkey does not yet exist. It returns an error when key does not exist.
     ///
     /// Return value: Integer reply
-    pub fn renamenx<K>(&mut self, key: K, newkey: K) -> RedisResult<bool>
+    pub fn renamenx<K>(&self, key: K, newkey: K) -> RedisResult<bool>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("RENAMENX"; args => key, newkey);
-        let reply = self.execute(cmd)?;
-        <bool>::deserialization(reply)
+        self.call(commands::renamenx(key, newkey))
+    }
+
+    /// Iterates over the keys of the database, without blocking the server like `keys` does.
+    pub fn scan<T>(&self) -> Scan<'_, T>
+    where
+        T: RedisDeserializationProtocol,
+    {
+        Scan::new(self, Command::new("SCAN"), None)
+    }
+
+    /// Iterates over the keys matching pattern, see `scan`.
+    pub fn scan_match<S, T>(&self, pattern: S) -> Scan<'_, T>
+    where
+        S: ToString,
+        T: RedisDeserializationProtocol,
+    {
+        Scan::new(self, Command::new("SCAN"), Some(pattern.to_string()))
     }
 
     /// Alters the last access time of a key(s). A key is ignored if it does not exist.
     ///
     /// Return value: Integer reply
-    pub fn touch<K>(&mut self, keys: Vec<K>) -> RedisResult<isize>
+    pub fn touch<K, I>(&self, keys: I) -> RedisResult<isize>
     where
         K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = Command::new("TOUCH");
-        for key in keys {
-            cmd.arg(key);
-        }
-        let reply = self.execute(cmd)?;
-        <isize>::deserialization(reply)
+        self.call(commands::touch(keys))
     }
 
     /// Returns the remaining time to live of a key that has a timeout.
     ///
     /// Return value: Integer reply
-    pub fn ttl<K>(&mut self, key: K) -> RedisResult<isize>
+    pub fn ttl<K>(&self, key: K) -> RedisResult<isize>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("TTL"; args => key);
-        let reply = self.execute(cmd)?;
-        <isize>::deserialization(reply)
+        self.call(commands::ttl(key))
     }
 
     /// Returns the string representation of the type of the value stored at key.
     ///
     /// Return value: Simple string reply
-    pub fn type_<K>(&mut self, key: K) -> RedisResult<DataType>
+    pub fn type_<K>(&self, key: K) -> RedisResult<DataType>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("TYPE"; args => key);
-        let reply = self.execute(cmd)?;
-        <DataType>::deserialization(reply)
+        self.call(commands::type_(key))
     }
 
     /// This command is very similar to DEL: it removes the specified keys.
     ///
     /// Return value: Integer reply
-    pub fn unlink<K>(&mut self, keys: Vec<K>) -> RedisResult<usize>
+    pub fn unlink<K, I>(&self, keys: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = Command::new("UNLINK");
-        for key in keys {
-            cmd.arg(key);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::unlink(keys))
+    }
+
+    /// BLMOVE is the blocking variant of LMOVE, it waits up to timeout for an element,
+    /// or forever if zero.
+    ///
+    /// Return value: Bulk string reply, nil when the timeout is reached
+    pub fn blmove<K, V>(
+        &self,
+        source: K,
+        destination: K,
+        wherefrom: ListDirection,
+        whereto: ListDirection,
+        timeout: Duration,
+    ) -> RedisResult<Option<V>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::blmove(source, destination, wherefrom, whereto, timeout))
+    }
+
+    /// BLMPOP is the blocking variant of LMPOP, it waits up to timeout for an element,
+    /// or forever if zero.
+    ///
+    /// Return value: Array reply, the key and the popped elements, nil when the timeout is reached
+    pub fn blmpop<K, R, V, I>(
+        &self,
+        timeout: Duration,
+        keys: I,
+        direction: ListDirection,
+        count: usize,
+    ) -> RedisResult<Option<(R, Vec<V>)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::blmpop(timeout, keys, direction, count))
+    }
+
+    /// BLPOP is the blocking variant of LPOP, it pops from the first non-empty list of keys and
+    /// waits up to timeout for an element, or forever if zero.
+    ///
+    /// Return value: Array reply, the key and the popped element, nil when the timeout is reached
+    pub fn blpop<K, R, V, I>(&self, keys: I, timeout: Duration) -> RedisResult<Option<(R, V)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::blpop(keys, timeout))
+    }
+
+    /// BRPOP is the blocking variant of RPOP, like `blpop`.
+    ///
+    /// Return value: Array reply, the key and the popped element, nil when the timeout is reached
+    pub fn brpop<K, R, V, I>(&self, keys: I, timeout: Duration) -> RedisResult<Option<(R, V)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::brpop(keys, timeout))
     }
 
     /// BRPOPLPUSH is the blocking variant of RPOPLPUSH.
     ///
     /// Return value: Bulk string reply
-    pub fn brpoplpush<K, E>(&mut self, source: K, destination: K, timeout: usize) -> RedisResult<E>
+    pub fn brpoplpush<K, E>(&self, source: K, destination: K, timeout: Duration) -> RedisResult<E>
     where
         K: RedisSerializationProtocol,
         E: RedisDeserializationProtocol,
     {
-        let cmd = command!("BRPOPLPUSH"; args => source, destination, timeout);
-        let reply = self.execute(cmd)?;
-        <E>::deserialization(reply)
+        self.call(commands::brpoplpush(source, destination, timeout))
     }
 
     // Lists commands
     /// Returns the element at index index in the list stored at key.
     ///
     /// Return value: Bulk string reply
-    pub fn lindex<K, V>(&mut self, key: K, index: isize) -> RedisResult<V>
+    pub fn lindex<K, V>(&self, key: K, index: isize) -> RedisResult<V>
     where
         K: RedisSerializationProtocol,
         V: RedisDeserializationProtocol,
     {
-        let cmd = command!("LINDEX"; args => key, index);
-        let reply = self.execute(cmd)?;
-        <V>::deserialization(reply)
+        self.call(commands::lindex(key, index))
     }
 
     /// Inserts element in the list stored at key either before or after the reference value pivot.
     ///
     /// Return value: Integer reply
-    pub fn linsert<K, E>(&mut self, key: K, operator: ListBeforeOrAfter, pivot: E, element: E) -> RedisResult<usize>
+    pub fn linsert<K, E>(&self, key: K, operator: ListBeforeOrAfter, pivot: E, element: E) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         E: RedisSerializationProtocol,
     {
-        let cmd = command!("LINSERT"; args => key, operator, pivot, element);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::linsert(key, operator, pivot, element))
     }
 
     /// Returns the length of the list stored at key.
     ///
     /// Return value: Integer reply
-    pub fn llen<K>(&mut self, key: K) -> RedisResult<usize>
+    pub fn llen<K>(&self, key: K) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("LLEN"; args => key);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::llen(key))
+    }
+
+    /// Atomically returns and removes the first or last element of the list stored at source,
+    /// and pushes it at the first or last element of the list stored at destination.
+    ///
+    /// Return value: Bulk string reply, nil when source doesn't exist
+    pub fn lmove<K, V>(
+        &self,
+        source: K,
+        destination: K,
+        wherefrom: ListDirection,
+        whereto: ListDirection,
+    ) -> RedisResult<Option<V>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::lmove(source, destination, wherefrom, whereto))
+    }
+
+    /// Pops up to count elements from the first non-empty list of keys.
+    ///
+    /// Return value: Array reply, the key and the popped elements, nil when all the lists are empty
+    pub fn lmpop<K, R, V, I>(&self, keys: I, direction: ListDirection, count: usize) -> RedisResult<Option<(R, Vec<V>)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::lmpop(keys, direction, count))
     }
 
     /// Removes and returns the first element of the list stored at key.
     ///
     /// Return value: Bulk string reply
-    pub fn lpop<K, E>(&mut self, key: K) -> RedisResult<E>
+    pub fn lpop<K, E>(&self, key: K) -> RedisResult<E>
     where
         K: RedisSerializationProtocol,
         E: RedisDeserializationProtocol,
     {
-        let cmd = command!("LPOP"; args => key);
-        let reply = self.execute(cmd)?;
-        <E>::deserialization(reply)
+        self.call(commands::lpop(key))
     }
 
-    /// The command returns the index of matching elements inside a Redis list.
-    pub fn lpos(&mut self) {
-        todo!()
+    /// Removes and returns up to count elements from the head of the list stored at key.
+    ///
+    /// Return value: Array reply, empty when key doesn't exist
+    pub fn lpop_count<K, E>(&self, key: K, count: usize) -> RedisResult<Vec<E>>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisDeserializationProtocol,
+    {
+        self.call(commands::lpop_count(key, count))
+    }
+
+    /// Returns the index of the first element matching element in the list stored at key.
+    ///
+    /// Return value: Integer reply, nil when there is no match
+    pub fn lpos<K, E>(&self, key: K, element: E, options: LPosOptions) -> RedisResult<Option<usize>>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.call(commands::lpos(key, element, options))
+    }
+
+    /// Returns the indexes of up to count elements matching element, zero meaning all of them.
+    ///
+    /// Return value: Array reply
+    pub fn lpos_count<K, E>(&self, key: K, element: E, count: usize, options: LPosOptions) -> RedisResult<Vec<usize>>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.call(commands::lpos_count(key, element, count, options))
     }
 
     /// Insert all the specified values at the head of the list stored at key.
     ///
     /// Retrun value: Integer reply
-    pub fn lpush<K, E>(&mut self, key: K, elements: Vec<E>) -> RedisResult<usize>
+    pub fn lpush<K, E, I>(&self, key: K, elements: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
     {
-        let mut cmd = command!("LPUSH"; args => key);
-        for element in elements {
-            cmd.arg(element);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::lpush(key, elements))
     }
 
     /// Inserts specified values at the head of the list stored at key, only if key already exists and holds a list.
     ///
     /// Return value: Integer value
-    pub fn lpushx<K, E>(&mut self, key: K, elements: Vec<E>) -> RedisResult<usize>
+    pub fn lpushx<K, E, I>(&self, key: K, elements: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
     {
-        let mut cmd = command!("LPUSHX"; args => key);
-        for element in elements {
-            cmd.arg(element);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::lpushx(key, elements))
     }
 
     /// Returns the specified elements of the list stored at key.
     ///
     /// Return value: Array reply
-    pub fn lrange<K, E>(&mut self, key: K, start: isize, end: isize) -> RedisResult<Vec<E>>
+    pub fn lrange<K, E>(&self, key: K, start: isize, end: isize) -> RedisResult<Vec<E>>
     where
         K: RedisSerializationProtocol,
         E: RedisDeserializationProtocol,
     {
-        let cmd = command!("LRANGE"; args => key, start, end);
-        let reply = self.execute(cmd)?;
-        <Vec<E>>::deserialization(reply)
+        self.call(commands::lrange(key, start, end))
     }
 
     /// Removes the first count occurrences of elements equal to element from the list stored at key.
     ///
     /// Return value: Integer reply
-    pub fn lrem<K, E>(&mut self, key: K, count: isize, element: E) -> RedisResult<usize>
+    pub fn lrem<K, E>(&self, key: K, count: isize, element: E) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         E: RedisSerializationProtocol,
     {
-        let cmd = command!("LREM"; args => key, count, element);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::lrem(key, count, element))
     }
 
     /// Sets the list element at index to element.
     ///
     /// Return value: Simple string reply
-    pub fn lset<K, E>(&mut self, key: K, index: isize, element: E) -> RedisResult<()>
+    pub fn lset<K, E>(&self, key: K, index: isize, element: E) -> RedisResult<()>
     where
         K: RedisSerializationProtocol,
         E: RedisSerializationProtocol,
     {
-        let cmd = command!("LSET"; args => key, index, element);
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::lset(key, index, element))
     }
 
     /// Trim an existing list so that it will contain only the specified range of elements specified.
     ///
     /// Return value: Simple string reply
-    pub fn ltrim<K>(&mut self, key: K, start: isize, stop: isize) -> RedisResult<()>
+    pub fn ltrim<K>(&self, key: K, start: isize, stop: isize) -> RedisResult<()>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("LTRIM"; args => key, start, stop);
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::ltrim(key, start, stop))
     }
 
     /// Removes and returns the last element of the list stored at key.
     ///
     /// Return value: Bulk string reply
-    pub fn rpop<K, E>(&mut self, key: K) -> RedisResult<E>
+    pub fn rpop<K, E>(&self, key: K) -> RedisResult<E>
     where
         K: RedisSerializationProtocol,
         E: RedisDeserializationProtocol,
     {
-        let cmd = command!("RPOP"; args => key);
-        let reply = self.execute(cmd)?;
-        <E>::deserialization(reply)
+        self.call(commands::rpop(key))
+    }
+
+    /// Removes and returns up to count elements from the tail of the list stored at key.
+    ///
+    /// Return value: Array reply, empty when key doesn't exist
+    pub fn rpop_count<K, E>(&self, key: K, count: usize) -> RedisResult<Vec<E>>
+    where
+        K: RedisSerializationProtocol,
+        E: RedisDeserializationProtocol,
+    {
+        self.call(commands::rpop_count(key, count))
     }
 
     /// Atomically returns and removes the last element (tail) of the list stored at source, and
     /// pushes the element at the first element (head) of the list stored at destination.
     ///
     /// Return value: Bulk string reply
-    pub fn rpoplpush<K, E>(&mut self, source: K, destination: K) -> RedisResult<E>
+    pub fn rpoplpush<K, E>(&self, source: K, destination: K) -> RedisResult<E>
     where
         K: RedisSerializationProtocol,
         E: RedisDeserializationProtocol,
     {
-        let cmd = command!("RPOPLPUSH"; args => source, destination);
-        let reply = self.execute(cmd)?;
-        <E>::deserialization(reply)
+        self.call(commands::rpoplpush(source, destination))
     }
 
     /// Insert all the specified values at the tail of the list stored at key.
     ///
     /// Return value: Integer value
-    pub fn rpush<K, E>(&mut self, key: K, elements: Vec<E>) -> RedisResult<usize>
+    pub fn rpush<K, E, I>(&self, key: K, elements: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
     {
-        let mut cmd = command!("RPUSH"; args => key);
-        for element in elements {
-            cmd.arg(element);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::rpush(key, elements))
     }
 
     /// Inserts specified values at the tail of the list stored at key, only if key already exists and holds a list.
     ///
     /// Return value: Integer reply
-    pub fn rpushx<K, E>(&mut self, key: K, elements: Vec<E>) -> RedisResult<usize>
+    pub fn rpushx<K, E, I>(&self, key: K, elements: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
     {
-        let mut cmd = command!("RPUSHX"; args => key);
-        for element in elements {
-            cmd.arg(element);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::rpushx(key, elements))
     }
 
     // Sets commands
     /// Add the specified members to the set stored at key.
     ///
     /// Return value: Integer value
-    pub fn sadd<K, M>(&mut self, key: K, members: HashSet<M>) -> RedisResult<usize>
+    pub fn sadd<K, M, I>(&self, key: K, members: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
-        M: RedisSerializationProtocol + Hash + Eq,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
     {
-        let mut cmd = command!("SADD"; args => key);
-        for member in members {
-            cmd.arg(member);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::sadd(key, members))
     }
 
     /// Returns the set cardinality (number of elements) of the set stored at key.
     ///
     /// Return value: Integer reply
-    pub fn scard<K>(&mut self, key: K) -> RedisResult<usize>
+    pub fn scard<K>(&self, key: K) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("SCARD"; args => key);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::scard(key))
     }
 
     /// Returns the members of the set resulting from the difference between the first set and all the successive sets.
     ///
     /// Return value: Array reply
-    pub fn sdiff<K, M>(&mut self, keys: Vec<K>) -> RedisResult<HashSet<M>>
+    pub fn sdiff<K, M, I>(&self, keys: I) -> RedisResult<HashSet<M>>
     where
         K: RedisSerializationProtocol,
         M: RedisDeserializationProtocol + Hash + Eq,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = Command::new("SDIFF");
-        for key in keys {
-            cmd.arg(key);
-        }
-        let reply = self.execute(cmd)?;
-        <HashSet<M>>::deserialization(reply)
+        self.call(commands::sdiff(keys))
     }
 
     /// This command is equal to SDIFF, but instead of returning the resulting set, it is stored in destination.
     ///
     /// Return value: Integer reply
-    pub fn sdiffstore<K>(&mut self, destination: K, keys: Vec<K>) -> RedisResult<usize>
+    pub fn sdiffstore<K, I>(&self, destination: K, keys: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol + Hash + Eq,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = command!("SDIFFSTORE"; args => destination);
-        for key in keys {
-            cmd.arg(key);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::sdiffstore(destination, keys))
     }
 
     /// Returns the members of the set resulting from the intersection of all the given sets.
     ///
     /// Return value: Array reply
-    pub fn sinter<K, M>(&mut self, keys: Vec<K>) -> RedisResult<HashSet<M>>
+    pub fn sinter<K, M, I>(&self, keys: I) -> RedisResult<HashSet<M>>
     where
         K: RedisSerializationProtocol,
         M: RedisDeserializationProtocol + Hash + Eq,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = Command::new("SINTER");
-        for key in keys {
-            cmd.arg(key);
-        }
-        let reply = self.execute(cmd)?;
-        <HashSet<M>>::deserialization(reply)
+        self.call(commands::sinter(keys))
     }
 
     /// This command is equal to SINTER, but instead of returning the resulting set, it is stored in destination.
     ///
     /// Return value: Integer reply
-    pub fn sinterstore<K>(&mut self, destination: K, keys: Vec<K>) -> RedisResult<usize>
+    pub fn sinterstore<K, I>(&self, destination: K, keys: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = command!("SINTERSTORE"; args => destination);
-        for key in keys {
-            cmd.arg(key);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::sinterstore(destination, keys))
     }
 
     /// Returns if member is a member of the set stored at key.
     ///
     /// Return value: Integer reply
-    pub fn sismember<K, M>(&mut self, key: K, member: M) -> RedisResult<bool>
+    pub fn sismember<K, M>(&self, key: K, member: M) -> RedisResult<bool>
     where
         K: RedisSerializationProtocol,
         M: RedisSerializationProtocol + Hash + Eq,
     {
-        let cmd = command!("SISMEMBER"; args => key, member);
-        let reply = self.execute(cmd)?;
-        <bool>::deserialization(reply)
+        self.call(commands::sismember(key, member))
     }
 
     /// Returns all the members of the set value stored at key.
     ///
     /// Return value: Array reply
-    pub fn smembers<K, M>(&mut self, key: K) -> RedisResult<HashSet<M>>
+    pub fn smembers<K, M>(&self, key: K) -> RedisResult<HashSet<M>>
     where
         K: RedisSerializationProtocol,
         M: RedisDeserializationProtocol + Hash + Eq,
     {
-        let cmd = command!("SMEMBERS"; args => key);
-        let reply = self.execute(cmd)?;
-        <HashSet<M>>::deserialization(reply)
+        self.call(commands::smembers(key))
     }
 
     /// Returns whether each member is a member of the set stored at key.
     ///
     /// Return value: Array reply
-    pub fn smismember<K, M>(&mut self, key: K, members: HashSet<M>) -> RedisResult<Vec<bool>>
+    pub fn smismember<K, M, I>(&self, key: K, members: I) -> RedisResult<Vec<bool>>
     where
         K: RedisSerializationProtocol,
-        M: RedisSerializationProtocol + Hash + Eq,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
     {
-        let mut cmd = command!("SMISMEMBER"; args => key);
-        for member in members {
-            cmd.arg(member);
-        }
-        let reply = self.execute(cmd)?;
-        <Vec<bool>>::deserialization(reply)
+        self.call(commands::smismember(key, members))
     }
 
     /// Move member from the set at source to the set at destination.
     ///
     /// Return value: Integer reply
-    pub fn smove<K, M>(&mut self, source: K, destination: K, member: M) -> RedisResult<usize>
+    pub fn smove<K, M>(&self, source: K, destination: K, member: M) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         M: RedisSerializationProtocol + Hash + Eq,
     {
-        let cmd = command!("SMOVE"; args => source, destination, member);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::smove(source, destination, member))
     }
 
     /// Removes and returns one or more random members from the set value store at key.
     ///
     /// Return value: Bulk string reply or Array reply
-    pub fn spop<K, M>(&mut self, key: K, count: Option<usize>) -> RedisResult<M>
+    pub fn spop<K, M>(&self, key: K, count: Option<usize>) -> RedisResult<M>
     where
         K: RedisSerializationProtocol,
         M: RedisDeserializationProtocol,
     {
-        let mut cmd = command!("SPOP"; args => key);
-        if let Some(count) = count {
-            cmd.arg(count);
-        }
-        let reply = self.execute(cmd)?;
-        <M>::deserialization(reply)
+        self.call(commands::spop(key, count))
     }
 
     /// When called with just the key argument, return a random element from the set value stored at key.
     ///
     /// Return value: Bulk string reply or Array reply
-    pub fn srandmember<K, M>(&mut self, key: K, count: Option<usize>) -> RedisResult<M>
+    pub fn srandmember<K, M>(&self, key: K, count: Option<usize>) -> RedisResult<M>
     where
         K: RedisSerializationProtocol,
         M: RedisDeserializationProtocol,
     {
-        let mut cmd = command!("SPOP"; args => key);
-        if let Some(count) = count {
-            cmd.arg(count);
-        }
-        let reply = self.execute(cmd)?;
-        <M>::deserialization(reply)
+        self.call(commands::srandmember(key, count))
     }
 
     /// Remove the specified members from the set stored at key.
     ///
     /// Return value: Integer reply
-    pub fn srem<K, M>(&mut self, key: K, members: HashSet<M>) -> RedisResult<usize>
+    pub fn srem<K, M, I>(&self, key: K, members: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
-        M: RedisSerializationProtocol + Hash + Eq,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
     {
-        let mut cmd = command!("SREM"; args => key);
-        for member in members {
-            cmd.arg(member);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::srem(key, members))
     }
 
-    /// Like Scan command
-    pub fn sscan() {
-        todo!()
+    /// Iterates over the members of the set stored at key.
+    pub fn sscan<K, T>(&self, key: K) -> Scan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        Scan::new(self, command!("SSCAN"; args => key), None)
+    }
+
+    /// Iterates over the members matching pattern, see `sscan`.
+    pub fn sscan_match<K, S, T>(&self, key: K, pattern: S) -> Scan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        S: ToString,
+        T: RedisDeserializationProtocol,
+    {
+        Scan::new(self, command!("SSCAN"; args => key), Some(pattern.to_string()))
     }
 
     /// Returns the members of the set resulting from the union of all the given sets.
     ///
     /// Return value: Array reply
-    pub fn sunion<K, M>(&mut self, keys: Vec<K>) -> RedisResult<HashSet<M>>
+    pub fn sunion<K, M, I>(&self, keys: I) -> RedisResult<HashSet<M>>
     where
         K: RedisSerializationProtocol,
         M: RedisDeserializationProtocol + Hash + Eq,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = Command::new("SUNION");
-        for key in keys {
-            cmd.arg(key);
-        }
-        let reply = self.execute(cmd)?;
-        <HashSet<M>>::deserialization(reply)
+        self.call(commands::sunion(keys))
     }
 
     /// This command is equal to SUNION, but instead of returning the resulting set, it is stored in destination.
     ///
     /// Return value: Integer reply
-    pub fn sunionstore<K>(&mut self, destination: K, keys: Vec<K>) -> RedisResult<usize>
+    pub fn sunionstore<K, I>(&self, destination: K, keys: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = command!("SUNIONSTORE"; args => destination);
-        for key in keys {
-            cmd.arg(key);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::sunionstore(destination, keys))
     }
 
     // Sorted Sets commands
-    // TODO: design data structure
+    /// BZPOPMAX is the blocking variant of ZPOPMAX, it pops from the first non-empty sorted set
+    /// of keys and waits up to timeout for a member, or forever if zero.
+    ///
+    /// Return value: Array reply, the key, the member and its score, nil when the timeout is reached
+    pub fn bzpopmax<K, R, M, I>(&self, keys: I, timeout: Duration) -> RedisResult<Option<(R, M, f64)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        M: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::bzpopmax(keys, timeout))
+    }
+
+    /// BZPOPMIN is the blocking variant of ZPOPMIN, like `bzpopmax`.
+    ///
+    /// Return value: Array reply, the key, the member and its score, nil when the timeout is reached
+    pub fn bzpopmin<K, R, M, I>(&self, keys: I, timeout: Duration) -> RedisResult<Option<(R, M, f64)>>
+    where
+        K: RedisSerializationProtocol,
+        R: RedisDeserializationProtocol,
+        M: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::bzpopmin(keys, timeout))
+    }
+
     /// Adds all the specified members with the specified scores to the sorted set stored at key.
     ///
     /// Return value: Integer reply
-    pub fn zadd<K, M>(&mut self, key: K, paris: Vec<(isize, M)>) -> RedisResult<usize>
+    pub fn zadd<K, M, I>(&self, key: K, members: I) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         M: RedisSerializationProtocol,
+        I: IntoIterator<Item = (f64, M)>,
     {
-        let mut cmd = command!("ZADD"; args => key);
-        for (score, member) in paris {
-            cmd.arg(score).arg(member);
-        }
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.zadd_options(key, members, ZAddOptions::new())
+    }
+
+    /// Like `zadd`, with the NX, XX, GT, LT and CH options.
+    ///
+    /// Return value: Integer reply
+    pub fn zadd_options<K, M, I>(&self, key: K, members: I, options: ZAddOptions) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = (f64, M)>,
+    {
+        self.call(commands::zadd_options(key, members, options))
+    }
+
+    /// Increments the score of member like `zincrby`, unless the options prevent it.
+    ///
+    /// Return value: Bulk string reply, the new score or nil if the options prevented the update
+    pub fn zadd_incr<K, M>(&self, key: K, increment: f64, member: M, options: ZAddOptions) -> RedisResult<Option<f64>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zadd_incr(key, increment, member, options))
     }
 
     /// Returns the sorted set cardinality (number of elements) of the sorted set stored at key.
     ///
     /// Return value: Integer reply
-    pub fn zcard<K>(&mut self, key: K) -> RedisResult<usize>
+    pub fn zcard<K>(&self, key: K) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("ZCARD"; args => key);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::zcard(key))
     }
 
     /// Returns the number of elements in the sorted set at key with a score between min and max.
     ///
     /// Return value: Integer reply
-    pub fn zcount<K>(&mut self, key: K, min: isize, max: isize) -> RedisResult<usize>
+    pub fn zcount<K, Min, Max>(&self, key: K, min: Min, max: Max) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
+        Min: Into<ScoreBound>,
+        Max: Into<ScoreBound>,
     {
-        let cmd = command!("ZCOUNT"; args => key, min, max);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::zcount(key, min, max))
+    }
+
+    /// Returns the members of the sets resulting from the difference between the first set and all the successive sets.
+    ///
+    /// Return value: Array reply, with the scores if `with_scores`
+    pub fn zdiff<K, T, I>(&self, keys: I, with_scores: bool) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zdiff(keys, with_scores))
+    }
+
+    /// Computes the difference like `zdiff` and stores the result in destination.
+    ///
+    /// Return value: Integer reply, the number of members in the resulting sorted set
+    pub fn zdiffstore<D, K, I>(&self, destination: D, keys: I) -> RedisResult<usize>
+    where
+        D: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zdiffstore(destination, keys))
+    }
+
+    /// Increments the score of member in the sorted set stored at key by increment.
+    ///
+    /// Return value: Bulk string reply, the new score
+    pub fn zincrby<K, M>(&self, key: K, increment: f64, member: M) -> RedisResult<f64>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zincrby(key, increment, member))
+    }
+
+    /// Returns the members of the intersection of the given sorted sets.
+    ///
+    /// Return value: Array reply, with the scores if `with_scores`
+    pub fn zinter<K, T, I>(&self, keys: I, options: ZAggregateOptions, with_scores: bool) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zinter(keys, options, with_scores))
+    }
+
+    /// Computes the intersection like `zinter` and stores the result in destination.
+    ///
+    /// Return value: Integer reply, the number of members in the resulting sorted set
+    pub fn zinterstore<D, K, I>(&self, destination: D, keys: I, options: ZAggregateOptions) -> RedisResult<usize>
+    where
+        D: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zinterstore(destination, keys, options))
+    }
+
+    /// Returns the scores associated with the specified members in the sorted set stored at key.
+    ///
+    /// Return value: Array reply, nil for a member which doesn't exist
+    pub fn zmscore<K, M, I>(&self, key: K, members: I) -> RedisResult<Vec<Option<f64>>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        self.call(commands::zmscore(key, members))
+    }
+
+    /// Removes and returns up to count members with the highest scores in the sorted set stored at key.
+    ///
+    /// Return value: Array reply, e.g. of `(String, f64)` pairs
+    pub fn zpopmax<K, T>(&self, key: K, count: usize) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        self.call(commands::zpopmax(key, count))
+    }
+
+    /// Removes and returns up to count members with the lowest scores in the sorted set stored at key.
+    ///
+    /// Return value: Array reply, e.g. of `(String, f64)` pairs
+    pub fn zpopmin<K, T>(&self, key: K, count: usize) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        self.call(commands::zpopmin(key, count))
+    }
+
+    /// Returns a random member of the sorted set stored at key.
+    ///
+    /// Return value: Bulk string reply, nil when key doesn't exist
+    pub fn zrandmember<K, M>(&self, key: K) -> RedisResult<M>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisDeserializationProtocol,
+    {
+        self.call(commands::zrandmember(key))
+    }
+
+    /// Returns up to count distinct random members, or count members which may repeat if it's negative.
+    ///
+    /// Return value: Array reply, with the scores if `with_scores`
+    pub fn zrandmember_count<K, T>(&self, key: K, count: isize, with_scores: bool) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        self.call(commands::zrandmember_count(key, count, with_scores))
+    }
+
+    /// Returns the specified range of members in the sorted set stored at key.
+    ///
+    /// Return value: Array reply, with the scores if the range says so
+    pub fn zrange<K, T>(&self, key: K, range: ZRange) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        self.call(commands::zrange(key, range))
+    }
+
+    /// Returns the rank of member in the sorted set stored at key, with the scores ordered from low to high.
+    ///
+    /// Return value: Integer reply, nil when member doesn't exist
+    pub fn zrank<K, M>(&self, key: K, member: M) -> RedisResult<Option<usize>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zrank(key, member))
+    }
+
+    /// Removes the specified members from the sorted set stored at key.
+    ///
+    /// Return value: Integer reply
+    pub fn zrem<K, M, I>(&self, key: K, members: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        self.call(commands::zrem(key, members))
+    }
+
+    /// Removes all members in the sorted set stored at key between the lexicographical range specified by min and max.
+    ///
+    /// Return value: Integer reply
+    pub fn zremrangebylex<K>(&self, key: K, min: LexBound, max: LexBound) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::zremrangebylex(key, min, max))
+    }
+
+    /// Removes all members in the sorted set stored at key with rank between start and stop.
+    ///
+    /// Return value: Integer reply
+    pub fn zremrangebyrank<K>(&self, key: K, start: isize, stop: isize) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::zremrangebyrank(key, start, stop))
+    }
+
+    /// Removes all members in the sorted set stored at key with a score between min and max.
+    ///
+    /// Return value: Integer reply
+    pub fn zremrangebyscore<K, Min, Max>(&self, key: K, min: Min, max: Max) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        Min: Into<ScoreBound>,
+        Max: Into<ScoreBound>,
+    {
+        self.call(commands::zremrangebyscore(key, min, max))
+    }
+
+    /// Returns the rank of member in the sorted set stored at key, with the scores ordered from high to low.
+    ///
+    /// Return value: Integer reply, nil when member doesn't exist
+    pub fn zrevrank<K, M>(&self, key: K, member: M) -> RedisResult<Option<usize>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zrevrank(key, member))
+    }
+
+    /// Iterates over the members and scores of the sorted set stored at key, e.g. as `(String, f64)` pairs.
+    pub fn zscan<K, T>(&self, key: K) -> Scan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+    {
+        Scan::new(self, command!("ZSCAN"; args => key), None)
+    }
+
+    /// Iterates over the members matching pattern and their scores, see `zscan`.
+    pub fn zscan_match<K, S, T>(&self, key: K, pattern: S) -> Scan<'_, T>
+    where
+        K: RedisSerializationProtocol,
+        S: ToString,
+        T: RedisDeserializationProtocol,
+    {
+        Scan::new(self, command!("ZSCAN"; args => key), Some(pattern.to_string()))
+    }
+
+    /// Returns the score of member in the sorted set at key.
+    ///
+    /// Return value: Bulk string reply, nil when member or key doesn't exist
+    pub fn zscore<K, M>(&self, key: K, member: M) -> RedisResult<Option<f64>>
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.call(commands::zscore(key, member))
+    }
+
+    /// Returns the members of the union of the given sorted sets.
+    ///
+    /// Return value: Array reply, with the scores if `with_scores`
+    pub fn zunion<K, T, I>(&self, keys: I, options: ZAggregateOptions, with_scores: bool) -> RedisResult<T>
+    where
+        K: RedisSerializationProtocol,
+        T: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zunion(keys, options, with_scores))
+    }
+
+    /// Computes the union like `zunion` and stores the result in destination.
+    ///
+    /// Return value: Integer reply, the number of members in the resulting sorted set
+    pub fn zunionstore<D, K, I>(&self, destination: D, keys: I, options: ZAggregateOptions) -> RedisResult<usize>
+    where
+        D: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        self.call(commands::zunionstore(destination, keys, options))
+    }
+
+    // Streams commands
+    /// Removes the specified entries from the pending entries list of the consumer group.
+    ///
+    /// Return value: Integer reply, the number of entries acknowledged
+    pub fn xack<K, G, I>(&self, key: K, group: G, ids: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+        I: IntoIterator<Item = StreamId>,
+    {
+        self.call(commands::xack(key, group, ids))
+    }
+
+    /// Appends an entry with the specified fields to the stream stored at key, with an ID generated by the server.
+    ///
+    /// Return value: Bulk string reply, the ID of the entry
+    pub fn xadd<K, F, V, I>(&self, key: K, fields: I) -> RedisResult<StreamId>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+        I: IntoIterator<Item = (F, V)>,
+    {
+        self.call(commands::xadd(key, fields))
+    }
+
+    /// Like `xadd`, with an explicit ID, the NOMKSTREAM option or trimming.
+    ///
+    /// Return value: Bulk string reply, the ID of the entry or nil if NOMKSTREAM prevented it
+    pub fn xadd_options<K, F, V, I>(&self, key: K, fields: I, options: XAddOptions) -> RedisResult<Option<StreamId>>
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+        I: IntoIterator<Item = (F, V)>,
+    {
+        self.call(commands::xadd_options(key, fields, options))
+    }
+
+    /// Transfers to consumer up to count pending entries idle for at least min_idle_time, from start on.
+    ///
+    /// Return value: Array reply, the ID to continue from and the claimed entries
+    pub fn xautoclaim<K, G, C, V>(
+        &self,
+        key: K,
+        group: G,
+        consumer: C,
+        min_idle_time: Duration,
+        start: StreamId,
+        count: Option<usize>,
+    ) -> RedisResult<AutoClaim<V>>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+        C: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::xautoclaim(key, group, consumer, min_idle_time, start, count))
+    }
+
+    /// Transfers to consumer the specified pending entries which are idle for at least min_idle_time.
+    ///
+    /// Return value: Array reply, the claimed entries
+    pub fn xclaim<K, G, C, V, I>(
+        &self,
+        key: K,
+        group: G,
+        consumer: C,
+        min_idle_time: Duration,
+        ids: I,
+    ) -> RedisResult<Vec<StreamEntry<V>>>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+        C: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = StreamId>,
+    {
+        self.call(commands::xclaim(key, group, consumer, min_idle_time, ids))
+    }
+
+    /// Removes the specified entries from the stream stored at key.
+    ///
+    /// Return value: Integer reply, the number of entries deleted
+    pub fn xdel<K, I>(&self, key: K, ids: I) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = StreamId>,
+    {
+        self.call(commands::xdel(key, ids))
+    }
+
+    /// Creates a consumer group starting from id, and the stream if `mkstream` and it doesn't exist.
+    ///
+    /// Return value: Simple string reply
+    pub fn xgroup_create<K, G>(&self, key: K, group: G, id: ReadFrom, mkstream: bool) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xgroup_create(key, group, id, mkstream))
+    }
+
+    /// Destroys a consumer group, its consumers and pending entries.
+    ///
+    /// Return value: Integer reply, whether the group existed
+    pub fn xgroup_destroy<K, G>(&self, key: K, group: G) -> RedisResult<bool>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xgroup_destroy(key, group))
+    }
+
+    /// Sets the last delivered ID of a consumer group.
+    ///
+    /// Return value: Simple string reply
+    pub fn xgroup_setid<K, G>(&self, key: K, group: G, id: ReadFrom) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xgroup_setid(key, group, id))
+    }
+
+    /// Returns the consumers of a consumer group.
+    ///
+    /// Return value: Array reply
+    pub fn xinfo_consumers<K, G>(&self, key: K, group: G) -> RedisResult<Vec<ConsumerInfo>>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xinfo_consumers(key, group))
+    }
+
+    /// Returns the consumer groups of the stream stored at key.
+    ///
+    /// Return value: Array reply
+    pub fn xinfo_groups<K>(&self, key: K) -> RedisResult<Vec<GroupInfo>>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::xinfo_groups(key))
+    }
+
+    /// Returns general information about the stream stored at key.
+    ///
+    /// Return value: Map reply
+    pub fn xinfo_stream<K, V>(&self, key: K) -> RedisResult<StreamInfo<V>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::xinfo_stream(key))
+    }
+
+    /// Returns the number of entries in the stream stored at key.
+    ///
+    /// Return value: Integer reply
+    pub fn xlen<K>(&self, key: K) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::xlen(key))
+    }
+
+    /// Returns the number of pending entries of a consumer group, their lowest and highest IDs and their consumers.
+    ///
+    /// Return value: Array reply
+    pub fn xpending<K, G>(&self, key: K, group: G) -> RedisResult<PendingSummary>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xpending(key, group))
+    }
+
+    /// Returns the pending entries of a consumer group in the specified range.
+    ///
+    /// Return value: Array reply
+    pub fn xpending_range<K, G>(&self, key: K, group: G, range: PendingRange) -> RedisResult<Vec<PendingEntry>>
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.call(commands::xpending_range(key, group, range))
+    }
+
+    /// Returns up to count entries of the stream stored at key with an ID from start to end.
+    ///
+    /// Return value: Array reply
+    pub fn xrange<K, V>(
+        &self,
+        key: K,
+        start: StreamBound,
+        end: StreamBound,
+        count: Option<usize>,
+    ) -> RedisResult<Vec<StreamEntry<V>>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::xrange(key, start, end, count))
+    }
+
+    /// Reads the entries of the specified streams with an ID greater than the one given for each of them.
+    ///
+    /// Return value: Array reply, empty when the BLOCK timeout is reached
+    pub fn xread<K, V, I>(&self, keys: I, options: XReadOptions) -> RedisResult<Vec<StreamKey<V>>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = (K, ReadFrom)>,
+    {
+        self.call(commands::xread(keys, options))
+    }
+
+    /// Like `xread`, as consumer of a consumer group. `ReadFrom::Undelivered` reads the new
+    /// entries, an ID the pending entries of the consumer.
+    ///
+    /// Return value: Array reply, empty when the BLOCK timeout is reached
+    pub fn xreadgroup<G, C, K, V, I>(
+        &self,
+        group: G,
+        consumer: C,
+        keys: I,
+        options: XReadOptions,
+    ) -> RedisResult<Vec<StreamKey<V>>>
+    where
+        G: RedisSerializationProtocol,
+        C: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = (K, ReadFrom)>,
+    {
+        self.call(commands::xreadgroup(group, consumer, keys, options))
+    }
+
+    /// Like `xrange`, in reverse order, so end comes before start.
+    ///
+    /// Return value: Array reply
+    pub fn xrevrange<K, V>(
+        &self,
+        key: K,
+        end: StreamBound,
+        start: StreamBound,
+        count: Option<usize>,
+    ) -> RedisResult<Vec<StreamEntry<V>>>
+    where
+        K: RedisSerializationProtocol,
+        V: RedisDeserializationProtocol,
+    {
+        self.call(commands::xrevrange(key, end, start, count))
+    }
+
+    /// Trims the stream stored at key.
+    ///
+    /// Return value: Integer reply, the number of entries deleted
+    pub fn xtrim<K>(&self, key: K, trim: StreamTrim) -> RedisResult<usize>
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.call(commands::xtrim(key, trim))
     }
 
     // Strings commands
-    pub fn append<K, V>(&mut self, key: K, value: V) -> RedisResult<u64>
+    pub fn append<K, V>(&self, key: K, value: V) -> RedisResult<u64>
     where
         K: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let cmd = command!("APPEND"; args => key, value);
-        let reply = self.execute(cmd)?;
-        <u64>::deserialization(reply)
+        self.call(commands::append(key, value))
     }
 
     /// Count the number of set bits (population counting) in a string.
-    pub fn bitcount<K>(&mut self, key: K, start: Option<i64>, end: Option<i64>) -> RedisResult<u64>
+    pub fn bitcount<K>(&self, key: K, start: Option<i64>, end: Option<i64>) -> RedisResult<u64>
     where
         K: RedisSerializationProtocol,
     {
-        let mut cmd = command!("BITCOUNT"; args => key);
-        if let Some(start) = start {
-            cmd.arg(start);
-        }
-        if let Some(end) = end {
-            cmd.arg(end);
-        }
-
-        let reply = self.execute(cmd)?;
-        <u64>::deserialization(reply)
+        self.call(commands::bitcount(key, start, end))
     }
 
     /// Perform a bitwise operation between multiple keys (containing string values) and store the result in the destination key.
-    pub fn bitop<K1, K2>(&mut self, operation: &str, destkey: K1, keys: Vec<K2>) -> RedisResult<usize>
+    pub fn bitop<K1, K2, I>(&self, operation: &str, destkey: K1, keys: I) -> RedisResult<usize>
     where
         K1: RedisSerializationProtocol,
         K2: RedisSerializationProtocol,
+        I: IntoIterator<Item = K2>,
     {
-        let mut cmd = command!("BITOP"; args => operation, destkey);
-        for key in keys {
-            cmd.arg(key);
-        }
-
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::bitop(operation, destkey, keys))
     }
 
     /// Return the position of the first bit set to 1 or 0 in a string.
-    pub fn bitpos<K>(&mut self, key: K, bit: u8, start: Option<usize>, end: Option<usize>) -> RedisResult<isize>
+    pub fn bitpos<K>(&self, key: K, bit: u8, start: Option<usize>, end: Option<usize>) -> RedisResult<isize>
     where
         K: RedisSerializationProtocol,
     {
-        if end.is_some() && start.is_none() {
-            return Err(RedisError::custom(
-                ErrorKind::ClientError,
-                "`start` shouldn't be none when `end` has given",
-            ));
-        }
-
-        let mut cmd = command!("BITPOS"; args => key, bit);
-        if let Some(start) = start {
-            cmd.arg(start);
-        }
-        if let Some(end) = end {
-            cmd.arg(end);
-        }
-
-        let reply = self.execute(cmd)?;
-        <isize>::deserialization(reply)
+        self.call(commands::bitpos(key, bit, start, end)?)
     }
 
     /// Decrements the number stored at key by one.
-    pub fn decr<K>(&mut self, key: K) -> RedisResult<i64>
+    pub fn decr<K>(&self, key: K) -> RedisResult<i64>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("DECR"; args => key);
-        let reply = self.execute(cmd)?;
-        <i64>::deserialization(reply)
+        self.call(commands::decr(key))
     }
 
     /// Decrements the number stored at key by decrement.
-    pub fn decrby<K>(&mut self, key: K, decrement: i64) -> RedisResult<i64>
+    pub fn decrby<K>(&self, key: K, decrement: i64) -> RedisResult<i64>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("DECRBY"; args => key, decrement);
-        let reply = self.execute(cmd)?;
-        <i64>::deserialization(reply)
+        self.call(commands::decrby(key, decrement))
     }
 
     /// Get the value of key.
-    pub fn get<K, V>(&mut self, key: K) -> RedisResult<V>
+    ///
+    /// A missing key is a nil reply, use `Option<V>` to tell it apart from an error.
+    pub fn get<K, V>(&self, key: K) -> RedisResult<V>
     where
         K: RedisSerializationProtocol,
         V: RedisDeserializationProtocol,
     {
-        let cmd = command!("GET"; args => key);
-        let reply = self.execute(cmd)?;
-        <V>::deserialization(reply)
+        self.call(commands::get(key))
     }
 
     /// Returns the bit value at offset in the string value stored at key.
-    pub fn getbit<K>(&mut self, key: K, offset: i64) -> RedisResult<u8>
+    pub fn getbit<K>(&self, key: K, offset: i64) -> RedisResult<u8>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("GETBIT"; args => key, offset);
-        let reply = self.execute(cmd)?;
-        <u8>::deserialization(reply)
+        self.call(commands::getbit(key, offset))
     }
 
     /// Returns the substring of the string value stored at key, determined by the offsets start and end (both are inclusive).
-    pub fn getrange<K>(&mut self, key: K, start: i64, end: i64) -> RedisResult<String>
+    pub fn getrange<K>(&self, key: K, start: i64, end: i64) -> RedisResult<String>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("GETRANGE"; args => key, start, end);
-        let reply = self.execute(cmd)?;
-        <String>::deserialization(reply)
+        self.call(commands::getrange(key, start, end))
     }
 
     /// Atomically sets key to value and returns the old value stored at key.
-    pub fn getset<K, V>(&mut self, key: K, value: V) -> RedisResult<String>
+    pub fn getset<K, V>(&self, key: K, value: V) -> RedisResult<Option<String>>
     where
         K: RedisSerializationProtocol,
         V: ToString,
     {
-        let cmd = command!("GETSET"; args => key, value.to_string());
-        let reply = self.execute(cmd)?;
-        <String>::deserialization(reply)
+        self.call(commands::getset(key, value))
     }
 
     /// Increments the number stored at key by one.
-    pub fn incr<K>(&mut self, key: K) -> RedisResult<i64>
+    pub fn incr<K>(&self, key: K) -> RedisResult<i64>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("INCR"; args => key);
-        let reply = self.execute(cmd)?;
-        <i64>::deserialization(reply)
+        self.call(commands::incr(key))
     }
 
     /// Increments the number stored at key by increment.
-    pub fn incrby<K>(&mut self, key: K, increment: i64) -> RedisResult<i64>
+    pub fn incrby<K>(&self, key: K, increment: i64) -> RedisResult<i64>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("INCRBY"; args => key, increment);
-        let reply = self.execute(cmd)?;
-        <i64>::deserialization(reply)
+        self.call(commands::incrby(key, increment))
     }
 
     /// Increment the string representing a floating point number stored at key by the specified increment.
-    pub fn incrbyfloat<K>(&mut self, key: K, increment: f64) -> RedisResult<f64>
+    pub fn incrbyfloat<K>(&self, key: K, increment: f64) -> RedisResult<f64>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("INCRBYFLOAT"; args => key, increment);
-        let reply = self.execute(cmd)?;
-        <f64>::deserialization(reply)
+        self.call(commands::incrbyfloat(key, increment))
     }
 
     /// Returns the values of all specified keys.
-    pub fn mget<K, V>(&mut self, keys: Vec<K>) -> RedisResult<Vec<V>>
+    pub fn mget<K, V, I>(&self, keys: I) -> RedisResult<Vec<V>>
     where
         K: RedisSerializationProtocol,
         V: RedisDeserializationProtocol,
+        I: IntoIterator<Item = K>,
     {
-        let mut cmd = Command::new("MGET");
-        for key in keys {
-            cmd.arg(key);
-        }
-
-        let reply = self.execute(cmd)?;
-        <Vec<V>>::deserialization(reply)
+        self.call(commands::mget(keys))
     }
 
     /// Sets the given keys to their respective values.
-    pub fn mset<K, V>(&mut self, kvs: Vec<(K, V)>) -> RedisResult<()>
+    pub fn mset<K, V>(&self, kvs: Vec<(K, V)>) -> RedisResult<()>
     where
         K: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let mut cmd = Command::new("MSET");
-        for (k, v) in kvs {
-            cmd.arg(k).arg(v);
-        }
-
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::mset(kvs))
     }
 
     /// Sets the given keys to their respective values.
-    pub fn msetnx<K, V>(&mut self, kvs: Vec<(K, V)>) -> RedisResult<()>
+    pub fn msetnx<K, V>(&self, kvs: Vec<(K, V)>) -> RedisResult<()>
     where
         K: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let mut cmd = Command::new("MSETNX");
-        for (k, v) in kvs {
-            cmd.arg(k).arg(v);
-        }
-
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::msetnx(kvs))
     }
 
     /// PSETEX works exactly like SETEX with the sole difference that the expire time is specified in milliseconds instead of seconds.
-    pub fn psetex<K, V>(&mut self, key: K, milliseconds: u64, value: V) -> RedisResult<()>
+    pub fn psetex<K, V>(&self, key: K, milliseconds: u64, value: V) -> RedisResult<()>
     where
         K: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let cmd = command!("PSETEX"; args => key, milliseconds, value);
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::psetex(key, milliseconds, value))
     }
 
     /// Set key to hold the string value.
     pub fn set<K, V>(
-        &mut self,
+        &self,
         key: K,
         value: V,
         ex_seconds: Option<u64>,
@@ -1290,30 +2036,11 @@ impl RedisClient {
         K: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let mut cmd = command!("SET"; args => key, value);
-        if let Some(ex) = ex_seconds {
-            cmd.arg("EX").arg(ex);
-        }
-        if let Some(px) = px_milliseconds {
-            cmd.arg("PX").arg(px);
-        }
-        if let Some(nx) = nx {
-            if nx {
-                cmd.arg("NX");
-            }
-        }
-        if let Some(xx) = xx {
-            if xx {
-                cmd.arg("XX");
-            }
-        }
-
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::set(key, value, ex_seconds, px_milliseconds, nx, xx))
     }
 
     /// Set key to hold the string value.
-    pub fn simple_set<K, V>(&mut self, key: K, value: V) -> RedisResult<()>
+    pub fn simple_set<K, V>(&self, key: K, value: V) -> RedisResult<()>
     where
         K: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
@@ -1322,63 +2049,174 @@ impl RedisClient {
     }
 
     /// Sets or clears the bit at offset in the string value stored at key.
-    pub fn setbit<K>(&mut self, key: K, offset: usize, value: u8) -> RedisResult<u8>
+    pub fn setbit<K>(&self, key: K, offset: usize, value: u8) -> RedisResult<u8>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("SETBIT"; args => key, offset, value);
-        let reply = self.execute(cmd)?;
-        <u8>::deserialization(reply)
+        self.call(commands::setbit(key, offset, value))
     }
 
     /// Set key to hold the string value and set key to timeout after a given number of seconds.
-    pub fn setex<K, V>(&mut self, key: K, seconds: usize, value: V) -> RedisResult<()>
+    pub fn setex<K, V>(&self, key: K, seconds: usize, value: V) -> RedisResult<()>
     where
         K: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let cmd = command!("SETEX"; args => key, seconds, value);
-        let reply = self.execute(cmd)?;
-        <()>::deserialization(reply)
+        self.call(commands::setex(key, seconds, value))
     }
 
     /// Set key to hold string value if key does not exist.
-    pub fn setnx<K, V>(&mut self, key: K, value: V) -> RedisResult<bool>
+    pub fn setnx<K, V>(&self, key: K, value: V) -> RedisResult<bool>
     where
         K: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let cmd = command!("SETNX"; args => key, value);
-        let reply = self.execute(cmd)?;
-        <bool>::deserialization(reply)
+        self.call(commands::setnx(key, value))
     }
 
     /// Overwrites part of the string stored at key, starting at the specified offset, for the entire length of value.
-    pub fn setrange<K, V>(&mut self, key: K, offset: usize, value: V) -> RedisResult<usize>
+    pub fn setrange<K, V>(&self, key: K, offset: usize, value: V) -> RedisResult<usize>
     where
         K: RedisSerializationProtocol,
         V: RedisSerializationProtocol,
     {
-        let cmd = command!("SETRANGE"; args => key, offset, value);
-        let reply = self.execute(cmd)?;
-        <usize>::deserialization(reply)
+        self.call(commands::setrange(key, offset, value))
     }
 
     /// Returns the length of the string value stored at key.
-    pub fn strlen<K>(&mut self, key: K) -> RedisResult<u64>
+    pub fn strlen<K>(&self, key: K) -> RedisResult<u64>
     where
         K: RedisSerializationProtocol,
     {
-        let cmd = command!("STRLEN"; args => key);
+        self.call(commands::strlen(key))
+    }
+
+    // Transactions commands
+    /// Marks the given keys to be watched for conditional execution of a transaction.
+    ///
+    /// Watching only makes sense on the connection running the transaction, see `transaction`.
+    ///
+    /// Return value: Simple string reply
+    pub fn watch<K, I>(&self, keys: I) -> RedisResult<()>
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("WATCH");
+        for key in keys {
+            cmd.arg(key);
+        }
         let reply = self.execute(cmd)?;
-        <u64>::deserialization(reply)
+        <()>::deserialization(reply)
+    }
+
+    /// Flushes all the previously watched keys for a transaction.
+    ///
+    /// Return value: Simple string reply
+    pub fn unwatch(&self) -> RedisResult<()> {
+        let cmd = Command::new("UNWATCH");
+        let reply = self.execute(cmd)?;
+        <()>::deserialization(reply)
+    }
+
+    // Runs `func` on the pinned connection, or on one from the pool, a connection an error left
+    // unusable is closed rather than put back. Failures are retried as the `RetryPolicy` says,
+    // after `func` may have sent a command only if `retryable`.
+    fn with_connection<T, F>(&self, retryable: bool, func: F) -> RedisResult<T>
+    where
+        F: FnMut(&mut Connection) -> RedisResult<T>,
+    {
+        self.with_checkout(retryable, ConnectionPool::get, self.read_timeout, func)
+    }
+
+    // Like `with_connection`, with the connection checked out of the pool by `checkout`, and
+    // waiting up to `read_timeout` for replies.
+    fn with_checkout<T, F, C>(
+        &self,
+        retryable: bool,
+        checkout: C,
+        read_timeout: Option<Duration>,
+        mut func: F,
+    ) -> RedisResult<T>
+    where
+        F: FnMut(&mut Connection) -> RedisResult<T>,
+        C: Fn(&Arc<ConnectionPool>) -> RedisResult<PooledConnection>,
+    {
+        if let Some(ref pinned) = self.pinned {
+            let mut conn = pinned.lock().unwrap_or_else(|e| e.into_inner());
+            if conn.is_discarded() {
+                return Err(RedisError::custom(
+                    ErrorKind::ClientError,
+                    "pinned connection was closed after an error",
+                ));
+            }
+            conn.set_read_timeout(read_timeout).map_err(|e| conn.discard(e))?;
+            return func(&mut conn).map_err(|e| conn.discard_if_unrecoverable(e));
+        }
+
+        let policy = self.pool.retry_policy();
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let (e, sent) = match checkout(&self.pool) {
+                Ok(mut conn) => match conn.set_read_timeout(read_timeout) {
+                    Ok(()) => match func(&mut conn) {
+                        Ok(result) => return Ok(result),
+                        Err(e) => (conn.discard_if_unrecoverable(e), true),
+                    },
+                    Err(e) => (conn.discard(e), false),
+                },
+                Err(e) => (e, false),
+            };
+
+            if (sent && !retryable) || !policy.should_retry(&e, attempts) {
+                return Err(e);
+            }
+            thread::sleep(policy.backoff(attempts));
+        }
+    }
+
+    // Drops the pinned connection rather than putting it back, and returns the error.
+    fn discard(&self, e: RedisError) -> RedisError {
+        match self.pinned {
+            Some(ref pinned) => pinned.lock().unwrap_or_else(|e| e.into_inner()).discard(e),
+            None => e,
+        }
+    }
+
+    // Sends a command built by `commands`, and decodes its reply.
+    fn call<T>(&self, call: Call<T>) -> RedisResult<T> {
+        let reply = match call.block {
+            Some(block) => self.execute_blocking(call.cmd, block)?,
+            None => self.execute(call.cmd)?,
+        };
+        (call.decode)(reply)
+    }
+
+    pub(crate) fn execute(&self, cmd: Command) -> RedisResult<Reply> {
+        self.with_connection(cmd.is_retryable(), |conn| {
+            conn.send(&cmd.to_vec())?;
+            conn.receive()
+        })
+    }
+
+    // Executes a command which blocks up to `block` on the server, or forever if zero, the read
+    // timeout is extended accordingly. It's sent on a connection dedicated to blocking commands
+    // unless one is pinned, so waiting doesn't hold up the other users of the pool.
+    fn execute_blocking(&self, cmd: Command, block: Duration) -> RedisResult<Reply> {
+        let timeout = match block {
+            Duration::ZERO => None,
+            block => self.read_timeout.map(|timeout| timeout + block),
+        };
+
+        // the connection gets the configured timeout back when it's put back in the pool
+        self.with_checkout(cmd.is_retryable(), ConnectionPool::get_blocking, timeout, |conn| {
+            conn.send(&cmd.to_vec())?;
+            conn.receive()
+        })
     }
 
-    fn execute(&mut self, cmd: Command) -> RedisResult<Reply> {
-        let mut conn = self.pool.get()?;
-        conn.send(&cmd.into_vec())?;
-        let reply = conn.receive()?;
-        self.pool.put(conn);
-        Ok(reply)
+    pub(crate) fn execute_pipeline(&self, pipeline: &Pipeline) -> RedisResult<Vec<Reply>> {
+        self.with_connection(pipeline.is_retryable(), |conn| pipeline.execute(conn))
     }
 }
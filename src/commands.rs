@@ -0,0 +1,1537 @@
+//! The commands of `RedisClient` and `AsyncRedisClient`, each built here once with how its
+//! reply is decoded, the clients only send them.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::time::Duration;
+
+use crate::client::{command, Command, LPosOptions, ListBeforeOrAfter, ListDirection};
+use crate::connection::Reply;
+use crate::error::{ErrorKind, RedisError};
+use crate::protocol::{RedisDeserializationProtocol, RedisSerializationProtocol};
+use crate::sorted_sets::{LexBound, ScoreBound, ZAddOptions, ZAggregateOptions, ZRange};
+use crate::streams::{
+    self, AutoClaim, ConsumerInfo, GroupInfo, PendingEntry, PendingRange, PendingSummary, ReadFrom, StreamBound,
+    StreamEntry, StreamId, StreamInfo, StreamKey, StreamTrim, XAddOptions, XReadOptions,
+};
+use crate::{DataType, RedisResult};
+
+/// A command, and how its reply is decoded.
+pub(crate) struct Call<T> {
+    pub(crate) cmd: Command,
+    // how long the command blocks on the server, forever if zero
+    pub(crate) block: Option<Duration>,
+    pub(crate) decode: fn(Reply) -> RedisResult<T>,
+}
+
+impl<T> Call<T>
+where
+    T: RedisDeserializationProtocol,
+{
+    fn new(cmd: Command) -> Call<T> {
+        Call::with_decoder(cmd, T::deserialization)
+    }
+
+    fn blocking(cmd: Command, block: Duration) -> Call<T> {
+        Call::new(cmd).block(Some(block))
+    }
+}
+
+impl<T> Call<T> {
+    fn with_decoder(cmd: Command, decode: fn(Reply) -> RedisResult<T>) -> Call<T> {
+        Call {
+            cmd,
+            block: None,
+            decode,
+        }
+    }
+
+    fn block(mut self, block: Option<Duration>) -> Call<T> {
+        self.block = block;
+        self
+    }
+}
+
+pub(crate) fn flushall() -> Call<()> {
+    Call::new(Command::new("FLUSHALL"))
+}
+
+// Connection commands
+
+pub(crate) fn auth<S>(username: Option<S>, password: S) -> Call<()>
+where
+    S: ToString,
+{
+    let mut cmd = Command::new("AUTH");
+    if let Some(username) = username {
+        cmd.arg(username.to_string());
+    }
+    cmd.arg(password.to_string());
+    Call::new(cmd)
+}
+
+pub(crate) fn echo<S>(message: S) -> Call<String>
+where
+    S: ToString,
+{
+    Call::new(command!("ECHO"; args => message.to_string()))
+}
+
+pub(crate) fn ping() -> Call<()> {
+    Call::new(Command::new("PING"))
+}
+
+pub(crate) fn quit() -> Call<()> {
+    Call::new(Command::new("QUIT"))
+}
+
+// Hashes commands
+
+pub(crate) fn hdel<K, I>(key: K, fields: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = command!("HDEL"; args => key);
+    for field in fields {
+        cmd.arg(field);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn hexists<K, F>(key: K, field: F) -> Call<bool>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+{
+    Call::new(command!("HEXISTS"; args => key, field))
+}
+
+pub(crate) fn hget<K, F, V>(key: K, field: F) -> Call<V>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    Call::new(command!("HGET"; args => key, field))
+}
+
+pub(crate) fn hgetall<K, M>(key: K) -> Call<M>
+where
+    K: RedisSerializationProtocol,
+    M: RedisDeserializationProtocol,
+{
+    Call::new(command!("HGETALL"; args => key))
+}
+
+pub(crate) fn hincrby<K, F>(key: K, field: F, increment: i64) -> Call<i64>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+{
+    Call::new(command!("HINCRBY"; args => key, field, increment))
+}
+
+pub(crate) fn hincrbyfloat<K, F>(key: K, field: F, increment: f64) -> Call<f64>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+{
+    Call::new(command!("HINCRBYFLOAT"; args => key, field, increment))
+}
+
+pub(crate) fn hkeys<K, V>(key: K) -> Call<Vec<V>>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    Call::new(command!("HKEYS"; args => key))
+}
+
+pub(crate) fn hlen<K>(key: K) -> Call<u64>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("HLEN"; args => key))
+}
+
+pub(crate) fn hmget<K, F, V, I>(key: K, fields: I) -> Call<Vec<V>>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+    I: IntoIterator<Item = F>,
+{
+    let mut cmd = command!("HMGET"; args => key);
+    for field in fields {
+        cmd.arg(field);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn hmset<K, F, V>(key: K, fvs: Vec<(F, V)>) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    let mut cmd = command!("HMSET"; args => key);
+    for (field, value) in fvs {
+        cmd.arg(field).arg(value);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn hset<K, F, V, I>(key: K, fvs: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+    I: IntoIterator<Item = (F, V)>,
+{
+    let mut cmd = command!("HSET"; args => key);
+    for (field, value) in fvs {
+        cmd.arg(field).arg(value);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn hsetnx<K, F, V>(key: K, field: F, value: V) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    Call::new(command!("HSETNX"; args => key, field, value))
+}
+
+pub(crate) fn hstrlen<K, F>(key: K, field: F) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+{
+    Call::new(command!("HSTRLEN"; args => key, field))
+}
+
+pub(crate) fn hvals<K, V>(key: K) -> Call<Vec<V>>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    Call::new(command!("HVALS"; args => key))
+}
+
+// Keys commands
+
+pub(crate) fn del<K, I>(keys: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("DEL");
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn exists<K, I>(keys: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("EXISTS");
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn expire<K>(key: K, seconds: usize) -> Call<bool>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("EXPIRE"; args => key, seconds))
+}
+
+pub(crate) fn keys<S>(pattern: S) -> Call<Vec<String>>
+where
+    S: ToString,
+{
+    Call::new(command!("KEYS"; args => pattern.to_string()))
+}
+
+pub(crate) fn persist<K>(key: K) -> Call<bool>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("PERSIST"; args => key))
+}
+
+pub(crate) fn pexpire<K>(key: K, milliseconds: u64) -> Call<bool>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("PEXPIRE"; args => key, milliseconds))
+}
+
+pub(crate) fn pttl<K>(key: K) -> Call<i64>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("PTTL"; args => key))
+}
+
+pub(crate) fn randomkey() -> Call<Option<String>> {
+    Call::new(Command::new("RANDOMKEY"))
+}
+
+pub(crate) fn rename<K>(key: K, newkey: K) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("RENAME"; args => key, newkey))
+}
+
+pub(crate) fn renamenx<K>(key: K, newkey: K) -> Call<bool>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("RENAMENX"; args => key, newkey))
+}
+
+pub(crate) fn touch<K, I>(keys: I) -> Call<isize>
+where
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("TOUCH");
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn ttl<K>(key: K) -> Call<isize>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("TTL"; args => key))
+}
+
+pub(crate) fn type_<K>(key: K) -> Call<DataType>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("TYPE"; args => key))
+}
+
+pub(crate) fn unlink<K, I>(keys: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("UNLINK");
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn blmove<K, V>(
+    source: K,
+    destination: K,
+    wherefrom: ListDirection,
+    whereto: ListDirection,
+    timeout: Duration,
+) -> Call<Option<V>>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    Call::blocking(
+        command!("BLMOVE"; args => source, destination, wherefrom, whereto, timeout.as_secs_f64()),
+        timeout,
+    )
+}
+
+pub(crate) fn blmpop<K, R, V, I>(
+    timeout: Duration,
+    keys: I,
+    direction: ListDirection,
+    count: usize,
+) -> Call<Option<(R, Vec<V>)>>
+where
+    K: RedisSerializationProtocol,
+    R: RedisDeserializationProtocol,
+    V: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+    let cmd = command!("BLMPOP"; args => timeout.as_secs_f64(), keys.len(), keys, direction, "COUNT", count);
+    Call::blocking(cmd, timeout)
+}
+
+pub(crate) fn blpop<K, R, V, I>(keys: I, timeout: Duration) -> Call<Option<(R, V)>>
+where
+    K: RedisSerializationProtocol,
+    R: RedisDeserializationProtocol,
+    V: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("BLPOP");
+    for key in keys {
+        cmd.arg(key);
+    }
+    cmd.arg(timeout.as_secs_f64());
+    Call::blocking(cmd, timeout)
+}
+
+pub(crate) fn brpop<K, R, V, I>(keys: I, timeout: Duration) -> Call<Option<(R, V)>>
+where
+    K: RedisSerializationProtocol,
+    R: RedisDeserializationProtocol,
+    V: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("BRPOP");
+    for key in keys {
+        cmd.arg(key);
+    }
+    cmd.arg(timeout.as_secs_f64());
+    Call::blocking(cmd, timeout)
+}
+
+pub(crate) fn brpoplpush<K, E>(source: K, destination: K, timeout: Duration) -> Call<E>
+where
+    K: RedisSerializationProtocol,
+    E: RedisDeserializationProtocol,
+{
+    Call::blocking(
+        command!("BRPOPLPUSH"; args => source, destination, timeout.as_secs_f64()),
+        timeout,
+    )
+}
+
+// Lists commands
+
+pub(crate) fn lindex<K, V>(key: K, index: isize) -> Call<V>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    Call::new(command!("LINDEX"; args => key, index))
+}
+
+pub(crate) fn linsert<K, E>(key: K, operator: ListBeforeOrAfter, pivot: E, element: E) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    E: RedisSerializationProtocol,
+{
+    Call::new(command!("LINSERT"; args => key, operator, pivot, element))
+}
+
+pub(crate) fn llen<K>(key: K) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("LLEN"; args => key))
+}
+
+pub(crate) fn lmove<K, V>(
+    source: K,
+    destination: K,
+    wherefrom: ListDirection,
+    whereto: ListDirection,
+) -> Call<Option<V>>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    Call::new(command!("LMOVE"; args => source, destination, wherefrom, whereto))
+}
+
+pub(crate) fn lmpop<K, R, V, I>(keys: I, direction: ListDirection, count: usize) -> Call<Option<(R, Vec<V>)>>
+where
+    K: RedisSerializationProtocol,
+    R: RedisDeserializationProtocol,
+    V: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+    let cmd = command!("LMPOP"; args => keys.len(), keys, direction, "COUNT", count);
+    Call::new(cmd)
+}
+
+pub(crate) fn lpop<K, E>(key: K) -> Call<E>
+where
+    K: RedisSerializationProtocol,
+    E: RedisDeserializationProtocol,
+{
+    Call::new(command!("LPOP"; args => key))
+}
+
+pub(crate) fn lpop_count<K, E>(key: K, count: usize) -> Call<Vec<E>>
+where
+    K: RedisSerializationProtocol,
+    E: RedisDeserializationProtocol,
+{
+    Call::with_decoder(command!("LPOP"; args => key, count), |reply| {
+        Ok(<Option<Vec<E>>>::deserialization(reply)?.unwrap_or_default())
+    })
+}
+
+pub(crate) fn lpos<K, E>(key: K, element: E, options: LPosOptions) -> Call<Option<usize>>
+where
+    K: RedisSerializationProtocol,
+    E: RedisSerializationProtocol,
+{
+    Call::new(command!("LPOS"; args => key, element, options))
+}
+
+pub(crate) fn lpos_count<K, E>(key: K, element: E, count: usize, options: LPosOptions) -> Call<Vec<usize>>
+where
+    K: RedisSerializationProtocol,
+    E: RedisSerializationProtocol,
+{
+    Call::new(command!("LPOS"; args => key, element, "COUNT", count, options))
+}
+
+pub(crate) fn lpush<K, E, I>(key: K, elements: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    E: RedisSerializationProtocol,
+    I: IntoIterator<Item = E>,
+{
+    let mut cmd = command!("LPUSH"; args => key);
+    for element in elements {
+        cmd.arg(element);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn lpushx<K, E, I>(key: K, elements: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    E: RedisSerializationProtocol,
+    I: IntoIterator<Item = E>,
+{
+    let mut cmd = command!("LPUSHX"; args => key);
+    for element in elements {
+        cmd.arg(element);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn lrange<K, E>(key: K, start: isize, end: isize) -> Call<Vec<E>>
+where
+    K: RedisSerializationProtocol,
+    E: RedisDeserializationProtocol,
+{
+    Call::new(command!("LRANGE"; args => key, start, end))
+}
+
+pub(crate) fn lrem<K, E>(key: K, count: isize, element: E) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    E: RedisSerializationProtocol,
+{
+    Call::new(command!("LREM"; args => key, count, element))
+}
+
+pub(crate) fn lset<K, E>(key: K, index: isize, element: E) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+    E: RedisSerializationProtocol,
+{
+    Call::new(command!("LSET"; args => key, index, element))
+}
+
+pub(crate) fn ltrim<K>(key: K, start: isize, stop: isize) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("LTRIM"; args => key, start, stop))
+}
+
+pub(crate) fn rpop<K, E>(key: K) -> Call<E>
+where
+    K: RedisSerializationProtocol,
+    E: RedisDeserializationProtocol,
+{
+    Call::new(command!("RPOP"; args => key))
+}
+
+pub(crate) fn rpop_count<K, E>(key: K, count: usize) -> Call<Vec<E>>
+where
+    K: RedisSerializationProtocol,
+    E: RedisDeserializationProtocol,
+{
+    Call::with_decoder(command!("RPOP"; args => key, count), |reply| {
+        Ok(<Option<Vec<E>>>::deserialization(reply)?.unwrap_or_default())
+    })
+}
+
+pub(crate) fn rpoplpush<K, E>(source: K, destination: K) -> Call<E>
+where
+    K: RedisSerializationProtocol,
+    E: RedisDeserializationProtocol,
+{
+    Call::new(command!("RPOPLPUSH"; args => source, destination))
+}
+
+pub(crate) fn rpush<K, E, I>(key: K, elements: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    E: RedisSerializationProtocol,
+    I: IntoIterator<Item = E>,
+{
+    let mut cmd = command!("RPUSH"; args => key);
+    for element in elements {
+        cmd.arg(element);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn rpushx<K, E, I>(key: K, elements: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    E: RedisSerializationProtocol,
+    I: IntoIterator<Item = E>,
+{
+    let mut cmd = command!("RPUSHX"; args => key);
+    for element in elements {
+        cmd.arg(element);
+    }
+    Call::new(cmd)
+}
+
+// Sets commands
+
+pub(crate) fn sadd<K, M, I>(key: K, members: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+    I: IntoIterator<Item = M>,
+{
+    let mut cmd = command!("SADD"; args => key);
+    for member in members {
+        cmd.arg(member);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn scard<K>(key: K) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("SCARD"; args => key))
+}
+
+pub(crate) fn sdiff<K, M, I>(keys: I) -> Call<HashSet<M>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisDeserializationProtocol + Hash + Eq,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("SDIFF");
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn sdiffstore<K, I>(destination: K, keys: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol + Hash + Eq,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = command!("SDIFFSTORE"; args => destination);
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn sinter<K, M, I>(keys: I) -> Call<HashSet<M>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisDeserializationProtocol + Hash + Eq,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("SINTER");
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn sinterstore<K, I>(destination: K, keys: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = command!("SINTERSTORE"; args => destination);
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn sismember<K, M>(key: K, member: M) -> Call<bool>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol + Hash + Eq,
+{
+    Call::new(command!("SISMEMBER"; args => key, member))
+}
+
+pub(crate) fn smembers<K, M>(key: K) -> Call<HashSet<M>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisDeserializationProtocol + Hash + Eq,
+{
+    Call::new(command!("SMEMBERS"; args => key))
+}
+
+pub(crate) fn smismember<K, M, I>(key: K, members: I) -> Call<Vec<bool>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+    I: IntoIterator<Item = M>,
+{
+    let mut cmd = command!("SMISMEMBER"; args => key);
+    for member in members {
+        cmd.arg(member);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn smove<K, M>(source: K, destination: K, member: M) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol + Hash + Eq,
+{
+    Call::new(command!("SMOVE"; args => source, destination, member))
+}
+
+pub(crate) fn spop<K, M>(key: K, count: Option<usize>) -> Call<M>
+where
+    K: RedisSerializationProtocol,
+    M: RedisDeserializationProtocol,
+{
+    let mut cmd = command!("SPOP"; args => key);
+    if let Some(count) = count {
+        cmd.arg(count);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn srandmember<K, M>(key: K, count: Option<usize>) -> Call<M>
+where
+    K: RedisSerializationProtocol,
+    M: RedisDeserializationProtocol,
+{
+    let mut cmd = command!("SPOP"; args => key);
+    if let Some(count) = count {
+        cmd.arg(count);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn srem<K, M, I>(key: K, members: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+    I: IntoIterator<Item = M>,
+{
+    let mut cmd = command!("SREM"; args => key);
+    for member in members {
+        cmd.arg(member);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn sunion<K, M, I>(keys: I) -> Call<HashSet<M>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisDeserializationProtocol + Hash + Eq,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("SUNION");
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn sunionstore<K, I>(destination: K, keys: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = command!("SUNIONSTORE"; args => destination);
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+// Sorted Sets commands
+
+pub(crate) fn bzpopmax<K, R, M, I>(keys: I, timeout: Duration) -> Call<Option<(R, M, f64)>>
+where
+    K: RedisSerializationProtocol,
+    R: RedisDeserializationProtocol,
+    M: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("BZPOPMAX");
+    for key in keys {
+        cmd.arg(key);
+    }
+    cmd.arg(timeout.as_secs_f64());
+    Call::blocking(cmd, timeout)
+}
+
+pub(crate) fn bzpopmin<K, R, M, I>(keys: I, timeout: Duration) -> Call<Option<(R, M, f64)>>
+where
+    K: RedisSerializationProtocol,
+    R: RedisDeserializationProtocol,
+    M: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("BZPOPMIN");
+    for key in keys {
+        cmd.arg(key);
+    }
+    cmd.arg(timeout.as_secs_f64());
+    Call::blocking(cmd, timeout)
+}
+
+pub(crate) fn zadd_options<K, M, I>(key: K, members: I, options: ZAddOptions) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+    I: IntoIterator<Item = (f64, M)>,
+{
+    let mut cmd = command!("ZADD"; args => key, options);
+    for (score, member) in members {
+        cmd.arg(score).arg(member);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn zadd_incr<K, M>(key: K, increment: f64, member: M, options: ZAddOptions) -> Call<Option<f64>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+{
+    let mut cmd = command!("ZADD"; args => key, options, "INCR", increment, member);
+    cmd.non_retryable();
+    Call::new(cmd)
+}
+
+pub(crate) fn zcard<K>(key: K) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("ZCARD"; args => key))
+}
+
+pub(crate) fn zcount<K, Min, Max>(key: K, min: Min, max: Max) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    Min: Into<ScoreBound>,
+    Max: Into<ScoreBound>,
+{
+    Call::new(command!("ZCOUNT"; args => key, min.into(), max.into()))
+}
+
+pub(crate) fn zdiff<K, T, I>(keys: I, with_scores: bool) -> Call<T>
+where
+    K: RedisSerializationProtocol,
+    T: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+    let mut cmd = command!("ZDIFF"; args => keys.len(), keys);
+    if with_scores {
+        cmd.arg("WITHSCORES");
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn zdiffstore<D, K, I>(destination: D, keys: I) -> Call<usize>
+where
+    D: RedisSerializationProtocol,
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+    let cmd = command!("ZDIFFSTORE"; args => destination, keys.len(), keys);
+    Call::new(cmd)
+}
+
+pub(crate) fn zincrby<K, M>(key: K, increment: f64, member: M) -> Call<f64>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+{
+    Call::new(command!("ZINCRBY"; args => key, increment, member))
+}
+
+pub(crate) fn zinter<K, T, I>(keys: I, options: ZAggregateOptions, with_scores: bool) -> Call<T>
+where
+    K: RedisSerializationProtocol,
+    T: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+    let mut cmd = command!("ZINTER"; args => keys.len(), keys, options);
+    if with_scores {
+        cmd.arg("WITHSCORES");
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn zinterstore<D, K, I>(destination: D, keys: I, options: ZAggregateOptions) -> Call<usize>
+where
+    D: RedisSerializationProtocol,
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+    let cmd = command!("ZINTERSTORE"; args => destination, keys.len(), keys, options);
+    Call::new(cmd)
+}
+
+pub(crate) fn zmscore<K, M, I>(key: K, members: I) -> Call<Vec<Option<f64>>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+    I: IntoIterator<Item = M>,
+{
+    let mut cmd = command!("ZMSCORE"; args => key);
+    for member in members {
+        cmd.arg(member);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn zpopmax<K, T>(key: K, count: usize) -> Call<T>
+where
+    K: RedisSerializationProtocol,
+    T: RedisDeserializationProtocol,
+{
+    Call::new(command!("ZPOPMAX"; args => key, count))
+}
+
+pub(crate) fn zpopmin<K, T>(key: K, count: usize) -> Call<T>
+where
+    K: RedisSerializationProtocol,
+    T: RedisDeserializationProtocol,
+{
+    Call::new(command!("ZPOPMIN"; args => key, count))
+}
+
+pub(crate) fn zrandmember<K, M>(key: K) -> Call<M>
+where
+    K: RedisSerializationProtocol,
+    M: RedisDeserializationProtocol,
+{
+    Call::new(command!("ZRANDMEMBER"; args => key))
+}
+
+pub(crate) fn zrandmember_count<K, T>(key: K, count: isize, with_scores: bool) -> Call<T>
+where
+    K: RedisSerializationProtocol,
+    T: RedisDeserializationProtocol,
+{
+    let mut cmd = command!("ZRANDMEMBER"; args => key, count);
+    if with_scores {
+        cmd.arg("WITHSCORES");
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn zrange<K, T>(key: K, range: ZRange) -> Call<T>
+where
+    K: RedisSerializationProtocol,
+    T: RedisDeserializationProtocol,
+{
+    Call::new(command!("ZRANGE"; args => key, range))
+}
+
+pub(crate) fn zrank<K, M>(key: K, member: M) -> Call<Option<usize>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+{
+    Call::new(command!("ZRANK"; args => key, member))
+}
+
+pub(crate) fn zrem<K, M, I>(key: K, members: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+    I: IntoIterator<Item = M>,
+{
+    let mut cmd = command!("ZREM"; args => key);
+    for member in members {
+        cmd.arg(member);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn zremrangebylex<K>(key: K, min: LexBound, max: LexBound) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("ZREMRANGEBYLEX"; args => key, min, max))
+}
+
+pub(crate) fn zremrangebyrank<K>(key: K, start: isize, stop: isize) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("ZREMRANGEBYRANK"; args => key, start, stop))
+}
+
+pub(crate) fn zremrangebyscore<K, Min, Max>(key: K, min: Min, max: Max) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    Min: Into<ScoreBound>,
+    Max: Into<ScoreBound>,
+{
+    Call::new(command!("ZREMRANGEBYSCORE"; args => key, min.into(), max.into()))
+}
+
+pub(crate) fn zrevrank<K, M>(key: K, member: M) -> Call<Option<usize>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+{
+    Call::new(command!("ZREVRANK"; args => key, member))
+}
+
+pub(crate) fn zscore<K, M>(key: K, member: M) -> Call<Option<f64>>
+where
+    K: RedisSerializationProtocol,
+    M: RedisSerializationProtocol,
+{
+    Call::new(command!("ZSCORE"; args => key, member))
+}
+
+pub(crate) fn zunion<K, T, I>(keys: I, options: ZAggregateOptions, with_scores: bool) -> Call<T>
+where
+    K: RedisSerializationProtocol,
+    T: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+    let mut cmd = command!("ZUNION"; args => keys.len(), keys, options);
+    if with_scores {
+        cmd.arg("WITHSCORES");
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn zunionstore<D, K, I>(destination: D, keys: I, options: ZAggregateOptions) -> Call<usize>
+where
+    D: RedisSerializationProtocol,
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+    let cmd = command!("ZUNIONSTORE"; args => destination, keys.len(), keys, options);
+    Call::new(cmd)
+}
+
+// Streams commands
+
+pub(crate) fn xack<K, G, I>(key: K, group: G, ids: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    G: RedisSerializationProtocol,
+    I: IntoIterator<Item = StreamId>,
+{
+    let mut cmd = command!("XACK"; args => key, group);
+    for id in ids {
+        cmd.arg(id);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn xadd<K, F, V, I>(key: K, fields: I) -> Call<StreamId>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+    I: IntoIterator<Item = (F, V)>,
+{
+    let mut cmd = command!("XADD"; args => key, "*");
+    for (field, value) in fields {
+        cmd.arg(field).arg(value);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn xadd_options<K, F, V, I>(key: K, fields: I, options: XAddOptions) -> Call<Option<StreamId>>
+where
+    K: RedisSerializationProtocol,
+    F: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+    I: IntoIterator<Item = (F, V)>,
+{
+    let mut cmd = command!("XADD"; args => key, options);
+    for (field, value) in fields {
+        cmd.arg(field).arg(value);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn xautoclaim<K, G, C, V>(
+    key: K,
+    group: G,
+    consumer: C,
+    min_idle_time: Duration,
+    start: StreamId,
+    count: Option<usize>,
+) -> Call<AutoClaim<V>>
+where
+    K: RedisSerializationProtocol,
+    G: RedisSerializationProtocol,
+    C: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    let min_idle_time = min_idle_time.as_millis() as u64;
+    let mut cmd = command!("XAUTOCLAIM"; args => key, group, consumer, min_idle_time, start);
+    if let Some(count) = count {
+        cmd.arg("COUNT").arg(count);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn xclaim<K, G, C, V, I>(
+    key: K,
+    group: G,
+    consumer: C,
+    min_idle_time: Duration,
+    ids: I,
+) -> Call<Vec<StreamEntry<V>>>
+where
+    K: RedisSerializationProtocol,
+    G: RedisSerializationProtocol,
+    C: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+    I: IntoIterator<Item = StreamId>,
+{
+    let min_idle_time = min_idle_time.as_millis() as u64;
+    let mut cmd = command!("XCLAIM"; args => key, group, consumer, min_idle_time);
+    for id in ids {
+        cmd.arg(id);
+    }
+    Call::with_decoder(cmd, streams::entries)
+}
+
+pub(crate) fn xdel<K, I>(key: K, ids: I) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    I: IntoIterator<Item = StreamId>,
+{
+    let mut cmd = command!("XDEL"; args => key);
+    for id in ids {
+        cmd.arg(id);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn xgroup_create<K, G>(key: K, group: G, id: ReadFrom, mkstream: bool) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+    G: RedisSerializationProtocol,
+{
+    let mut cmd = command!("XGROUP"; args => "CREATE", key, group, id);
+    cmd.non_retryable();
+    if mkstream {
+        cmd.arg("MKSTREAM");
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn xgroup_destroy<K, G>(key: K, group: G) -> Call<bool>
+where
+    K: RedisSerializationProtocol,
+    G: RedisSerializationProtocol,
+{
+    Call::new(command!("XGROUP"; args => "DESTROY", key, group))
+}
+
+pub(crate) fn xgroup_setid<K, G>(key: K, group: G, id: ReadFrom) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+    G: RedisSerializationProtocol,
+{
+    Call::new(command!("XGROUP"; args => "SETID", key, group, id))
+}
+
+pub(crate) fn xinfo_consumers<K, G>(key: K, group: G) -> Call<Vec<ConsumerInfo>>
+where
+    K: RedisSerializationProtocol,
+    G: RedisSerializationProtocol,
+{
+    Call::new(command!("XINFO"; args => "CONSUMERS", key, group))
+}
+
+pub(crate) fn xinfo_groups<K>(key: K) -> Call<Vec<GroupInfo>>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("XINFO"; args => "GROUPS", key))
+}
+
+pub(crate) fn xinfo_stream<K, V>(key: K) -> Call<StreamInfo<V>>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    Call::new(command!("XINFO"; args => "STREAM", key))
+}
+
+pub(crate) fn xlen<K>(key: K) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("XLEN"; args => key))
+}
+
+pub(crate) fn xpending<K, G>(key: K, group: G) -> Call<PendingSummary>
+where
+    K: RedisSerializationProtocol,
+    G: RedisSerializationProtocol,
+{
+    Call::new(command!("XPENDING"; args => key, group))
+}
+
+pub(crate) fn xpending_range<K, G>(key: K, group: G, range: PendingRange) -> Call<Vec<PendingEntry>>
+where
+    K: RedisSerializationProtocol,
+    G: RedisSerializationProtocol,
+{
+    Call::new(command!("XPENDING"; args => key, group, range))
+}
+
+pub(crate) fn xrange<K, V>(
+    key: K,
+    start: StreamBound,
+    end: StreamBound,
+    count: Option<usize>,
+) -> Call<Vec<StreamEntry<V>>>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    let mut cmd = command!("XRANGE"; args => key, start, end);
+    if let Some(count) = count {
+        cmd.arg("COUNT").arg(count);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn xread<K, V, I>(keys: I, options: XReadOptions) -> Call<Vec<StreamKey<V>>>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+    I: IntoIterator<Item = (K, ReadFrom)>,
+{
+    let (keys, ids): (Vec<K>, Vec<ReadFrom>) = keys.into_iter().unzip();
+    let cmd = command!("XREAD"; args => options, "STREAMS", keys, ids);
+    Call::with_decoder(cmd, |reply| {
+        Ok(<Option<Vec<StreamKey<V>>>>::deserialization(reply)?.unwrap_or_default())
+    })
+    .block(options.block_timeout())
+}
+
+pub(crate) fn xreadgroup<G, C, K, V, I>(
+    group: G,
+    consumer: C,
+    keys: I,
+    options: XReadOptions,
+) -> Call<Vec<StreamKey<V>>>
+where
+    G: RedisSerializationProtocol,
+    C: RedisSerializationProtocol,
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+    I: IntoIterator<Item = (K, ReadFrom)>,
+{
+    let (keys, ids): (Vec<K>, Vec<ReadFrom>) = keys.into_iter().unzip();
+    let cmd = command!("XREADGROUP"; args => "GROUP", group, consumer, options, "STREAMS", keys, ids);
+    Call::with_decoder(cmd, |reply| {
+        Ok(<Option<Vec<StreamKey<V>>>>::deserialization(reply)?.unwrap_or_default())
+    })
+    .block(options.block_timeout())
+}
+
+pub(crate) fn xrevrange<K, V>(
+    key: K,
+    end: StreamBound,
+    start: StreamBound,
+    count: Option<usize>,
+) -> Call<Vec<StreamEntry<V>>>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    let mut cmd = command!("XREVRANGE"; args => key, end, start);
+    if let Some(count) = count {
+        cmd.arg("COUNT").arg(count);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn xtrim<K>(key: K, trim: StreamTrim) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("XTRIM"; args => key, trim))
+}
+
+// Strings commands
+
+pub(crate) fn append<K, V>(key: K, value: V) -> Call<u64>
+where
+    K: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    Call::new(command!("APPEND"; args => key, value))
+}
+
+pub(crate) fn bitcount<K>(key: K, start: Option<i64>, end: Option<i64>) -> Call<u64>
+where
+    K: RedisSerializationProtocol,
+{
+    let mut cmd = command!("BITCOUNT"; args => key);
+    if let Some(start) = start {
+        cmd.arg(start);
+    }
+    if let Some(end) = end {
+        cmd.arg(end);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn bitop<K1, K2, I>(operation: &str, destkey: K1, keys: I) -> Call<usize>
+where
+    K1: RedisSerializationProtocol,
+    K2: RedisSerializationProtocol,
+    I: IntoIterator<Item = K2>,
+{
+    let mut cmd = command!("BITOP"; args => operation, destkey);
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn bitpos<K>(key: K, bit: u8, start: Option<usize>, end: Option<usize>) -> RedisResult<Call<isize>>
+where
+    K: RedisSerializationProtocol,
+{
+    if end.is_some() && start.is_none() {
+        return Err(RedisError::custom(
+            ErrorKind::ClientError,
+            "`start` shouldn't be none when `end` has given",
+        ));
+    }
+
+    let mut cmd = command!("BITPOS"; args => key, bit);
+    if let Some(start) = start {
+        cmd.arg(start);
+    }
+    if let Some(end) = end {
+        cmd.arg(end);
+    }
+    Ok(Call::new(cmd))
+}
+
+pub(crate) fn decr<K>(key: K) -> Call<i64>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("DECR"; args => key))
+}
+
+pub(crate) fn decrby<K>(key: K, decrement: i64) -> Call<i64>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("DECRBY"; args => key, decrement))
+}
+
+pub(crate) fn get<K, V>(key: K) -> Call<V>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+{
+    Call::new(command!("GET"; args => key))
+}
+
+pub(crate) fn getbit<K>(key: K, offset: i64) -> Call<u8>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("GETBIT"; args => key, offset))
+}
+
+pub(crate) fn getrange<K>(key: K, start: i64, end: i64) -> Call<String>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("GETRANGE"; args => key, start, end))
+}
+
+pub(crate) fn getset<K, V>(key: K, value: V) -> Call<Option<String>>
+where
+    K: RedisSerializationProtocol,
+    V: ToString,
+{
+    Call::new(command!("GETSET"; args => key, value.to_string()))
+}
+
+pub(crate) fn incr<K>(key: K) -> Call<i64>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("INCR"; args => key))
+}
+
+pub(crate) fn incrby<K>(key: K, increment: i64) -> Call<i64>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("INCRBY"; args => key, increment))
+}
+
+pub(crate) fn incrbyfloat<K>(key: K, increment: f64) -> Call<f64>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("INCRBYFLOAT"; args => key, increment))
+}
+
+pub(crate) fn mget<K, V, I>(keys: I) -> Call<Vec<V>>
+where
+    K: RedisSerializationProtocol,
+    V: RedisDeserializationProtocol,
+    I: IntoIterator<Item = K>,
+{
+    let mut cmd = Command::new("MGET");
+    for key in keys {
+        cmd.arg(key);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn mset<K, V>(kvs: Vec<(K, V)>) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    let mut cmd = Command::new("MSET");
+    for (k, v) in kvs {
+        cmd.arg(k).arg(v);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn msetnx<K, V>(kvs: Vec<(K, V)>) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    let mut cmd = Command::new("MSETNX");
+    for (k, v) in kvs {
+        cmd.arg(k).arg(v);
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn psetex<K, V>(key: K, milliseconds: u64, value: V) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    Call::new(command!("PSETEX"; args => key, milliseconds, value))
+}
+
+pub(crate) fn set<K, V>(
+    key: K,
+    value: V,
+    ex_seconds: Option<u64>,
+    px_milliseconds: Option<u64>,
+    nx: Option<bool>,
+    xx: Option<bool>,
+) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    let mut cmd = command!("SET"; args => key, value);
+    if let Some(ex) = ex_seconds {
+        cmd.arg("EX").arg(ex);
+    }
+    if let Some(px) = px_milliseconds {
+        cmd.arg("PX").arg(px);
+    }
+    if let Some(nx) = nx {
+        if nx {
+            cmd.arg("NX").non_retryable();
+        }
+    }
+    if let Some(xx) = xx {
+        if xx {
+            cmd.arg("XX");
+        }
+    }
+    Call::new(cmd)
+}
+
+pub(crate) fn setbit<K>(key: K, offset: usize, value: u8) -> Call<u8>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("SETBIT"; args => key, offset, value))
+}
+
+pub(crate) fn setex<K, V>(key: K, seconds: usize, value: V) -> Call<()>
+where
+    K: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    Call::new(command!("SETEX"; args => key, seconds, value))
+}
+
+pub(crate) fn setnx<K, V>(key: K, value: V) -> Call<bool>
+where
+    K: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    Call::new(command!("SETNX"; args => key, value))
+}
+
+pub(crate) fn setrange<K, V>(key: K, offset: usize, value: V) -> Call<usize>
+where
+    K: RedisSerializationProtocol,
+    V: RedisSerializationProtocol,
+{
+    Call::new(command!("SETRANGE"; args => key, offset, value))
+}
+
+pub(crate) fn strlen<K>(key: K) -> Call<u64>
+where
+    K: RedisSerializationProtocol,
+{
+    Call::new(command!("STRLEN"; args => key))
+}
@@ -1,134 +1,463 @@
 use std::io::{BufRead, BufReader, Read, Write};
 use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
 
-use crate::error::ErrorKind::{AuthenticationFailed, FromServer};
+use crate::client::Command;
+use crate::config::{ProtocolVersion, RedisConfig};
+use crate::error::ErrorKind::{AuthenticationFailed, ExecAbort, FromServer};
 use crate::error::{ErrorKind::ResponseError, RedisError};
 use crate::RedisResult;
 
+const SINGLE_STRINGS: u8 = b'+';
+const ERRORS: u8 = b'-';
+const INTEGERS: u8 = b':';
+const BULK_STRINGS: u8 = b'$';
+const ARRAYS: u8 = b'*';
+// RESP3
+const NULL: u8 = b'_';
+const DOUBLE: u8 = b',';
+const BOOLEAN: u8 = b'#';
+const BLOB_ERRORS: u8 = b'!';
+const VERBATIM_STRINGS: u8 = b'=';
+const BIG_NUMBER: u8 = b'(';
+const MAP: u8 = b'%';
+const SET: u8 = b'~';
+const ATTRIBUTE: u8 = b'|';
+const PUSH: u8 = b'>';
+
+// The largest bulk string redis accepts, 512 MB.
+pub(crate) const MAX_BULK_LENGTH: i64 = 512 * 1024 * 1024;
+// Aggregates grow past this as their elements arrive, rather than trusting the announced length.
+const MAX_PREALLOCATED: usize = 1024;
+
+// The transport a connection runs over.
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+    #[cfg(feature = "tls")]
+    Tls(Box<crate::tls::TlsStream>),
+}
+
+impl Stream {
+    // the connector only holds the TLS setup for now
+    #[cfg_attr(not(feature = "tls"), allow(unused_variables))]
+    fn connect(config: &RedisConfig, connector: &Connector) -> RedisResult<Stream> {
+        if let Some(path) = config.address.strip_prefix("unix://") {
+            return Self::connect_unix(path, config);
+        }
+
+        let stream = match config.connect_timeout {
+            Some(timeout) => connect_timeout(&config.address, timeout)?,
+            None => TcpStream::connect(&config.address)?,
+        };
+        // set before the TLS handshake, which reads and writes too
+        stream.set_read_timeout(config.read_timeout)?;
+        stream.set_write_timeout(config.write_timeout)?;
+
+        #[cfg(feature = "tls")]
+        if let Some(ref tls) = connector.tls {
+            let stream = tls.connect(stream, host(&config.address))?;
+            return Ok(Stream::Tls(Box::new(stream)));
+        }
+        Ok(Stream::Tcp(stream))
+    }
+
+    // connecting to a Unix domain socket doesn't block, so there's no connect timeout
+    #[cfg(unix)]
+    fn connect_unix(path: &str, config: &RedisConfig) -> RedisResult<Stream> {
+        let stream = UnixStream::connect(path)?;
+        stream.set_read_timeout(config.read_timeout)?;
+        stream.set_write_timeout(config.write_timeout)?;
+        Ok(Stream::Unix(stream))
+    }
+
+    #[cfg(not(unix))]
+    fn connect_unix(_path: &str, _config: &RedisConfig) -> RedisResult<Stream> {
+        Err(RedisError::custom(
+            crate::error::ErrorKind::InvalidConfig,
+            "Unix domain sockets aren't supported on this platform",
+        ))
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.set_read_timeout(timeout),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.set_read_timeout(timeout),
+            #[cfg(feature = "tls")]
+            Stream::Tls(stream) => stream.get_ref().set_read_timeout(timeout),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.read(buf),
+            #[cfg(feature = "tls")]
+            Stream::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.write(buf),
+            #[cfg(feature = "tls")]
+            Stream::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.flush(),
+            #[cfg(feature = "tls")]
+            Stream::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+/// What the connections of a pool share, prepared once from the config.
+pub(crate) struct Connector {
+    #[cfg(feature = "tls")]
+    tls: Option<crate::tls::TlsConnector>,
+}
+
+impl Connector {
+    #[cfg(feature = "tls")]
+    pub(crate) fn new(config: &RedisConfig) -> RedisResult<Connector> {
+        let tls = config.tls.as_ref().map(crate::tls::TlsConnector::new).transpose()?;
+        Ok(Connector { tls })
+    }
+
+    #[cfg(not(feature = "tls"))]
+    pub(crate) fn new(_config: &RedisConfig) -> RedisResult<Connector> {
+        Ok(Connector {})
+    }
+}
+
 pub(crate) struct Connection {
-    conn: TcpStream,
-    reader: BufReader<TcpStream>,
+    // writes go through `get_mut`, bypassing the buffer
+    stream: BufReader<Stream>,
+    // the selected database
+    database: u8,
+    // the read timeout of the stream
+    read_timeout: Option<Duration>,
 }
 
 impl Connection {
-    const SINGLE_STRINGS: u8 = b'+';
-    const ERRORS: u8 = b'-';
-    const INTEGERS: u8 = b':';
-    const BULK_STRINGS: u8 = b'$';
-    const ARRAYS: u8 = b'*';
+    /// Connects to `config.address`, either `host:port` or `unix:///path/to/redis.sock`.
+    pub(crate) fn connect(config: &RedisConfig, connector: &Connector) -> RedisResult<Connection> {
+        let stream = Stream::connect(config, connector)?;
+
+        Ok(Connection {
+            stream: BufReader::new(stream),
+            database: 0,
+            read_timeout: config.read_timeout,
+        })
+    }
+
+    /// Prepares a new connection according to the config, see `init_commands`.
+    pub(crate) fn init(&mut self, config: &RedisConfig) -> RedisResult<()> {
+        for cmd in init_commands(config) {
+            self.execute(&cmd)?;
+        }
+        self.database = config.database;
+        Ok(())
+    }
 
-    fn new(stream: TcpStream) -> RedisResult<Connection> {
-        let reader = BufReader::new(stream.try_clone()?);
+    pub(crate) fn database(&self) -> u8 {
+        self.database
+    }
 
-        Ok(Connection { conn: stream, reader })
+    pub(crate) fn select(&mut self, database: u8) -> RedisResult<()> {
+        let mut cmd = Command::new("SELECT");
+        self.execute(cmd.arg(database))?;
+        self.database = database;
+        Ok(())
     }
 
-    pub(crate) fn connect<A: ToSocketAddrs>(addr: A) -> RedisResult<Connection> {
-        let stream = TcpStream::connect(addr)?;
+    pub(crate) fn ping(&mut self) -> RedisResult<()> {
+        self.execute(&Command::new("PING"))?;
+        Ok(())
+    }
 
-        Self::new(stream)
+    fn execute(&mut self, cmd: &Command) -> RedisResult<Reply> {
+        self.send(&cmd.to_vec())?;
+        self.receive()
+    }
+
+    /// Overrides the read timeout from the config, e.g. for a blocking command.
+    pub(crate) fn set_read_timeout(&mut self, timeout: Option<Duration>) -> RedisResult<()> {
+        if self.read_timeout != timeout {
+            self.stream.get_ref().set_read_timeout(timeout)?;
+            self.read_timeout = timeout;
+        }
+        Ok(())
     }
 
     pub(crate) fn send(&mut self, data: &[u8]) -> RedisResult<()> {
-        self.conn.write_all(data)?;
+        self.stream.get_mut().write_all(data)?;
         Ok(())
     }
 
+    /// Reads one reply, an error reply is returned as `Err`.
     pub(crate) fn receive(&mut self) -> RedisResult<Reply> {
-        let mut buffer = Vec::new();
-        self.reader.read_until(b'\n', &mut buffer)?;
-        if buffer.len() < 3 {
-            return Err(RedisError::custom(ResponseError, "Empty redis response"));
-        }
-        if buffer == b"NIL" {
-            // TODO: remove
-            return Ok(Reply::Nil);
+        self.read_reply()?.into_result()
+    }
+
+    /// Reads one reply, an error reply is returned as `Reply::Errors`.
+    pub(crate) fn read_reply(&mut self) -> RedisResult<Reply> {
+        loop {
+            match parse_reply(&mut self.stream)? {
+                // out of band data (e.g. client side caching invalidations) isn't supported yet
+                Reply::Push(_) => continue,
+                reply => return Ok(reply),
+            }
         }
+    }
+}
 
-        let prefix = buffer[0];
-        let buffer = &buffer[1..buffer.len() - 2]; // remove prefix and '\r\n'
-
-        match prefix {
-            Self::SINGLE_STRINGS => self.read_single_strings(Vec::from(buffer)),
-            Self::ERRORS => self.read_errors(Vec::from(buffer)),
-            Self::INTEGERS => self.read_integer(Vec::from(buffer)),
-            Self::BULK_STRINGS => self.read_bulk_strings(String::from_utf8_lossy(buffer).parse::<i64>()?),
-            Self::ARRAYS => self.read_array(String::from_utf8_lossy(buffer).parse::<u64>()?),
-            _ => Err(RedisError::custom(
-                ResponseError,
-                format!("invalid prefix {:?}", prefix as char),
-            )),
+/// The commands preparing a new connection according to the config: switching it to RESP3,
+/// authenticating, naming it and selecting the database.
+pub(crate) fn init_commands(config: &RedisConfig) -> Vec<Command> {
+    let mut commands = Vec::new();
+    match config.protocol {
+        ProtocolVersion::Resp3 => {
+            let mut cmd = Command::new("HELLO");
+            cmd.arg(3);
+            if let Some(ref password) = config.password {
+                let username = config.username.as_deref().unwrap_or("default");
+                cmd.arg("AUTH").arg(username).arg(password);
+            }
+            if let Some(ref name) = config.client_name {
+                cmd.arg("SETNAME").arg(name);
+            }
+            commands.push(cmd);
+        }
+        ProtocolVersion::Resp2 => {
+            if let Some(ref password) = config.password {
+                let mut cmd = Command::new("AUTH");
+                if let Some(ref username) = config.username {
+                    cmd.arg(username);
+                }
+                cmd.arg(password);
+                commands.push(cmd);
+            }
+            if let Some(ref name) = config.client_name {
+                let mut cmd = Command::new("CLIENT");
+                cmd.arg("SETNAME").arg(name);
+                commands.push(cmd);
+            }
         }
     }
 
-    fn read_single_strings(&mut self, buffer: Vec<u8>) -> RedisResult<Reply> {
-        // TODO
-        if buffer == b"OK" {
-            return Ok(Reply::SingleStrings(SingleStrings::Okay));
-        } else if buffer == b"PONG" {
-            return Ok(Reply::SingleStrings(SingleStrings::Pong));
-        } else if buffer == b"string" {
-            return Ok(Reply::SingleStrings(SingleStrings::String));
-        } else if buffer == b"list" {
-            return Ok(Reply::SingleStrings(SingleStrings::List));
-        } else if buffer == b"set" {
-            return Ok(Reply::SingleStrings(SingleStrings::Set));
+    if config.database != 0 {
+        let mut cmd = Command::new("SELECT");
+        cmd.arg(config.database);
+        commands.push(cmd);
+    }
+    commands
+}
+
+// The host of a `host:port` address, without the brackets of an IPv6 address.
+#[cfg(feature = "tls")]
+fn host(address: &str) -> &str {
+    let host = address.rsplit_once(':').map_or(address, |(host, _)| host);
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+// Tries every address `addr` resolves to, like `TcpStream::connect` does.
+fn connect_timeout<A: ToSocketAddrs>(addr: A, timeout: Duration) -> RedisResult<TcpStream> {
+    let mut error = None;
+    for addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => error = Some(e),
         }
-        Ok(Reply::SingleStrings(SingleStrings::Okay))
     }
+    Err(error
+        .unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "could not resolve to any addresses"))
+        .into())
+}
+
+/// Reads one complete reply from `reader`, descending into nested arrays.
+///
+/// Error replies, including those nested in arrays, are kept as `Reply::Errors`.
+/// An incomplete reply results in an `UnexpectedEof` I/O error.
+pub fn parse_reply<R: BufRead>(reader: &mut R) -> RedisResult<Reply> {
+    let line = read_line(reader)?;
+    let prefix = line[0];
+    let content = &line[1..];
 
-    fn read_errors(&mut self, buffer: Vec<u8>) -> RedisResult<Reply> {
-        let error = String::from_utf8_lossy(&buffer).into_owned();
-        if error.starts_with("WRONGPASS") {
-            return Err(RedisError::custom(AuthenticationFailed, error));
+    match prefix {
+        SINGLE_STRINGS => Ok(Reply::Status(content.to_vec())),
+        ERRORS => Ok(Reply::Errors(String::from_utf8_lossy(content).into_owned())),
+        INTEGERS => Ok(Reply::Integers(content.to_vec())),
+        BULK_STRINGS => read_bulk_strings(reader, parse_length(content)?),
+        ARRAYS => read_array(reader, parse_length(content)?),
+        NULL => Ok(Reply::Null),
+        DOUBLE => Ok(Reply::Double(std::str::from_utf8(content)?.parse::<f64>()?)),
+        BOOLEAN => match content {
+            b"t" => Ok(Reply::Boolean(true)),
+            b"f" => Ok(Reply::Boolean(false)),
+            _ => Err(RedisError::custom(ResponseError, "invalid boolean")),
+        },
+        BLOB_ERRORS => match read_bulk_strings(reader, parse_length(content)?)? {
+            Reply::BulkStrings(error) => Ok(Reply::Errors(String::from_utf8_lossy(&error).into_owned())),
+            _ => Err(RedisError::custom(ResponseError, "invalid blob error")),
+        },
+        VERBATIM_STRINGS => read_verbatim_strings(reader, parse_length(content)?),
+        BIG_NUMBER => Ok(Reply::BigNumber(content.to_vec())),
+        MAP => Ok(Reply::Map(read_pairs(reader, parse_length(content)?)?)),
+        SET => match read_array(reader, parse_length(content)?)? {
+            Reply::Arrays(array) => Ok(Reply::Set(array)),
+            reply => Ok(reply),
+        },
+        ATTRIBUTE => {
+            let attributes = read_pairs(reader, parse_length(content)?)?;
+            let reply = parse_reply(reader)?;
+            Ok(Reply::Attribute {
+                attributes,
+                reply: Box::new(reply),
+            })
         }
-        Err(RedisError::custom(FromServer, error))
+        PUSH => match read_array(reader, parse_length(content)?)? {
+            Reply::Arrays(array) => Ok(Reply::Push(array)),
+            reply => Ok(reply),
+        },
+        _ => Err(RedisError::custom(
+            ResponseError,
+            format!("invalid prefix {:?}", prefix as char),
+        )),
     }
+}
 
-    fn read_integer(&mut self, buffer: Vec<u8>) -> RedisResult<Reply> {
-        Ok(Reply::Integers(buffer))
+// Reads a line and removes its trailing '\r\n'.
+fn read_line<R: BufRead>(reader: &mut R) -> RedisResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    reader.read_until(b'\n', &mut buffer)?;
+    if !buffer.ends_with(b"\r\n") {
+        return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "incomplete redis response").into());
+    }
+    if buffer.len() < 3 {
+        return Err(RedisError::custom(ResponseError, "Empty redis response"));
     }
 
-    fn read_bulk_strings(&mut self, size: i64) -> RedisResult<Reply> {
-        if size < 0 {
-            // TODO
-            return Ok(Reply::Nil);
-        }
+    buffer.truncate(buffer.len() - 2);
+    Ok(buffer)
+}
+
+fn parse_length(buffer: &[u8]) -> RedisResult<i64> {
+    Ok(std::str::from_utf8(buffer)?.parse::<i64>()?)
+}
 
-        let mut buf = vec![0; (size + 2) as usize];
-        self.reader.read_exact(&mut buf)?;
-        buf.truncate(buf.len() - 2);
-        Ok(Reply::BulkStrings(buf))
+fn read_bulk_strings<R: BufRead>(reader: &mut R, size: i64) -> RedisResult<Reply> {
+    if size < 0 {
+        return Ok(Reply::Nil);
+    }
+    if size > MAX_BULK_LENGTH {
+        return Err(RedisError::custom(
+            ResponseError,
+            format!("bulk string of {} bytes exceeds the 512 MB limit", size),
+        ));
     }
 
-    fn read_array(&mut self, len: u64) -> RedisResult<Reply> {
-        let mut result = Vec::with_capacity(len as usize);
-        for _ in 0..len {
-            let mut buf = Vec::new();
-            self.reader.read_until(b'\n', &mut buf)?;
-            let size = String::from_utf8_lossy(&buf[1..(buf.len() - 2)]).parse::<i64>()?;
-            let v = self.read_bulk_strings(size)?;
-            result.push(v);
-        }
+    let mut buf = vec![0; (size + 2) as usize];
+    reader.read_exact(&mut buf)?;
+    if !buf.ends_with(b"\r\n") {
+        return Err(RedisError::custom(ResponseError, "bulk string not terminated by CRLF"));
+    }
+    buf.truncate(buf.len() - 2);
+    Ok(Reply::BulkStrings(buf))
+}
 
-        Ok(Reply::Arrays(result))
+fn read_array<R: BufRead>(reader: &mut R, len: i64) -> RedisResult<Reply> {
+    if len < 0 {
+        return Ok(Reply::Nil);
+    }
+
+    let mut result = Vec::with_capacity((len as usize).min(MAX_PREALLOCATED));
+    for _ in 0..len {
+        result.push(parse_reply(reader)?);
+    }
+
+    Ok(Reply::Arrays(result))
+}
+
+fn read_verbatim_strings<R: BufRead>(reader: &mut R, size: i64) -> RedisResult<Reply> {
+    match read_bulk_strings(reader, size)? {
+        // the first three bytes are the format, followed by ':'
+        Reply::BulkStrings(buf) if buf.len() >= 4 && buf[3] == b':' => Ok(Reply::VerbatimString {
+            format: String::from_utf8_lossy(&buf[..3]).into_owned(),
+            text: buf[4..].to_vec(),
+        }),
+        _ => Err(RedisError::custom(ResponseError, "invalid verbatim string")),
     }
 }
 
-#[derive(Debug, Clone)]
-pub enum SingleStrings {
-    Okay,
-    Pong,
-    String,
-    List,
-    Set,
+fn read_pairs<R: BufRead>(reader: &mut R, len: i64) -> RedisResult<Vec<(Reply, Reply)>> {
+    let mut pairs = Vec::with_capacity((len.max(0) as usize).min(MAX_PREALLOCATED));
+    for _ in 0..len {
+        let key = parse_reply(reader)?;
+        let value = parse_reply(reader)?;
+        pairs.push((key, value));
+    }
+    Ok(pairs)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Reply {
-    SingleStrings(SingleStrings),
+    Status(Vec<u8>),
+    Errors(String),
     Integers(Vec<u8>),
     BulkStrings(Vec<u8>),
     Arrays(Vec<Reply>),
     Nil,
+    // RESP3
+    Null,
+    Double(f64),
+    Boolean(bool),
+    BigNumber(Vec<u8>),
+    VerbatimString {
+        format: String,
+        text: Vec<u8>,
+    },
+    Map(Vec<(Reply, Reply)>),
+    Set(Vec<Reply>),
+    Attribute {
+        attributes: Vec<(Reply, Reply)>,
+        reply: Box<Reply>,
+    },
+    Push(Vec<Reply>),
+}
+
+impl Reply {
+    /// Turns an error reply into the matching `RedisError`, attributes are dropped
+    /// and other replies are returned unchanged.
+    pub fn into_result(self) -> RedisResult<Reply> {
+        match self {
+            Reply::Attribute { reply, .. } => reply.into_result(),
+            Reply::Errors(error) => {
+                if error.starts_with("WRONGPASS") {
+                    return Err(RedisError::custom(AuthenticationFailed, error));
+                }
+                if error.starts_with("EXECABORT") {
+                    return Err(RedisError::custom(ExecAbort, error));
+                }
+                Err(RedisError::custom(FromServer, error))
+            }
+            reply => Ok(reply),
+        }
+    }
 }
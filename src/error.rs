@@ -9,6 +9,16 @@ pub enum ErrorKind {
     Io,
     ClientError,
     FromServer,
+    /// EXEC was rejected because a command failed to queue.
+    ExecAbort,
+    /// EXEC returned nil because a watched key was modified.
+    TransactionAborted,
+    /// The reply is nil, but the target type can't represent it (use an `Option`).
+    NilReply,
+    /// The connection URL or config is malformed.
+    InvalidConfig,
+    /// No connection of the pool became available before the checkout timeout.
+    PoolTimeout,
 }
 
 enum Repr {
@@ -115,7 +125,16 @@ impl RedisError {
     pub fn is_timeout(&self) -> bool {
         match self.repr {
             Repr::Io(ref e) => matches!(e.kind(), std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock),
-            _ => false,
+            Repr::Custom(kind, _) => kind == ErrorKind::PoolTimeout,
+        }
+    }
+
+    /// Whether the connection the error happened on can't be used anymore, e.g. after an I/O
+    /// error or a malformed reply left the stream out of sync. Error replies aren't.
+    pub fn is_unrecoverable(&self) -> bool {
+        match self.repr {
+            Repr::Io(_) => true,
+            Repr::Custom(kind, _) => matches!(kind, ErrorKind::ResponseError | ErrorKind::TypeError),
         }
     }
 
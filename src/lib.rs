@@ -19,7 +19,7 @@
 //! use redisclient::RedisClient;
 //!
 //! fn run() -> RedisResult<()> {
-//!     let mut client = RedisClient::new()?;
+//!     let client = RedisClient::new()?;
 //!     client.mset(vec![("key1", 1), ("key2", 2)])?;
 //!
 //!     let values: Vec<String> = client.mget(vec!["key1", "key2"])?;
@@ -32,6 +32,8 @@
 //! }
 //! ```
 
+#[cfg(feature = "tokio")]
+pub mod aio;
 pub mod client;
 pub mod config;
 pub mod connection;
@@ -40,15 +42,29 @@ pub mod macros;
 pub mod pipeline;
 pub mod pool;
 pub mod protocol;
+pub mod scan;
+pub mod sorted_sets;
+pub mod streams;
 
-pub use client::RedisClient;
+mod commands;
+#[cfg(feature = "tls")]
+mod tls;
+
+#[cfg(feature = "tokio")]
+pub use aio::AsyncRedisClient;
+pub use client::{cmd, Command, RedisClient};
 pub use error::{ErrorKind, RedisError};
 
 pub type RedisResult<T> = std::result::Result<T, RedisError>;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DataType {
     String,
     List,
     Set,
+    Hash,
+    ZSet,
+    Stream,
+    /// e.g. `none` for a missing key, or a type defined by a module.
+    Other(String),
 }
@@ -1,27 +1,38 @@
-use crate::client::Command;
-use crate::RedisClient;
+use std::time::Duration;
 
-#[allow(dead_code)]
-pub struct Pipeline<'a> {
-    client: &'a RedisClient, // TODO
+#[cfg(feature = "tokio")]
+use crate::aio::AsyncRedisClient;
+use crate::client::{command, Command, LPosOptions, ListBeforeOrAfter, ListDirection};
+use crate::connection::{Connection, Reply};
+use crate::error::{ErrorKind, RedisError};
+use crate::protocol::{RedisDeserializationProtocol, RedisSerializationProtocol};
+use crate::sorted_sets::{LexBound, ScoreBound, ZAddOptions, ZAggregateOptions, ZRange};
+use crate::streams::{PendingRange, ReadFrom, StreamBound, StreamId, StreamTrim, XAddOptions, XReadOptions};
+use crate::{RedisClient, RedisResult};
+
+/// A pipeline buffers commands and sends them to the server in a single round trip.
+///
+/// The replies are returned in the same order as the commands were added.
+#[derive(Default)]
+pub struct Pipeline {
     commands: Vec<Command>,
     transaction: bool,
 }
 
-impl<'a> Pipeline<'a> {
-    pub(crate) fn new(client: &RedisClient) -> Pipeline {
-        Self::with_capacity(client, 0)
+impl Pipeline {
+    pub fn new() -> Pipeline {
+        Self::with_capacity(0)
     }
 
-    pub(crate) fn with_capacity(client: &RedisClient, capacity: usize) -> Pipeline {
+    pub fn with_capacity(capacity: usize) -> Pipeline {
         Pipeline {
-            client,
             commands: Vec::with_capacity(capacity),
             transaction: false,
         }
     }
 
-    pub fn transaction_mode(&'a mut self) -> &mut Pipeline {
+    /// Wraps the buffered commands in MULTI/EXEC, so they are executed atomically.
+    pub fn transaction_mode(&mut self) -> &mut Pipeline {
         self.transaction = true;
         self
     }
@@ -29,4 +40,1645 @@ impl<'a> Pipeline<'a> {
     pub fn clear(&mut self) {
         self.commands.clear();
     }
+
+    /// Returns the number of buffered commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Sends all buffered commands over one connection and deserializes the replies,
+    /// e.g. into a tuple or a `Vec`.
+    pub fn query<T>(&self, client: &RedisClient) -> RedisResult<T>
+    where
+        T: RedisDeserializationProtocol,
+    {
+        let replies = client.execute_pipeline(self)?;
+        <T>::deserialization(Reply::Arrays(replies))
+    }
+
+    /// Sends all buffered commands with the async client, like `query` does.
+    #[cfg(feature = "tokio")]
+    pub async fn query_async<T>(&self, client: &AsyncRedisClient) -> RedisResult<T>
+    where
+        T: RedisDeserializationProtocol,
+    {
+        let replies = client.execute_pipeline(self).await?;
+        <T>::deserialization(Reply::Arrays(replies))
+    }
+
+    // A pipeline may be retried once sent only if all of its commands may.
+    pub(crate) fn is_retryable(&self) -> bool {
+        self.commands.iter().all(Command::is_retryable)
+    }
+
+    pub(crate) fn execute(&self, conn: &mut Connection) -> RedisResult<Vec<Reply>> {
+        if self.commands.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        conn.send(&self.to_vec())?;
+        // read every reply before reporting an error, so the stream stays in sync
+        let mut replies = Vec::with_capacity(self.reply_count());
+        for _ in 0..self.reply_count() {
+            replies.push(conn.read_reply()?);
+        }
+        self.results(replies)
+    }
+
+    // The buffered commands, wrapped in MULTI/EXEC in transaction mode.
+    pub(crate) fn to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        if self.transaction {
+            buf.extend(Command::new("MULTI").to_vec());
+        }
+        for cmd in &self.commands {
+            buf.extend(cmd.to_vec());
+        }
+        if self.transaction {
+            buf.extend(Command::new("EXEC").to_vec());
+        }
+        buf
+    }
+
+    // The number of replies the server sends back for `to_vec`.
+    pub(crate) fn reply_count(&self) -> usize {
+        if self.transaction {
+            self.commands.len() + 2
+        } else {
+            self.commands.len()
+        }
+    }
+
+    // Turns the replies the server sent back into the replies of the commands.
+    pub(crate) fn results(&self, replies: Vec<Reply>) -> RedisResult<Vec<Reply>> {
+        if !self.transaction {
+            return replies.into_iter().map(Reply::into_result).collect();
+        }
+
+        // the reply of MULTI, then one `+QUEUED` (or an error) for each command, then of EXEC
+        let mut replies = replies.into_iter();
+        let mut error = None;
+        for reply in replies.by_ref().take(self.commands.len() + 1) {
+            if let Err(e) = reply.into_result() {
+                error.get_or_insert(e);
+            }
+        }
+
+        match replies.next().map(Reply::into_result) {
+            Some(Ok(Reply::Arrays(replies))) => replies.into_iter().map(Reply::into_result).collect(),
+            Some(Ok(Reply::Nil)) | Some(Ok(Reply::Null)) => Err(RedisError::custom(
+                ErrorKind::TransactionAborted,
+                "Transaction aborted, a watched key has been modified",
+            )),
+            Some(Err(e)) => match error {
+                Some(cause) if e.kind() == ErrorKind::ExecAbort => {
+                    Err(RedisError::custom(ErrorKind::ExecAbort, format!("{} ({})", e, cause)))
+                }
+                _ => Err(e),
+            },
+            _ => Err(RedisError::custom(ErrorKind::ResponseError, "invalid EXEC reply")),
+        }
+    }
+
+    fn add(&mut self, cmd: Command) -> &mut Self {
+        self.commands.push(cmd);
+        self
+    }
+
+    /// Adds a command built with [`cmd`](crate::cmd), for commands the pipeline doesn't wrap.
+    pub fn add_command(&mut self, cmd: &Command) -> &mut Self {
+        self.add(cmd.clone())
+    }
+
+    pub fn flushall(&mut self) -> &mut Self {
+        self.add(Command::new("FLUSHALL"))
+    }
+
+    // Connection commands
+    /// The AUTH command authenticates the current connection
+    pub fn auth<S>(&mut self, username: Option<S>, password: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        let mut cmd = Command::new("AUTH");
+        if let Some(username) = username {
+            cmd.arg(username.to_string());
+        }
+        cmd.arg(password.to_string());
+        self.add(cmd)
+    }
+
+    /// Returns message.
+    pub fn echo<S>(&mut self, message: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.add(command!("ECHO"; args => message.to_string()))
+    }
+
+    /// Returns PONG if no argument is provided, otherwise return a copy of the argument as a bulk.
+    pub fn ping(&mut self) -> &mut Self {
+        self.add(Command::new("PING"))
+    }
+
+    // no SELECT, the client keeps track of the database of every connection, see `RedisClient::select`
+
+    // Hashes commands
+    /// Removes the specified fields from the hash stored at key.
+    pub fn hdel<K, I>(&mut self, key: K, fields: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = command!("HDEL"; args => key);
+        for field in fields {
+            cmd.arg(field);
+        }
+        self.add(cmd)
+    }
+
+    /// Returns if field is an existing field in the hash stored at key.
+    pub fn hexists<K, F>(&mut self, key: K, field: F) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+    {
+        self.add(command!("HEXISTS"; args => key, field))
+    }
+
+    /// Returns the value associated with field in the hash stored at key.
+    pub fn hget<K, F>(&mut self, key: K, field: F) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+    {
+        self.add(command!("HGET"; args => key, field))
+    }
+
+    /// Returns all fields and values of the hash stored at key.
+    pub fn hgetall<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("HGETALL"; args => key))
+    }
+
+    /// Increments the number stored at field in the hash stored at key by increment.
+    pub fn hincrby<K, F>(&mut self, key: K, field: F, increment: i64) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+    {
+        self.add(command!("HINCRBY"; args => key, field, increment))
+    }
+
+    /// Increment the specified field of a hash stored at key, and representing a floating point number, by the specified increment.
+    pub fn hincrbyfloat<K, F>(&mut self, key: K, field: F, increment: f64) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+    {
+        self.add(command!("HINCRBYFLOAT"; args => key, field, increment))
+    }
+
+    /// Returns all field names in the hash stored at key.
+    pub fn hkeys<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("HKEYS"; args => key))
+    }
+
+    /// Returns the number of fields contained in the hash stored at key.
+    pub fn hlen<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("HLEN"; args => key))
+    }
+
+    /// Returns the values associated with the specified fields in the hash stored at key.
+    pub fn hmget<K, F, I>(&mut self, key: K, fields: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        I: IntoIterator<Item = F>,
+    {
+        let mut cmd = command!("HMGET"; args => key);
+        for field in fields {
+            cmd.arg(field);
+        }
+        self.add(cmd)
+    }
+
+    /// Sets the specified fields to their respective values in the hash stored at key.
+    pub fn hmset<K, F, V>(&mut self, key: K, fvs: Vec<(F, V)>) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("HMSET"; args => key);
+        for (field, value) in fvs {
+            cmd.arg(field).arg(value);
+        }
+        self.add(cmd)
+    }
+
+    /// Sets the specified fields to their respective values in the hash stored at key.
+    pub fn hset<K, F, V, I>(&mut self, key: K, fvs: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+        I: IntoIterator<Item = (F, V)>,
+    {
+        let mut cmd = command!("HSET"; args => key);
+        for (field, value) in fvs {
+            cmd.arg(field).arg(value);
+        }
+        self.add(cmd)
+    }
+
+    /// Sets field in the hash stored at key to value, only if field does not yet exist.
+    pub fn hsetnx<K, F, V>(&mut self, key: K, field: F, value: V) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.add(command!("HSETNX"; args => key, field, value))
+    }
+
+    /// Returns the string length of the value associated with field in the hash stored at key.
+    pub fn hstrlen<K, F>(&mut self, key: K, field: F) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+    {
+        self.add(command!("HSTRLEN"; args => key, field))
+    }
+
+    /// Returns all values in the hash stored at key.
+    pub fn hvals<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("HVALS"; args => key))
+    }
+
+    // keys command
+    /// Removes the specified keys. A key is ignored if it does not exist.
+    pub fn del<K, I>(&mut self, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("DEL");
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// Returns if key exists.
+    pub fn exists<K, I>(&mut self, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("EXISTS");
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// Set a timeout on key. After the timeout has expired, the key will automatically be deleted.
+    pub fn expire<K>(&mut self, key: K, seconds: usize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("EXPIRE"; args => key, seconds))
+    }
+
+    /// Returns all keys matching pattern.
+    pub fn keys<S>(&mut self, pattern: S) -> &mut Self
+    where
+        S: ToString,
+    {
+        self.add(command!("KEYS"; args => pattern.to_string()))
+    }
+
+    /// Remove the existing timeout on key, turning the key from volatile to persistent.
+    pub fn persist<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("PERSIST"; args => key))
+    }
+
+    /// This command works exactly like EXPIRE but the time to live of the key is specified in milliseconds instead of seconds.
+    pub fn pexpire<K>(&mut self, key: K, milliseconds: u64) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("PEXPIRE"; args => key, milliseconds))
+    }
+
+    /// Returns the remaining time to live of a key that has an expire set, in milliseconds.
+    pub fn pttl<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("PTTL"; args => key))
+    }
+
+    /// Return a random key from the currently selected database.
+    pub fn randomkey(&mut self) -> &mut Self {
+        self.add(Command::new("RANDOMKEY"))
+    }
+
+    /// Renames key to newkey. It returns an error when key does not exist.
+    pub fn rename<K>(&mut self, key: K, newkey: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("RENAME"; args => key, newkey))
+    }
+
+    /// Renames key to newkey if newkey does not yet exist. It returns an error when key does not exist.
+    pub fn renamenx<K>(&mut self, key: K, newkey: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("RENAMENX"; args => key, newkey))
+    }
+
+    /// Alters the last access time of a key(s). A key is ignored if it does not exist.
+    pub fn touch<K, I>(&mut self, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("TOUCH");
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// Returns the remaining time to live of a key that has a timeout.
+    pub fn ttl<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("TTL"; args => key))
+    }
+
+    /// Returns the string representation of the type of the value stored at key.
+    pub fn type_<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("TYPE"; args => key))
+    }
+
+    /// This command is very similar to DEL: it removes the specified keys.
+    pub fn unlink<K, I>(&mut self, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("UNLINK");
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    // Lists commands
+    /// BLMOVE is the blocking variant of LMOVE, it waits up to timeout for an element,
+    /// or forever if zero.
+    pub fn blmove<K>(
+        &mut self,
+        source: K,
+        destination: K,
+        wherefrom: ListDirection,
+        whereto: ListDirection,
+        timeout: Duration,
+    ) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("BLMOVE"; args => source, destination, wherefrom, whereto, timeout.as_secs_f64()))
+    }
+
+    /// BLMPOP is the blocking variant of LMPOP, it waits up to timeout for an element,
+    /// or forever if zero.
+    pub fn blmpop<K, I>(&mut self, timeout: Duration, keys: I, direction: ListDirection, count: usize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        self.add(command!("BLMPOP"; args => timeout.as_secs_f64(), keys.len(), keys, direction, "COUNT", count))
+    }
+
+    /// BLPOP is the blocking variant of LPOP, it pops from the first non-empty list of keys and
+    /// waits up to timeout for an element, or forever if zero.
+    pub fn blpop<K, I>(&mut self, keys: I, timeout: Duration) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("BLPOP");
+        for key in keys {
+            cmd.arg(key);
+        }
+        cmd.arg(timeout.as_secs_f64());
+        self.add(cmd)
+    }
+
+    /// BRPOP is the blocking variant of RPOP, like `blpop`.
+    pub fn brpop<K, I>(&mut self, keys: I, timeout: Duration) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("BRPOP");
+        for key in keys {
+            cmd.arg(key);
+        }
+        cmd.arg(timeout.as_secs_f64());
+        self.add(cmd)
+    }
+
+    /// BRPOPLPUSH is the blocking variant of RPOPLPUSH.
+    pub fn brpoplpush<K>(&mut self, source: K, destination: K, timeout: Duration) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("BRPOPLPUSH"; args => source, destination, timeout.as_secs_f64()))
+    }
+
+    /// Returns the element at index index in the list stored at key.
+    pub fn lindex<K>(&mut self, key: K, index: isize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("LINDEX"; args => key, index))
+    }
+
+    /// Inserts element in the list stored at key either before or after the reference value pivot.
+    pub fn linsert<K, E>(&mut self, key: K, operator: ListBeforeOrAfter, pivot: E, element: E) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.add(command!("LINSERT"; args => key, operator, pivot, element))
+    }
+
+    /// Returns the length of the list stored at key.
+    pub fn llen<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("LLEN"; args => key))
+    }
+
+    /// Atomically returns and removes the first or last element of the list stored at source,
+    /// and pushes it at the first or last element of the list stored at destination.
+    pub fn lmove<K>(&mut self, source: K, destination: K, wherefrom: ListDirection, whereto: ListDirection) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("LMOVE"; args => source, destination, wherefrom, whereto))
+    }
+
+    /// Pops up to count elements from the first non-empty list of keys.
+    pub fn lmpop<K, I>(&mut self, keys: I, direction: ListDirection, count: usize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        self.add(command!("LMPOP"; args => keys.len(), keys, direction, "COUNT", count))
+    }
+
+    /// Removes and returns the first element of the list stored at key.
+    pub fn lpop<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("LPOP"; args => key))
+    }
+
+    /// Removes and returns up to count elements from the head of the list stored at key.
+    pub fn lpop_count<K>(&mut self, key: K, count: usize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("LPOP"; args => key, count))
+    }
+
+    /// Returns the index of the first element matching element in the list stored at key.
+    pub fn lpos<K, E>(&mut self, key: K, element: E, options: LPosOptions) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.add(command!("LPOS"; args => key, element, options))
+    }
+
+    /// Returns the indexes of up to count elements matching element, zero meaning all of them.
+    pub fn lpos_count<K, E>(&mut self, key: K, element: E, count: usize, options: LPosOptions) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.add(command!("LPOS"; args => key, element, "COUNT", count, options))
+    }
+
+    /// Insert all the specified values at the head of the list stored at key.
+    pub fn lpush<K, E, I>(&mut self, key: K, elements: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
+    {
+        let mut cmd = command!("LPUSH"; args => key);
+        for element in elements {
+            cmd.arg(element);
+        }
+        self.add(cmd)
+    }
+
+    /// Inserts specified values at the head of the list stored at key, only if key already exists and holds a list.
+    pub fn lpushx<K, E, I>(&mut self, key: K, elements: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
+    {
+        let mut cmd = command!("LPUSHX"; args => key);
+        for element in elements {
+            cmd.arg(element);
+        }
+        self.add(cmd)
+    }
+
+    /// Returns the specified elements of the list stored at key.
+    pub fn lrange<K>(&mut self, key: K, start: isize, end: isize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("LRANGE"; args => key, start, end))
+    }
+
+    /// Removes the first count occurrences of elements equal to element from the list stored at key.
+    pub fn lrem<K, E>(&mut self, key: K, count: isize, element: E) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.add(command!("LREM"; args => key, count, element))
+    }
+
+    /// Sets the list element at index to element.
+    pub fn lset<K, E>(&mut self, key: K, index: isize, element: E) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+    {
+        self.add(command!("LSET"; args => key, index, element))
+    }
+
+    /// Trim an existing list so that it will contain only the specified range of elements specified.
+    pub fn ltrim<K>(&mut self, key: K, start: isize, stop: isize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("LTRIM"; args => key, start, stop))
+    }
+
+    /// Removes and returns the last element of the list stored at key.
+    pub fn rpop<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("RPOP"; args => key))
+    }
+
+    /// Removes and returns up to count elements from the tail of the list stored at key.
+    pub fn rpop_count<K>(&mut self, key: K, count: usize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("RPOP"; args => key, count))
+    }
+
+    /// Atomically returns and removes the last element (tail) of the list stored at source, and
+    /// pushes the element at the first element (head) of the list stored at destination.
+    pub fn rpoplpush<K>(&mut self, source: K, destination: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("RPOPLPUSH"; args => source, destination))
+    }
+
+    /// Insert all the specified values at the tail of the list stored at key.
+    pub fn rpush<K, E, I>(&mut self, key: K, elements: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
+    {
+        let mut cmd = command!("RPUSH"; args => key);
+        for element in elements {
+            cmd.arg(element);
+        }
+        self.add(cmd)
+    }
+
+    /// Inserts specified values at the tail of the list stored at key, only if key already exists and holds a list.
+    pub fn rpushx<K, E, I>(&mut self, key: K, elements: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        E: RedisSerializationProtocol,
+        I: IntoIterator<Item = E>,
+    {
+        let mut cmd = command!("RPUSHX"; args => key);
+        for element in elements {
+            cmd.arg(element);
+        }
+        self.add(cmd)
+    }
+
+    // Sets commands
+    /// Add the specified members to the set stored at key.
+    pub fn sadd<K, M, I>(&mut self, key: K, members: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        let mut cmd = command!("SADD"; args => key);
+        for member in members {
+            cmd.arg(member);
+        }
+        self.add(cmd)
+    }
+
+    /// Returns the set cardinality (number of elements) of the set stored at key.
+    pub fn scard<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("SCARD"; args => key))
+    }
+
+    /// Returns the members of the set resulting from the difference between the first set and all the successive sets.
+    pub fn sdiff<K, I>(&mut self, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("SDIFF");
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// This command is equal to SDIFF, but instead of returning the resulting set, it is stored in destination.
+    pub fn sdiffstore<K, I>(&mut self, destination: K, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = command!("SDIFFSTORE"; args => destination);
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// Returns the members of the set resulting from the intersection of all the given sets.
+    pub fn sinter<K, I>(&mut self, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("SINTER");
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// This command is equal to SINTER, but instead of returning the resulting set, it is stored in destination.
+    pub fn sinterstore<K, I>(&mut self, destination: K, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = command!("SINTERSTORE"; args => destination);
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// Returns if member is a member of the set stored at key.
+    pub fn sismember<K, M>(&mut self, key: K, member: M) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.add(command!("SISMEMBER"; args => key, member))
+    }
+
+    /// Returns all the members of the set value stored at key.
+    pub fn smembers<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("SMEMBERS"; args => key))
+    }
+
+    /// Returns whether each member is a member of the set stored at key.
+    pub fn smismember<K, M, I>(&mut self, key: K, members: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        let mut cmd = command!("SMISMEMBER"; args => key);
+        for member in members {
+            cmd.arg(member);
+        }
+        self.add(cmd)
+    }
+
+    /// Move member from the set at source to the set at destination.
+    pub fn smove<K, M>(&mut self, source: K, destination: K, member: M) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.add(command!("SMOVE"; args => source, destination, member))
+    }
+
+    /// Removes and returns one or more random members from the set value store at key.
+    pub fn spop<K>(&mut self, key: K, count: Option<usize>) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("SPOP"; args => key);
+        if let Some(count) = count {
+            cmd.arg(count);
+        }
+        self.add(cmd)
+    }
+
+    /// When called with just the key argument, return a random element from the set value stored at key.
+    pub fn srandmember<K>(&mut self, key: K, count: Option<usize>) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("SRANDMEMBER"; args => key);
+        if let Some(count) = count {
+            cmd.arg(count);
+        }
+        self.add(cmd)
+    }
+
+    /// Remove the specified members from the set stored at key.
+    pub fn srem<K, M, I>(&mut self, key: K, members: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        let mut cmd = command!("SREM"; args => key);
+        for member in members {
+            cmd.arg(member);
+        }
+        self.add(cmd)
+    }
+
+    /// Returns the members of the set resulting from the union of all the given sets.
+    pub fn sunion<K, I>(&mut self, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("SUNION");
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// This command is equal to SUNION, but instead of returning the resulting set, it is stored in destination.
+    pub fn sunionstore<K, I>(&mut self, destination: K, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = command!("SUNIONSTORE"; args => destination);
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    // Sorted Sets commands
+    /// BZPOPMAX is the blocking variant of ZPOPMAX, it pops from the first non-empty sorted set
+    /// of keys and waits up to timeout for a member, or forever if zero.
+    pub fn bzpopmax<K, I>(&mut self, keys: I, timeout: Duration) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("BZPOPMAX");
+        for key in keys {
+            cmd.arg(key);
+        }
+        cmd.arg(timeout.as_secs_f64());
+        self.add(cmd)
+    }
+
+    /// BZPOPMIN is the blocking variant of ZPOPMIN, like `bzpopmax`.
+    pub fn bzpopmin<K, I>(&mut self, keys: I, timeout: Duration) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("BZPOPMIN");
+        for key in keys {
+            cmd.arg(key);
+        }
+        cmd.arg(timeout.as_secs_f64());
+        self.add(cmd)
+    }
+
+    /// Adds all the specified members with the specified scores to the sorted set stored at key.
+    pub fn zadd<K, M, I>(&mut self, key: K, members: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = (f64, M)>,
+    {
+        self.zadd_options(key, members, ZAddOptions::new())
+    }
+
+    /// Like `zadd`, with the NX, XX, GT, LT and CH options.
+    pub fn zadd_options<K, M, I>(&mut self, key: K, members: I, options: ZAddOptions) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = (f64, M)>,
+    {
+        let mut cmd = command!("ZADD"; args => key, options);
+        for (score, member) in members {
+            cmd.arg(score).arg(member);
+        }
+        self.add(cmd)
+    }
+
+    /// Increments the score of member like `zincrby`, unless the options prevent it.
+    pub fn zadd_incr<K, M>(&mut self, key: K, increment: f64, member: M, options: ZAddOptions) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("ZADD"; args => key, options, "INCR", increment, member);
+        cmd.non_retryable();
+        self.add(cmd)
+    }
+
+    /// Returns the sorted set cardinality (number of elements) of the sorted set stored at key.
+    pub fn zcard<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("ZCARD"; args => key))
+    }
+
+    /// Returns the number of elements in the sorted set at key with a score between min and max.
+    pub fn zcount<K, Min, Max>(&mut self, key: K, min: Min, max: Max) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        Min: Into<ScoreBound>,
+        Max: Into<ScoreBound>,
+    {
+        self.add(command!("ZCOUNT"; args => key, min.into(), max.into()))
+    }
+
+    /// Returns the members of the sets resulting from the difference between the first set and all the successive sets.
+    pub fn zdiff<K, I>(&mut self, keys: I, with_scores: bool) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        let mut cmd = command!("ZDIFF"; args => keys.len(), keys);
+        if with_scores {
+            cmd.arg("WITHSCORES");
+        }
+        self.add(cmd)
+    }
+
+    /// Computes the difference like `zdiff` and stores the result in destination.
+    pub fn zdiffstore<D, K, I>(&mut self, destination: D, keys: I) -> &mut Self
+    where
+        D: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        self.add(command!("ZDIFFSTORE"; args => destination, keys.len(), keys))
+    }
+
+    /// Increments the score of member in the sorted set stored at key by increment.
+    pub fn zincrby<K, M>(&mut self, key: K, increment: f64, member: M) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.add(command!("ZINCRBY"; args => key, increment, member))
+    }
+
+    /// Returns the members of the intersection of the given sorted sets.
+    pub fn zinter<K, I>(&mut self, keys: I, options: ZAggregateOptions, with_scores: bool) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        let mut cmd = command!("ZINTER"; args => keys.len(), keys, options);
+        if with_scores {
+            cmd.arg("WITHSCORES");
+        }
+        self.add(cmd)
+    }
+
+    /// Computes the intersection like `zinter` and stores the result in destination.
+    pub fn zinterstore<D, K, I>(&mut self, destination: D, keys: I, options: ZAggregateOptions) -> &mut Self
+    where
+        D: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        self.add(command!("ZINTERSTORE"; args => destination, keys.len(), keys, options))
+    }
+
+    /// Returns the scores associated with the specified members in the sorted set stored at key.
+    pub fn zmscore<K, M, I>(&mut self, key: K, members: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        let mut cmd = command!("ZMSCORE"; args => key);
+        for member in members {
+            cmd.arg(member);
+        }
+        self.add(cmd)
+    }
+
+    /// Removes and returns up to count members with the highest scores in the sorted set stored at key.
+    pub fn zpopmax<K>(&mut self, key: K, count: usize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("ZPOPMAX"; args => key, count))
+    }
+
+    /// Removes and returns up to count members with the lowest scores in the sorted set stored at key.
+    pub fn zpopmin<K>(&mut self, key: K, count: usize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("ZPOPMIN"; args => key, count))
+    }
+
+    /// Returns a random member of the sorted set stored at key.
+    pub fn zrandmember<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("ZRANDMEMBER"; args => key))
+    }
+
+    /// Returns up to count distinct random members, or count members which may repeat if it's negative.
+    pub fn zrandmember_count<K>(&mut self, key: K, count: isize, with_scores: bool) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("ZRANDMEMBER"; args => key, count);
+        if with_scores {
+            cmd.arg("WITHSCORES");
+        }
+        self.add(cmd)
+    }
+
+    /// Returns the specified range of members in the sorted set stored at key.
+    pub fn zrange<K>(&mut self, key: K, range: ZRange) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("ZRANGE"; args => key, range))
+    }
+
+    /// Returns the rank of member in the sorted set stored at key, with the scores ordered from low to high.
+    pub fn zrank<K, M>(&mut self, key: K, member: M) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.add(command!("ZRANK"; args => key, member))
+    }
+
+    /// Removes the specified members from the sorted set stored at key.
+    pub fn zrem<K, M, I>(&mut self, key: K, members: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+        I: IntoIterator<Item = M>,
+    {
+        let mut cmd = command!("ZREM"; args => key);
+        for member in members {
+            cmd.arg(member);
+        }
+        self.add(cmd)
+    }
+
+    /// Removes all members in the sorted set stored at key between the lexicographical range specified by min and max.
+    pub fn zremrangebylex<K>(&mut self, key: K, min: LexBound, max: LexBound) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("ZREMRANGEBYLEX"; args => key, min, max))
+    }
+
+    /// Removes all members in the sorted set stored at key with rank between start and stop.
+    pub fn zremrangebyrank<K>(&mut self, key: K, start: isize, stop: isize) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("ZREMRANGEBYRANK"; args => key, start, stop))
+    }
+
+    /// Removes all members in the sorted set stored at key with a score between min and max.
+    pub fn zremrangebyscore<K, Min, Max>(&mut self, key: K, min: Min, max: Max) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        Min: Into<ScoreBound>,
+        Max: Into<ScoreBound>,
+    {
+        self.add(command!("ZREMRANGEBYSCORE"; args => key, min.into(), max.into()))
+    }
+
+    /// Returns the rank of member in the sorted set stored at key, with the scores ordered from high to low.
+    pub fn zrevrank<K, M>(&mut self, key: K, member: M) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.add(command!("ZREVRANK"; args => key, member))
+    }
+
+    /// Returns the score of member in the sorted set at key.
+    pub fn zscore<K, M>(&mut self, key: K, member: M) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        M: RedisSerializationProtocol,
+    {
+        self.add(command!("ZSCORE"; args => key, member))
+    }
+
+    /// Returns the members of the union of the given sorted sets.
+    pub fn zunion<K, I>(&mut self, keys: I, options: ZAggregateOptions, with_scores: bool) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        let mut cmd = command!("ZUNION"; args => keys.len(), keys, options);
+        if with_scores {
+            cmd.arg("WITHSCORES");
+        }
+        self.add(cmd)
+    }
+
+    /// Computes the union like `zunion` and stores the result in destination.
+    pub fn zunionstore<D, K, I>(&mut self, destination: D, keys: I, options: ZAggregateOptions) -> &mut Self
+    where
+        D: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        self.add(command!("ZUNIONSTORE"; args => destination, keys.len(), keys, options))
+    }
+
+    // Streams commands
+    /// Removes the specified entries from the pending entries list of the consumer group.
+    pub fn xack<K, G, I>(&mut self, key: K, group: G, ids: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+        I: IntoIterator<Item = StreamId>,
+    {
+        let mut cmd = command!("XACK"; args => key, group);
+        for id in ids {
+            cmd.arg(id);
+        }
+        self.add(cmd)
+    }
+
+    /// Appends an entry with the specified fields to the stream stored at key, with an ID generated by the server.
+    pub fn xadd<K, F, V, I>(&mut self, key: K, fields: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+        I: IntoIterator<Item = (F, V)>,
+    {
+        let mut cmd = command!("XADD"; args => key, "*");
+        for (field, value) in fields {
+            cmd.arg(field).arg(value);
+        }
+        self.add(cmd)
+    }
+
+    /// Like `xadd`, with an explicit ID, the NOMKSTREAM option or trimming.
+    pub fn xadd_options<K, F, V, I>(&mut self, key: K, fields: I, options: XAddOptions) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        F: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+        I: IntoIterator<Item = (F, V)>,
+    {
+        let mut cmd = command!("XADD"; args => key, options);
+        for (field, value) in fields {
+            cmd.arg(field).arg(value);
+        }
+        self.add(cmd)
+    }
+
+    /// Transfers to consumer up to count pending entries idle for at least min_idle_time, from start on.
+    pub fn xautoclaim<K, G, C>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: C,
+        min_idle_time: Duration,
+        start: StreamId,
+        count: Option<usize>,
+    ) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+        C: RedisSerializationProtocol,
+    {
+        let min_idle_time = min_idle_time.as_millis() as u64;
+        let mut cmd = command!("XAUTOCLAIM"; args => key, group, consumer, min_idle_time, start);
+        if let Some(count) = count {
+            cmd.arg("COUNT").arg(count);
+        }
+        self.add(cmd)
+    }
+
+    /// Transfers to consumer the specified pending entries which are idle for at least min_idle_time.
+    pub fn xclaim<K, G, C, I>(&mut self, key: K, group: G, consumer: C, min_idle_time: Duration, ids: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+        C: RedisSerializationProtocol,
+        I: IntoIterator<Item = StreamId>,
+    {
+        let min_idle_time = min_idle_time.as_millis() as u64;
+        let mut cmd = command!("XCLAIM"; args => key, group, consumer, min_idle_time);
+        for id in ids {
+            cmd.arg(id);
+        }
+        self.add(cmd)
+    }
+
+    /// Removes the specified entries from the stream stored at key.
+    pub fn xdel<K, I>(&mut self, key: K, ids: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = StreamId>,
+    {
+        let mut cmd = command!("XDEL"; args => key);
+        for id in ids {
+            cmd.arg(id);
+        }
+        self.add(cmd)
+    }
+
+    /// Creates a consumer group starting from id, and the stream if `mkstream` and it doesn't exist.
+    pub fn xgroup_create<K, G>(&mut self, key: K, group: G, id: ReadFrom, mkstream: bool) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("XGROUP"; args => "CREATE", key, group, id);
+        cmd.non_retryable();
+        if mkstream {
+            cmd.arg("MKSTREAM");
+        }
+        self.add(cmd)
+    }
+
+    /// Destroys a consumer group, its consumers and pending entries.
+    pub fn xgroup_destroy<K, G>(&mut self, key: K, group: G) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.add(command!("XGROUP"; args => "DESTROY", key, group))
+    }
+
+    /// Sets the last delivered ID of a consumer group.
+    pub fn xgroup_setid<K, G>(&mut self, key: K, group: G, id: ReadFrom) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.add(command!("XGROUP"; args => "SETID", key, group, id))
+    }
+
+    /// Returns the consumers of a consumer group.
+    pub fn xinfo_consumers<K, G>(&mut self, key: K, group: G) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.add(command!("XINFO"; args => "CONSUMERS", key, group))
+    }
+
+    /// Returns the consumer groups of the stream stored at key.
+    pub fn xinfo_groups<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("XINFO"; args => "GROUPS", key))
+    }
+
+    /// Returns general information about the stream stored at key.
+    pub fn xinfo_stream<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("XINFO"; args => "STREAM", key))
+    }
+
+    /// Returns the number of entries in the stream stored at key.
+    pub fn xlen<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("XLEN"; args => key))
+    }
+
+    /// Returns the number of pending entries of a consumer group, their lowest and highest IDs and their consumers.
+    pub fn xpending<K, G>(&mut self, key: K, group: G) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.add(command!("XPENDING"; args => key, group))
+    }
+
+    /// Returns the pending entries of a consumer group in the specified range.
+    pub fn xpending_range<K, G>(&mut self, key: K, group: G, range: PendingRange) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        G: RedisSerializationProtocol,
+    {
+        self.add(command!("XPENDING"; args => key, group, range))
+    }
+
+    /// Returns up to count entries of the stream stored at key with an ID from start to end.
+    pub fn xrange<K>(&mut self, key: K, start: StreamBound, end: StreamBound, count: Option<usize>) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("XRANGE"; args => key, start, end);
+        if let Some(count) = count {
+            cmd.arg("COUNT").arg(count);
+        }
+        self.add(cmd)
+    }
+
+    /// Reads the entries of the specified streams with an ID greater than the one given for each of them.
+    pub fn xread<K, I>(&mut self, keys: I, options: XReadOptions) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = (K, ReadFrom)>,
+    {
+        let (keys, ids): (Vec<K>, Vec<ReadFrom>) = keys.into_iter().unzip();
+        self.add(command!("XREAD"; args => options, "STREAMS", keys, ids))
+    }
+
+    /// Like `xread`, as consumer of a consumer group. `ReadFrom::Undelivered` reads the new
+    /// entries, an ID the pending entries of the consumer.
+    pub fn xreadgroup<G, C, K, I>(&mut self, group: G, consumer: C, keys: I, options: XReadOptions) -> &mut Self
+    where
+        G: RedisSerializationProtocol,
+        C: RedisSerializationProtocol,
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = (K, ReadFrom)>,
+    {
+        let (keys, ids): (Vec<K>, Vec<ReadFrom>) = keys.into_iter().unzip();
+        self.add(command!("XREADGROUP"; args => "GROUP", group, consumer, options, "STREAMS", keys, ids))
+    }
+
+    /// Like `xrange`, in reverse order, so end comes before start.
+    pub fn xrevrange<K>(&mut self, key: K, end: StreamBound, start: StreamBound, count: Option<usize>) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("XREVRANGE"; args => key, end, start);
+        if let Some(count) = count {
+            cmd.arg("COUNT").arg(count);
+        }
+        self.add(cmd)
+    }
+
+    /// Trims the stream stored at key.
+    pub fn xtrim<K>(&mut self, key: K, trim: StreamTrim) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("XTRIM"; args => key, trim))
+    }
+
+    // Strings commands
+    pub fn append<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.add(command!("APPEND"; args => key, value))
+    }
+
+    /// Count the number of set bits (population counting) in a string.
+    pub fn bitcount<K>(&mut self, key: K, start: Option<i64>, end: Option<i64>) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("BITCOUNT"; args => key);
+        if let Some(start) = start {
+            cmd.arg(start);
+        }
+        if let Some(end) = end {
+            cmd.arg(end);
+        }
+        self.add(cmd)
+    }
+
+    /// Perform a bitwise operation between multiple keys (containing string values) and store the result in the destination key.
+    pub fn bitop<K1, K2, I>(&mut self, operation: &str, destkey: K1, keys: I) -> &mut Self
+    where
+        K1: RedisSerializationProtocol,
+        K2: RedisSerializationProtocol,
+        I: IntoIterator<Item = K2>,
+    {
+        let mut cmd = command!("BITOP"; args => operation, destkey);
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// Return the position of the first bit set to 1 or 0 in a string.
+    ///
+    /// When only `end` is given, `start` defaults to 0.
+    pub fn bitpos<K>(&mut self, key: K, bit: u8, start: Option<usize>, end: Option<usize>) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("BITPOS"; args => key, bit);
+        if let Some(end) = end {
+            cmd.arg(start.unwrap_or(0)).arg(end);
+        } else if let Some(start) = start {
+            cmd.arg(start);
+        }
+        self.add(cmd)
+    }
+
+    /// Decrements the number stored at key by one.
+    pub fn decr<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("DECR"; args => key))
+    }
+
+    /// Decrements the number stored at key by decrement.
+    pub fn decrby<K>(&mut self, key: K, decrement: i64) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("DECRBY"; args => key, decrement))
+    }
+
+    /// Get the value of key.
+    pub fn get<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("GET"; args => key))
+    }
+
+    /// Returns the bit value at offset in the string value stored at key.
+    pub fn getbit<K>(&mut self, key: K, offset: i64) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("GETBIT"; args => key, offset))
+    }
+
+    /// Returns the substring of the string value stored at key, determined by the offsets start and end (both are inclusive).
+    pub fn getrange<K>(&mut self, key: K, start: i64, end: i64) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("GETRANGE"; args => key, start, end))
+    }
+
+    /// Atomically sets key to value and returns the old value stored at key.
+    pub fn getset<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: ToString,
+    {
+        self.add(command!("GETSET"; args => key, value.to_string()))
+    }
+
+    /// Increments the number stored at key by one.
+    pub fn incr<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("INCR"; args => key))
+    }
+
+    /// Increments the number stored at key by increment.
+    pub fn incrby<K>(&mut self, key: K, increment: i64) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("INCRBY"; args => key, increment))
+    }
+
+    /// Increment the string representing a floating point number stored at key by the specified increment.
+    pub fn incrbyfloat<K>(&mut self, key: K, increment: f64) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("INCRBYFLOAT"; args => key, increment))
+    }
+
+    /// Returns the values of all specified keys.
+    pub fn mget<K, I>(&mut self, keys: I) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        I: IntoIterator<Item = K>,
+    {
+        let mut cmd = Command::new("MGET");
+        for key in keys {
+            cmd.arg(key);
+        }
+        self.add(cmd)
+    }
+
+    /// Sets the given keys to their respective values.
+    pub fn mset<K, V>(&mut self, kvs: Vec<(K, V)>) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        let mut cmd = Command::new("MSET");
+        for (k, v) in kvs {
+            cmd.arg(k).arg(v);
+        }
+        self.add(cmd)
+    }
+
+    /// Sets the given keys to their respective values.
+    pub fn msetnx<K, V>(&mut self, kvs: Vec<(K, V)>) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        let mut cmd = Command::new("MSETNX");
+        for (k, v) in kvs {
+            cmd.arg(k).arg(v);
+        }
+        self.add(cmd)
+    }
+
+    /// PSETEX works exactly like SETEX with the sole difference that the expire time is specified in milliseconds instead of seconds.
+    pub fn psetex<K, V>(&mut self, key: K, milliseconds: u64, value: V) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.add(command!("PSETEX"; args => key, milliseconds, value))
+    }
+
+    /// Set key to hold the string value.
+    pub fn set<K, V>(
+        &mut self,
+        key: K,
+        value: V,
+        ex_seconds: Option<u64>,
+        px_milliseconds: Option<u64>,
+        nx: Option<bool>,
+        xx: Option<bool>,
+    ) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        let mut cmd = command!("SET"; args => key, value);
+        if let Some(ex) = ex_seconds {
+            cmd.arg("EX").arg(ex);
+        }
+        if let Some(px) = px_milliseconds {
+            cmd.arg("PX").arg(px);
+        }
+        if let Some(true) = nx {
+            cmd.arg("NX").non_retryable();
+        }
+        if let Some(true) = xx {
+            cmd.arg("XX");
+        }
+        self.add(cmd)
+    }
+
+    /// Set key to hold the string value.
+    pub fn simple_set<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.set(key, value, None, None, None, None)
+    }
+
+    /// Sets or clears the bit at offset in the string value stored at key.
+    pub fn setbit<K>(&mut self, key: K, offset: usize, value: u8) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("SETBIT"; args => key, offset, value))
+    }
+
+    /// Set key to hold the string value and set key to timeout after a given number of seconds.
+    pub fn setex<K, V>(&mut self, key: K, seconds: usize, value: V) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.add(command!("SETEX"; args => key, seconds, value))
+    }
+
+    /// Set key to hold string value if key does not exist.
+    pub fn setnx<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.add(command!("SETNX"; args => key, value))
+    }
+
+    /// Overwrites part of the string stored at key, starting at the specified offset, for the entire length of value.
+    pub fn setrange<K, V>(&mut self, key: K, offset: usize, value: V) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+        V: RedisSerializationProtocol,
+    {
+        self.add(command!("SETRANGE"; args => key, offset, value))
+    }
+
+    /// Returns the length of the string value stored at key.
+    pub fn strlen<K>(&mut self, key: K) -> &mut Self
+    where
+        K: RedisSerializationProtocol,
+    {
+        self.add(command!("STRLEN"; args => key))
+    }
 }
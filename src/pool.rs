@@ -1,55 +1,289 @@
 use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
-use crate::connection::Connection;
+use crate::config::{RedisConfig, RetryPolicy};
+use crate::connection::{Connection, Connector};
 use crate::error::{ErrorKind, RedisError};
 use crate::RedisResult;
 
+/// A pool opening at most `pool_capacity` connections, shared by the threads using a client,
+/// and at most `blocking_pool_capacity` more dedicated to blocking commands.
 pub struct ConnectionPool {
-    addr: String,
-    capacity: usize,
-    idles: VecDeque<Connection>,
+    state: Mutex<State>,
+    // notified when a connection is put back, or a slot for a new one is freed
+    available: Condvar,
+    // the same, for the connections dedicated to blocking commands
+    blocking_available: Condvar,
+    connector: Connector,
+    retry: RetryPolicy,
+    read_timeout: Option<Duration>,
+}
+
+struct State {
+    config: RedisConfig,
+    regular: Slots,
+    // the connections dedicated to blocking commands, they don't count towards `pool_capacity`
+    blocking: Slots,
     closed: bool,
 }
 
-impl ConnectionPool {
-    pub(super) fn new(capacity: usize, address: String) -> ConnectionPool {
-        ConnectionPool {
-            addr: address,
-            capacity,
-            idles: VecDeque::with_capacity(capacity),
-            closed: false,
+// The connections of either kind.
+#[derive(Default)]
+struct Slots {
+    // the most recently used last
+    idles: VecDeque<Idle<Connection>>,
+    // the number of connections open, idle or checked out
+    open: usize,
+}
+
+impl Slots {
+    fn evict_expired(&mut self, config: &RedisConfig, now: Instant) {
+        let idles = self.idles.len();
+        self.idles.retain(|idle| !idle.expired(config, now));
+        self.open -= idles - self.idles.len();
+    }
+
+    fn clear(&mut self) {
+        self.open -= self.idles.len();
+        self.idles.clear();
+    }
+}
+
+/// An idle connection, of the sync or the async pool.
+pub(crate) struct Idle<C> {
+    pub(crate) conn: C,
+    pub(crate) created: Instant,
+    pub(crate) since: Instant,
+}
+
+impl<C> Idle<C> {
+    pub(crate) fn new(conn: C, created: Instant) -> Idle<C> {
+        Idle {
+            conn,
+            created,
+            since: Instant::now(),
         }
     }
 
-    pub(super) fn get(&mut self) -> RedisResult<Connection> {
-        if self.closed {
-            return Err(RedisError::custom(ErrorKind::ClientError, "Connection pool closed"));
+    /// Whether the connection has been idle for `pool_idle_timeout`, or open for `pool_max_lifetime`.
+    pub(crate) fn expired(&self, config: &RedisConfig, now: Instant) -> bool {
+        elapsed(self.since, now, config.pool_idle_timeout) || elapsed(self.created, now, config.pool_max_lifetime)
+    }
+}
+
+/// Whether `timeout` has passed between `start` and `now`, never if `None`.
+pub(crate) fn elapsed(start: Instant, now: Instant, timeout: Option<Duration>) -> bool {
+    timeout.is_some_and(|timeout| now.duration_since(start) >= timeout)
+}
+
+impl State {
+    fn evict_expired(&mut self) {
+        let now = Instant::now();
+        self.regular.evict_expired(&self.config, now);
+        self.blocking.evict_expired(&self.config, now);
+    }
+
+    fn slots(&mut self, blocking: bool) -> &mut Slots {
+        if blocking {
+            &mut self.blocking
+        } else {
+            &mut self.regular
+        }
+    }
+
+    fn capacity(&self, blocking: bool) -> usize {
+        let capacity = if blocking {
+            self.config.blocking_pool_capacity
+        } else {
+            self.config.pool_capacity
+        };
+        capacity.max(1)
+    }
+}
+
+impl ConnectionPool {
+    /// Fails if the TLS config is invalid, e.g. a certificate file can't be read.
+    pub(super) fn new(config: RedisConfig) -> RedisResult<ConnectionPool> {
+        let capacity = config.pool_capacity;
+        let retry = config.retry.clone();
+        let read_timeout = config.read_timeout;
+        let connector = Connector::new(&config)?;
+        Ok(ConnectionPool {
+            state: Mutex::new(State {
+                config,
+                regular: Slots {
+                    idles: VecDeque::with_capacity(capacity),
+                    open: 0,
+                },
+                blocking: Slots::default(),
+                closed: false,
+            }),
+            available: Condvar::new(),
+            blocking_available: Condvar::new(),
+            connector,
+            retry,
+            read_timeout,
+        })
+    }
+
+    pub(super) fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// Checks out a connection, waiting up to `pool_checkout_timeout` while all of them are in use.
+    ///
+    /// Expired idle connections are closed first, and one idle for `pool_health_check_after`
+    /// is replaced if it doesn't answer a PING, e.g. because the server closed it.
+    pub(super) fn get(self: &Arc<Self>) -> RedisResult<PooledConnection> {
+        self.checkout(false)
+    }
+
+    /// Checks out a connection dedicated to commands blocking on the server, like BLPOP. These
+    /// count towards `blocking_pool_capacity` instead of `pool_capacity`, so waiting doesn't hold
+    /// up the other users of the pool.
+    pub(super) fn get_blocking(self: &Arc<Self>) -> RedisResult<PooledConnection> {
+        self.checkout(true)
+    }
+
+    fn checkout(self: &Arc<Self>, blocking: bool) -> RedisResult<PooledConnection> {
+        let mut state = self.lock();
+        let deadline = state
+            .config
+            .pool_checkout_timeout
+            .map(|timeout| Instant::now() + timeout);
+        loop {
+            if state.closed {
+                return Err(RedisError::custom(ErrorKind::ClientError, "Connection pool closed"));
+            }
+
+            state.evict_expired();
+            if let Some(idle) = state.slots(blocking).idles.pop_back() {
+                let (database, health_check_after) = (state.config.database, state.config.pool_health_check_after);
+                drop(state);
+
+                if let Some(conn) = self.reuse(idle, blocking, database, health_check_after)? {
+                    return Ok(conn);
+                }
+                state = self.lock();
+                continue;
+            }
+
+            if state.slots(blocking).open < state.capacity(blocking) {
+                state.slots(blocking).open += 1;
+                let config = state.config.clone();
+                drop(state);
+
+                return match Connection::connect(&config, &self.connector)
+                    .and_then(|mut conn| conn.init(&config).map(|_| conn))
+                {
+                    Ok(conn) => Ok(self.guard(conn, Instant::now(), blocking)),
+                    Err(e) => {
+                        self.release(blocking);
+                        Err(e)
+                    }
+                };
+            }
+
+            let available = self.available(blocking);
+            state = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(RedisError::custom(
+                            ErrorKind::PoolTimeout,
+                            "timed out waiting for a connection",
+                        ));
+                    }
+                    available
+                        .wait_timeout(state, deadline - now)
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0
+                }
+                None => available.wait(state).unwrap_or_else(|e| e.into_inner()),
+            };
         }
+    }
 
-        if let Some(conn) = self.idles.pop_front() {
-            return Ok(conn);
+    // Checks out an idle connection, `None` if it's been closed because it didn't answer the
+    // health check.
+    fn reuse(
+        self: &Arc<Self>,
+        idle: Idle<Connection>,
+        blocking: bool,
+        database: u8,
+        health_check_after: Option<Duration>,
+    ) -> RedisResult<Option<PooledConnection>> {
+        let health_check = elapsed(idle.since, Instant::now(), health_check_after);
+        let mut conn = self.guard(idle.conn, idle.created, blocking);
+        if health_check {
+            if let Err(e) = conn.ping() {
+                conn.discard(e);
+                return Ok(None);
+            }
         }
-        Connection::connect(&self.addr)
+        // the target database has changed since the connection was idle
+        if conn.database() != database {
+            conn.select(database).map_err(|e| conn.discard(e))?;
+        }
+        Ok(Some(conn))
+    }
+
+    /// Sets the database new connections select, idle ones switch to it on checkout.
+    pub(super) fn select(&self, database: u8) {
+        self.lock().config.database = database;
     }
 
-    pub(super) fn put(&mut self, conn: Connection) {
-        if self.closed {
-            return;
+    fn guard(self: &Arc<Self>, conn: Connection, created: Instant, blocking: bool) -> PooledConnection {
+        PooledConnection {
+            conn: Some(conn),
+            created,
+            blocking,
+            pool: self.clone(),
         }
+    }
 
-        if self.idles.len() >= self.capacity {
-            let _ = self.idles.pop_front();
+    fn put(&self, conn: Connection, created: Instant, blocking: bool) {
+        let mut state = self.lock();
+        let expired = state.closed || elapsed(created, Instant::now(), state.config.pool_max_lifetime);
+        let slots = state.slots(blocking);
+        if expired {
+            slots.open -= 1;
+        } else {
+            slots.idles.push_back(Idle::new(conn, created));
         }
-        self.idles.push_back(conn);
+        self.available(blocking).notify_one();
     }
 
-    fn close(&mut self) {
-        self.closed = true;
+    // Frees the slot of a connection which has been dropped.
+    fn release(&self, blocking: bool) {
+        self.lock().slots(blocking).open -= 1;
+        self.available(blocking).notify_one();
+    }
 
-        while let Some(conn) = self.idles.pop_front() {
-            drop(conn);
+    fn available(&self, blocking: bool) -> &Condvar {
+        if blocking {
+            &self.blocking_available
+        } else {
+            &self.available
         }
     }
+
+    fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+
+        state.regular.clear();
+        state.blocking.clear();
+        self.available.notify_all();
+        self.blocking_available.notify_all();
+    }
+
+    // A panic while holding the lock can't leave the state inconsistent, so poisoning is ignored.
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 impl Drop for ConnectionPool {
@@ -57,3 +291,64 @@ impl Drop for ConnectionPool {
         self.close()
     }
 }
+
+/// A connection checked out of the pool, it's put back when dropped.
+pub(crate) struct PooledConnection {
+    conn: Option<Connection>,
+    created: Instant,
+    // dedicated to blocking commands, see `ConnectionPool::get_blocking`
+    blocking: bool,
+    pool: Arc<ConnectionPool>,
+}
+
+impl PooledConnection {
+    /// Closes the connection instead of putting it back, e.g. after an error left it in an
+    /// unknown state, and returns the error.
+    pub(crate) fn discard(&mut self, e: RedisError) -> RedisError {
+        if self.conn.take().is_some() {
+            self.pool.release(self.blocking);
+        }
+        e
+    }
+
+    /// Closes the connection if `e` left it unusable, and returns the error.
+    pub(crate) fn discard_if_unrecoverable(&mut self, e: RedisError) -> RedisError {
+        if e.is_unrecoverable() {
+            self.discard(e)
+        } else {
+            e
+        }
+    }
+
+    pub(crate) fn is_discarded(&self) -> bool {
+        self.conn.is_none()
+    }
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection discarded")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection discarded")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        // a connection left with another read timeout, e.g. by a blocking command, is put back
+        // with the configured one
+        let read_timeout = self.pool.read_timeout;
+        if let Some(Err(e)) = self.conn.as_mut().map(|conn| conn.set_read_timeout(read_timeout)) {
+            self.discard(e);
+        }
+        if let Some(conn) = self.conn.take() {
+            self.pool.put(conn, self.created, self.blocking);
+        }
+    }
+}
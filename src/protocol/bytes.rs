@@ -0,0 +1,79 @@
+use std::convert::TryInto;
+
+use crate::connection::Reply;
+use crate::error::ErrorKind::TypeError;
+use crate::error::RedisError;
+use crate::protocol::{miss_type, RedisDeserializationProtocol, RedisSerializationProtocol};
+use crate::RedisResult;
+
+// Binary data is sent as a single bulk string, as is.
+fn serialization_bytes(data: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(data.len() + 16);
+    buf.extend(format!("${}\r\n", data.len()).as_bytes());
+    buf.extend(data);
+    buf.extend(b"\r\n");
+    buf
+}
+
+fn deserialization_bytes(reply: Reply) -> RedisResult<Vec<u8>> {
+    match reply {
+        Reply::BulkStrings(data) | Reply::Status(data) => Ok(data),
+        Reply::VerbatimString { text, .. } => Ok(text),
+        reply => Err(miss_type(&reply)),
+    }
+}
+
+impl RedisSerializationProtocol for u8 {
+    fn serialization(&self) -> Vec<u8> {
+        self.to_string().serialization()
+    }
+
+    // `&[u8]`, `Vec<u8>` and `[u8; N]` are one bulk string rather than one per byte.
+    fn serialization_slice(items: &[Self]) -> Vec<u8> {
+        serialization_bytes(items)
+    }
+
+    fn count_slice(_items: &[Self]) -> usize {
+        1
+    }
+}
+
+impl RedisDeserializationProtocol for u8 {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        match reply {
+            Reply::Integers(data) | Reply::BulkStrings(data) | Reply::BigNumber(data) => {
+                Ok(String::from_utf8(data)?.parse::<u8>()?)
+            }
+            Reply::Double(data) => Ok(data.to_string().parse::<u8>()?),
+            reply => Err(miss_type(&reply)),
+        }
+    }
+
+    // `Vec<u8>` takes a bulk string as is, without going through UTF-8.
+    fn deserialization_from_bytes(data: Vec<u8>) -> Option<Vec<Self>> {
+        Some(data)
+    }
+}
+
+impl<const N: usize> RedisDeserializationProtocol for [u8; N] {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        let data = deserialization_bytes(reply)?;
+        let length = data.len();
+        data.try_into()
+            .map_err(|_| RedisError::custom(TypeError, format!("expected {} bytes, got {}", N, length)))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl RedisSerializationProtocol for bytes::Bytes {
+    fn serialization(&self) -> Vec<u8> {
+        serialization_bytes(self)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl RedisDeserializationProtocol for bytes::Bytes {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        Ok(bytes::Bytes::from(deserialization_bytes(reply)?))
+    }
+}
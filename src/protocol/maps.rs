@@ -8,7 +8,16 @@ macro_rules! implement_serialization_for_maps {
                 V: RedisSerializationProtocol,
             {
                 fn serialization(&self) -> Vec<u8> {
-                    unimplemented!()
+                    let mut buf = Vec::new();
+                    for (field, value) in self {
+                        buf.extend(field.serialization());
+                        buf.extend(value.serialization());
+                    }
+                    buf
+                }
+
+                fn count(&self) -> usize {
+                    self.iter().map(|(field, value)| field.count() + value.count()).sum()
                 }
             }
         )*
@@ -25,23 +34,33 @@ macro_rules! implement_deserialization_for_maps {
                 V: RedisDeserializationProtocol,
             {
                 fn deserialization(reply: Reply) -> RedisResult<Self> {
-                    // TODO: ugly code, refactor !!!
                     match reply {
+                        // RESP2 flattens maps into an array of fields and values
                         Reply::Arrays(array) => {
-                            let hash = array
-                                .chunks(2)
-                                .map(|chunk| {
-                                    let field = &chunk[0];
-                                    let value = &chunk[1];
-                                    (
-                                        <K>::deserialization(field.clone()).unwrap(),
-                                        <V>::deserialization(value.clone()).unwrap(), // TODO: remove clone and unwrap
-                                    )
-                                })
-                                .collect();
-                            Ok(hash)
+                            if array.len() % 2 != 0 {
+                                return Err(RedisError::custom(TypeError, "odd number of map elements"));
+                            }
+                            let mut map = $t::<K, V>::new();
+                            let mut array = array.into_iter();
+                            while let (Some(field), Some(value)) = (array.next(), array.next()) {
+                                map.insert(
+                                    <K>::deserialization(field.into_result()?)?,
+                                    <V>::deserialization(value.into_result()?)?,
+                                );
+                            }
+                            Ok(map)
+                        }
+                        Reply::Map(pairs) => {
+                            let mut map = $t::<K, V>::new();
+                            for (field, value) in pairs {
+                                map.insert(
+                                    <K>::deserialization(field.into_result()?)?,
+                                    <V>::deserialization(value.into_result()?)?,
+                                );
+                            }
+                            Ok(map)
                         }
-                        _ => Err(RedisError::custom(TypeError, "miss type")),
+                        reply => Err(miss_type(&reply)),
                     }
                 }
             }
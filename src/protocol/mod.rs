@@ -1,44 +1,125 @@
+mod bytes;
 mod maps;
 mod numbers;
 mod sequences;
 mod sets;
 mod strings;
+mod tuples;
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::hash::Hash;
 
-use crate::client::ListBeforeOrAfter;
-use crate::connection::{Reply, SingleStrings};
-use crate::error::ErrorKind::{ResponseError, TypeError};
+use crate::client::{LPosOptions, ListBeforeOrAfter, ListDirection};
+use crate::connection::Reply;
+use crate::error::ErrorKind::{NilReply, TypeError};
 use crate::error::RedisError;
 use crate::implement_deserialization_for_maps;
 use crate::implement_deserialization_for_numbers;
 use crate::implement_deserialization_for_sets;
 use crate::implement_deserialization_for_string;
+use crate::implement_deserialization_for_tuples;
 use crate::implement_serialization_for_maps;
 use crate::implement_serialization_for_numbers;
+use crate::implement_serialization_for_sequences;
 use crate::implement_serialization_for_sets;
 use crate::implement_serialization_for_string;
+use crate::implement_serialization_for_tuples;
 use crate::DataType;
 use crate::RedisResult;
 
 pub trait RedisSerializationProtocol {
     fn serialization(&self) -> Vec<u8>;
+
+    /// The number of bulk strings written by `serialization`, collections write one per item.
+    fn count(&self) -> usize {
+        1
+    }
+
+    /// Serializes a slice of values, only `u8` overrides it so that `&[u8]` is one bulk string.
+    #[doc(hidden)]
+    fn serialization_slice(items: &[Self]) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        items.iter().flat_map(|item| item.serialization()).collect()
+    }
+
+    #[doc(hidden)]
+    fn count_slice(items: &[Self]) -> usize
+    where
+        Self: Sized,
+    {
+        items.iter().map(|item| item.count()).sum()
+    }
 }
 
 pub trait RedisDeserializationProtocol {
     fn deserialization(reply: Reply) -> RedisResult<Self>
     where
         Self: Sized;
+
+    /// Decodes a bulk string as a `Vec<Self>`, only `u8` supports it so that
+    /// `Vec<u8>` keeps binary data untouched.
+    #[doc(hidden)]
+    fn deserialization_from_bytes(_data: Vec<u8>) -> Option<Vec<Self>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// The number of consecutive elements of a flat array decoded as one value, only
+    /// tuples support it so that `Vec<(K, V)>` reads e.g. `ZRANGE ... WITHSCORES`.
+    #[doc(hidden)]
+    fn flat_arity() -> Option<usize>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 // ---------------------------------------
 
+// Decodes a flat array, e.g. `[member, score, member, score]`, `arity` elements at a time.
+// `usize::is_multiple_of` needs Rust 1.87, keep the modulo.
+#[allow(unknown_lints, clippy::manual_is_multiple_of)]
+fn deserialization_flat<T>(array: Vec<Reply>, arity: usize) -> RedisResult<Vec<T>>
+where
+    T: RedisDeserializationProtocol,
+{
+    if array.len() % arity != 0 {
+        return Err(RedisError::custom(
+            TypeError,
+            format!("expected a multiple of {} elements, got {}", arity, array.len()),
+        ));
+    }
+
+    let mut values = Vec::with_capacity(array.len() / arity);
+    let mut array = array.into_iter();
+    loop {
+        let chunk: Vec<Reply> = array.by_ref().take(arity).collect();
+        if chunk.is_empty() {
+            return Ok(values);
+        }
+        values.push(<T>::deserialization(Reply::Arrays(chunk))?);
+    }
+}
+
+// The error for a reply which doesn't match the target type.
+pub(crate) fn miss_type(reply: &Reply) -> RedisError {
+    match reply {
+        Reply::Nil | Reply::Null => RedisError::custom(NilReply, "nil reply"),
+        _ => RedisError::custom(TypeError, "miss type"),
+    }
+}
+
 implement_deserialization_for_string!(String);
-implement_serialization_for_string!(String, &str);
+implement_serialization_for_string!(String, str);
 
-implement_serialization_for_numbers!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64);
-implement_deserialization_for_numbers!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64);
+// `u8` is implemented with binary data, see `bytes.rs`
+implement_serialization_for_numbers!(i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64);
+implement_deserialization_for_numbers!(i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64);
 
 implement_serialization_for_maps!(HashMap, BTreeMap);
 implement_deserialization_for_maps!(HashMap, BTreeMap);
@@ -46,58 +127,113 @@ implement_deserialization_for_maps!(HashMap, BTreeMap);
 implement_serialization_for_sets!(HashSet, BTreeSet);
 implement_deserialization_for_sets!(HashSet); // TODO: BTreeSet, Ord trait
 
+implement_serialization_for_sequences!(Vec);
+
+implement_serialization_for_tuples!(
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H),
+    (A, B, C, D, E, F, G, H, I),
+    (A, B, C, D, E, F, G, H, I, J),
+    (A, B, C, D, E, F, G, H, I, J, K),
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+);
+implement_deserialization_for_tuples!(
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H),
+    (A, B, C, D, E, F, G, H, I),
+    (A, B, C, D, E, F, G, H, I, J),
+    (A, B, C, D, E, F, G, H, I, J, K),
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+);
+
+impl<T> RedisSerializationProtocol for &T
+where
+    T: RedisSerializationProtocol + ?Sized,
+{
+    fn serialization(&self) -> Vec<u8> {
+        (**self).serialization()
+    }
+
+    fn count(&self) -> usize {
+        (**self).count()
+    }
+}
+
 impl RedisDeserializationProtocol for () {
     fn deserialization(reply: Reply) -> RedisResult<Self> {
         match reply {
-            Reply::SingleStrings(single) => match single {
-                SingleStrings::Okay | SingleStrings::Pong => Ok(()),
-                _ => Err(RedisError::custom(ResponseError, "wrong data type")),
-            },
-            _ => Err(RedisError::custom(TypeError, "miss type")),
+            Reply::Status(_) => Ok(()),
+            reply => Err(miss_type(&reply)),
         }
     }
 }
 
-impl RedisDeserializationProtocol for bool {
+impl<T> RedisDeserializationProtocol for Option<T>
+where
+    T: RedisDeserializationProtocol,
+{
     fn deserialization(reply: Reply) -> RedisResult<Self> {
-        let v = <usize>::deserialization(reply)?;
-        Ok(v != 0)
+        match reply {
+            Reply::Nil | Reply::Null => Ok(None),
+            reply => Ok(Some(<T>::deserialization(reply)?)),
+        }
     }
 }
 
-impl RedisDeserializationProtocol for DataType {
+impl RedisDeserializationProtocol for bool {
     fn deserialization(reply: Reply) -> RedisResult<Self> {
         match reply {
-            Reply::SingleStrings(single) => match single {
-                SingleStrings::String => Ok(DataType::String),
-                SingleStrings::List => Ok(DataType::List),
-                SingleStrings::Set => Ok(DataType::Set),
-                _ => Err(RedisError::custom(ResponseError, "wrong data type")),
-            },
-            _ => Err(RedisError::custom(TypeError, "miss type")),
+            Reply::Boolean(b) => Ok(b),
+            reply => {
+                let v = <usize>::deserialization(reply)?;
+                Ok(v != 0)
+            }
         }
     }
 }
 
-macro_rules! implement_serialization_for_sequences {
-    ($($t:ty),*) => {
-        $(
-            impl RedisSerializationProtocol for $t {
-                fn serialization(&self) -> Vec<u8> {
-                    let length = self.len();
-                    let mut buf = Vec::new();
-                    buf.extend(b"*1\r\n");
-                    buf.extend(format!("${}\r\n", length).as_bytes());
-                    buf.extend(self);
-                    buf.extend(b"\r\n");
-                    buf
-                }
-            }
-        )*
-    };
+impl RedisSerializationProtocol for DataType {
+    fn serialization(&self) -> Vec<u8> {
+        match self {
+            DataType::String => "string".serialization(),
+            DataType::List => "list".serialization(),
+            DataType::Set => "set".serialization(),
+            DataType::Hash => "hash".serialization(),
+            DataType::ZSet => "zset".serialization(),
+            DataType::Stream => "stream".serialization(),
+            DataType::Other(name) => name.serialization(),
+        }
+    }
 }
 
-implement_serialization_for_sequences!(Vec<u8>);
+impl RedisDeserializationProtocol for DataType {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        match reply {
+            Reply::Status(status) => match status.as_slice() {
+                b"string" => Ok(DataType::String),
+                b"list" => Ok(DataType::List),
+                b"set" => Ok(DataType::Set),
+                b"hash" => Ok(DataType::Hash),
+                b"zset" => Ok(DataType::ZSet),
+                b"stream" => Ok(DataType::Stream),
+                _ => Ok(DataType::Other(String::from_utf8(status)?)),
+            },
+            reply => Err(miss_type(&reply)),
+        }
+    }
+}
 
 impl RedisSerializationProtocol for ListBeforeOrAfter {
     fn serialization(&self) -> Vec<u8> {
@@ -108,20 +244,53 @@ impl RedisSerializationProtocol for ListBeforeOrAfter {
     }
 }
 
+impl RedisSerializationProtocol for ListDirection {
+    fn serialization(&self) -> Vec<u8> {
+        match self {
+            ListDirection::Left => "LEFT".serialization(),
+            ListDirection::Right => "RIGHT".serialization(),
+        }
+    }
+}
+
+impl RedisSerializationProtocol for LPosOptions {
+    fn serialization(&self) -> Vec<u8> {
+        self.args().concat()
+    }
+
+    fn count(&self) -> usize {
+        self.args().len()
+    }
+}
+
 impl<T> RedisDeserializationProtocol for Vec<T>
 where
     T: RedisDeserializationProtocol,
 {
     fn deserialization(reply: Reply) -> RedisResult<Self> {
         match reply {
-            Reply::Arrays(array) => {
+            Reply::Arrays(array) | Reply::Set(array) => match <T>::flat_arity() {
+                // RESP2 replies are flat, while RESP3 nests each tuple in an array
+                Some(arity) if !matches!(array.first(), Some(Reply::Arrays(_))) => deserialization_flat(array, arity),
+                _ => {
+                    let mut values = Vec::new();
+                    for ele in array {
+                        values.push(<T>::deserialization(ele.into_result()?)?);
+                    }
+                    Ok(values)
+                }
+            },
+            Reply::Map(pairs) if <T>::flat_arity() == Some(2) => {
                 let mut values = Vec::new();
-                for ele in array {
-                    values.push(<T>::deserialization(ele)?);
+                for (key, value) in pairs {
+                    values.push(<T>::deserialization(Reply::Arrays(vec![key, value]))?);
                 }
                 Ok(values)
             }
-            _ => Err(RedisError::custom(TypeError, "miss type")),
+            Reply::BulkStrings(data) => {
+                <T>::deserialization_from_bytes(data).ok_or_else(|| RedisError::custom(TypeError, "miss type"))
+            }
+            reply => Err(miss_type(&reply)),
         }
     }
 }
@@ -26,7 +26,9 @@ macro_rules! implement_deserialization_for_numbers {
                     match reply {
                         Reply::Integers(data) => Ok(String::from_utf8(data)?.parse::<$t>()?),
                         Reply::BulkStrings(data) => Ok(String::from_utf8(data)?.parse::<$t>()?),
-                        _ => Err(RedisError::custom(TypeError, "miss type")),
+                        Reply::Double(data) => Ok(data.to_string().parse::<$t>()?),
+                        Reply::BigNumber(data) => Ok(String::from_utf8(data)?.parse::<$t>()?),
+                        reply => Err(miss_type(&reply)),
                     }
                 }
             }
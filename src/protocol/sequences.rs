@@ -1,4 +1,5 @@
-// TODO
+use crate::protocol::RedisSerializationProtocol;
+
 #[macro_export]
 macro_rules! implement_serialization_for_sequences {
     ($($t: ident),*) => {
@@ -8,14 +9,11 @@ macro_rules! implement_serialization_for_sequences {
                 T: RedisSerializationProtocol,
             {
                 fn serialization(&self) -> Vec<u8> {
-                    let length = self.len();
-                    let mut buf = Vec::new();
-                    buf.extend(b"1\r\n");
-                    buf.extend(format!("${}\r\n", length).as_bytes());
-                    for ele in self {
-                        buf.extend(ele.serialization());
-                    }
-                    buf
+                    <T>::serialization_slice(self)
+                }
+
+                fn count(&self) -> usize {
+                    <T>::count_slice(self)
                 }
             }
         )*
@@ -26,3 +24,29 @@ macro_rules! implement_serialization_for_sequences {
 macro_rules! implement_deserialization_for_sequences {
     ($($t: ident),*) => {};
 }
+
+impl<T> RedisSerializationProtocol for [T]
+where
+    T: RedisSerializationProtocol,
+{
+    fn serialization(&self) -> Vec<u8> {
+        <T>::serialization_slice(self)
+    }
+
+    fn count(&self) -> usize {
+        <T>::count_slice(self)
+    }
+}
+
+impl<T, const N: usize> RedisSerializationProtocol for [T; N]
+where
+    T: RedisSerializationProtocol,
+{
+    fn serialization(&self) -> Vec<u8> {
+        <T>::serialization_slice(self)
+    }
+
+    fn count(&self) -> usize {
+        <T>::count_slice(self)
+    }
+}
@@ -7,14 +7,11 @@ macro_rules! implement_serialization_for_sets {
                 T: RedisSerializationProtocol + Hash + Eq,
             {
                 fn serialization(&self) -> Vec<u8> {
-                    let length = self.len();
-                    let mut buf = Vec::new();
-                    buf.extend(b"1\r\n");
-                    buf.extend(format!("${}\r\n", length).as_bytes());
-                    for member in self {
-                        buf.extend(member.serialization());
-                    }
-                    buf
+                    self.iter().flat_map(|member| member.serialization()).collect()
+                }
+
+                fn count(&self) -> usize {
+                    self.iter().map(|member| member.count()).sum()
                 }
             }
         )*
@@ -31,15 +28,15 @@ macro_rules! implement_deserialization_for_sets {
             {
                 fn deserialization(reply: Reply) -> RedisResult<Self> {
                     match reply {
-                        Reply::Arrays(array) => {
+                        Reply::Arrays(array) | Reply::Set(array) => {
                             let mut set = $t::<T>::new();
                             for reply in array {
-                                let element = <T>::deserialization(reply)?;
+                                let element = <T>::deserialization(reply.into_result()?)?;
                                 set.insert(element);
                             }
                             Ok(set)
                         }
-                        _ => Err(RedisError::custom(TypeError, "miss type")),
+                        reply => Err(miss_type(&reply)),
                     }
                 }
             }
@@ -23,12 +23,12 @@ macro_rules! implement_deserialization_for_string {
             impl RedisDeserializationProtocol for $t {
                 fn deserialization(reply: Reply) -> RedisResult<Self> {
                     match reply {
-                        Reply::SingleStrings(single) => {
-                            match single { SingleStrings::Okay | SingleStrings::Pong => Ok(<$t>::new()), _ => Err(RedisError::custom(ResponseError, "wrong data type")) }
-                        },
+                        Reply::Status(data) => Ok(<$t>::from_utf8(data)?),
                         Reply::BulkStrings(data) => Ok(<$t>::from_utf8(data)?),
-                        Reply::Nil => Ok(<$t>::new()),
-                        _ => Err(RedisError::custom(TypeError, "miss type")),
+                        Reply::VerbatimString { text, .. } => Ok(<$t>::from_utf8(text)?),
+                        Reply::BigNumber(data) => Ok(<$t>::from_utf8(data)?),
+                        Reply::Double(data) => Ok(data.to_string()),
+                        reply => Err(miss_type(&reply)),
                     }
                 }
             }
@@ -0,0 +1,58 @@
+#[macro_export]
+macro_rules! implement_serialization_for_tuples {
+    ($(($($t:ident),+)),*) => {
+        $(
+            impl<$($t),+> RedisSerializationProtocol for ($($t,)+)
+            where
+                $($t: RedisSerializationProtocol,)+
+            {
+                #[allow(non_snake_case)]
+                fn serialization(&self) -> Vec<u8> {
+                    let ($($t,)+) = self;
+                    let mut buf = Vec::new();
+                    $(buf.extend($t.serialization());)+
+                    buf
+                }
+
+                #[allow(non_snake_case)]
+                fn count(&self) -> usize {
+                    let ($($t,)+) = self;
+                    0 $(+ $t.count())+
+                }
+            }
+        )*
+    };
+}
+
+#[macro_export]
+macro_rules! implement_deserialization_for_tuples {
+    ($(($($t:ident),+)),*) => {
+        $(
+            impl<$($t),+> RedisDeserializationProtocol for ($($t,)+)
+            where
+                $($t: RedisDeserializationProtocol,)+
+            {
+                fn deserialization(reply: Reply) -> RedisResult<Self> {
+                    match reply {
+                        Reply::Arrays(array) => {
+                            let expected = [$(stringify!($t)),+].len();
+                            if array.len() != expected {
+                                return Err(RedisError::custom(
+                                    TypeError,
+                                    format!("expected {} elements, got {}", expected, array.len()),
+                                ));
+                            }
+                            let mut array = array.into_iter();
+                            Ok(($(<$t>::deserialization(array.next().unwrap().into_result()?)?,)+))
+                        }
+                        reply => Err(miss_type(&reply)),
+                    }
+                }
+
+                fn flat_arity() -> Option<usize> {
+                    Some([$(stringify!($t)),+].len())
+                }
+            }
+        )*
+    };
+}
@@ -0,0 +1,162 @@
+use std::collections::VecDeque;
+
+#[cfg(feature = "tokio")]
+use crate::aio::AsyncRedisClient;
+use crate::client::Command;
+use crate::connection::Reply;
+use crate::protocol::RedisDeserializationProtocol;
+use crate::{DataType, RedisClient, RedisResult};
+
+// The cursor and options of a SCAN family command, and the items of the last page.
+struct ScanState<T> {
+    // the command name, and the key for HSCAN, SSCAN and ZSCAN
+    cmd: Command,
+    pattern: Option<String>,
+    count: Option<usize>,
+    type_: Option<DataType>,
+    // `None` once the server has returned the cursor 0
+    cursor: Option<u64>,
+    items: VecDeque<T>,
+}
+
+impl<T> ScanState<T>
+where
+    T: RedisDeserializationProtocol,
+{
+    fn new(cmd: Command, pattern: Option<String>) -> ScanState<T> {
+        ScanState {
+            cmd,
+            pattern,
+            count: None,
+            type_: None,
+            cursor: Some(0),
+            items: VecDeque::new(),
+        }
+    }
+
+    // The command fetching the next page, `None` once the iteration is complete.
+    fn next_page(&self) -> Option<Command> {
+        let mut cmd = self.cmd.clone();
+        cmd.arg(self.cursor?);
+        if let Some(ref pattern) = self.pattern {
+            cmd.arg("MATCH").arg(pattern);
+        }
+        if let Some(count) = self.count {
+            cmd.arg("COUNT").arg(count);
+        }
+        if let Some(ref type_) = self.type_ {
+            cmd.arg("TYPE").arg(type_);
+        }
+        Some(cmd)
+    }
+
+    fn push_page(&mut self, reply: RedisResult<Reply>) -> RedisResult<()> {
+        // the iteration stops after an error
+        let (cursor, items) = reply
+            .and_then(<(u64, Vec<T>)>::deserialization)
+            .inspect_err(|_| self.cursor = None)?;
+        self.cursor = if cursor == 0 { None } else { Some(cursor) };
+        self.items.extend(items);
+        Ok(())
+    }
+}
+
+/// An iterator over the items of a SCAN, HSCAN, SSCAN or ZSCAN command, following the cursor
+/// across round trips.
+///
+/// An item may be returned more than once, e.g. if the collection is resized during the iteration.
+pub struct Scan<'a, T> {
+    client: &'a RedisClient,
+    state: ScanState<T>,
+}
+
+impl<'a, T> Scan<'a, T>
+where
+    T: RedisDeserializationProtocol,
+{
+    pub(crate) fn new(client: &'a RedisClient, cmd: Command, pattern: Option<String>) -> Scan<'a, T> {
+        Scan {
+            client,
+            state: ScanState::new(cmd, pattern),
+        }
+    }
+
+    /// Hints how many items the server looks at for each page.
+    pub fn count(mut self, count: usize) -> Self {
+        self.state.count = Some(count);
+        self
+    }
+
+    /// Only returns the keys of the given type, for SCAN.
+    pub fn type_(mut self, type_: DataType) -> Self {
+        self.state.type_ = Some(type_);
+        self
+    }
+}
+
+impl<T> Iterator for Scan<'_, T>
+where
+    T: RedisDeserializationProtocol,
+{
+    type Item = RedisResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.state.items.pop_front() {
+                return Some(Ok(item));
+            }
+
+            // a page may be empty while the iteration isn't complete
+            let cmd = self.state.next_page()?;
+            if let Err(e) = self.state.push_page(self.client.execute(cmd)) {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// The async counterpart of `Scan`, items are returned by `next_item`.
+#[cfg(feature = "tokio")]
+pub struct AsyncScan<'a, T> {
+    client: &'a AsyncRedisClient,
+    state: ScanState<T>,
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, T> AsyncScan<'a, T>
+where
+    T: RedisDeserializationProtocol,
+{
+    pub(crate) fn new(client: &'a AsyncRedisClient, cmd: Command, pattern: Option<String>) -> AsyncScan<'a, T> {
+        AsyncScan {
+            client,
+            state: ScanState::new(cmd, pattern),
+        }
+    }
+
+    /// Hints how many items the server looks at for each page.
+    pub fn count(mut self, count: usize) -> Self {
+        self.state.count = Some(count);
+        self
+    }
+
+    /// Only returns the keys of the given type, for SCAN.
+    pub fn type_(mut self, type_: DataType) -> Self {
+        self.state.type_ = Some(type_);
+        self
+    }
+
+    /// Returns the next item, `None` once the iteration is complete.
+    pub async fn next_item(&mut self) -> Option<RedisResult<T>> {
+        loop {
+            if let Some(item) = self.state.items.pop_front() {
+                return Some(Ok(item));
+            }
+
+            let cmd = self.state.next_page()?;
+            if let Err(e) = self.state.push_page(self.client.execute(cmd).await) {
+                return Some(Err(e));
+            }
+        }
+    }
+}
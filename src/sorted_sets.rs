@@ -0,0 +1,319 @@
+//! The scores, ranges and options of the sorted set commands.
+
+use crate::protocol::RedisSerializationProtocol;
+
+/// A bound of a score range, e.g. the `min` and `max` of ZCOUNT.
+///
+/// A number converts to an inclusive bound, `f64::INFINITY` and `f64::NEG_INFINITY` to the
+/// unbounded ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScoreBound {
+    Inclusive(f64),
+    Exclusive(f64),
+    NegInfinity,
+    PosInfinity,
+}
+
+impl From<f64> for ScoreBound {
+    fn from(score: f64) -> Self {
+        if score == f64::INFINITY {
+            ScoreBound::PosInfinity
+        } else if score == f64::NEG_INFINITY {
+            ScoreBound::NegInfinity
+        } else {
+            ScoreBound::Inclusive(score)
+        }
+    }
+}
+
+impl From<i32> for ScoreBound {
+    fn from(score: i32) -> Self {
+        ScoreBound::Inclusive(score.into())
+    }
+}
+
+impl From<i64> for ScoreBound {
+    fn from(score: i64) -> Self {
+        ScoreBound::Inclusive(score as f64)
+    }
+}
+
+impl From<isize> for ScoreBound {
+    fn from(score: isize) -> Self {
+        ScoreBound::Inclusive(score as f64)
+    }
+}
+
+impl RedisSerializationProtocol for ScoreBound {
+    fn serialization(&self) -> Vec<u8> {
+        match self {
+            ScoreBound::Inclusive(score) => score.serialization(),
+            ScoreBound::Exclusive(score) => format!("({}", score).serialization(),
+            ScoreBound::NegInfinity => "-inf".serialization(),
+            ScoreBound::PosInfinity => "+inf".serialization(),
+        }
+    }
+}
+
+/// A bound of a lexicographical range, e.g. the `min` and `max` of ZRANGE BYLEX.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexBound {
+    Inclusive(Vec<u8>),
+    Exclusive(Vec<u8>),
+    /// `-`, lower than any member.
+    Min,
+    /// `+`, greater than any member.
+    Max,
+}
+
+impl LexBound {
+    pub fn inclusive<M: Into<Vec<u8>>>(member: M) -> LexBound {
+        LexBound::Inclusive(member.into())
+    }
+
+    pub fn exclusive<M: Into<Vec<u8>>>(member: M) -> LexBound {
+        LexBound::Exclusive(member.into())
+    }
+}
+
+impl RedisSerializationProtocol for LexBound {
+    fn serialization(&self) -> Vec<u8> {
+        let bound = match self {
+            LexBound::Inclusive(member) => [b"[", member.as_slice()].concat(),
+            LexBound::Exclusive(member) => [b"(", member.as_slice()].concat(),
+            LexBound::Min => b"-".to_vec(),
+            LexBound::Max => b"+".to_vec(),
+        };
+        bound.serialization()
+    }
+}
+
+/// The options of ZADD, named after the Redis ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ZAddOptions {
+    condition: Option<&'static str>,
+    comparison: Option<&'static str>,
+    changed: bool,
+}
+
+impl ZAddOptions {
+    pub fn new() -> ZAddOptions {
+        ZAddOptions::default()
+    }
+
+    /// Only adds new members, existing ones are left unchanged.
+    pub fn nx(mut self) -> Self {
+        self.condition = Some("NX");
+        self
+    }
+
+    /// Only updates existing members, no member is added.
+    pub fn xx(mut self) -> Self {
+        self.condition = Some("XX");
+        self
+    }
+
+    /// Only updates a score if the new one is greater, new members are still added.
+    pub fn gt(mut self) -> Self {
+        self.comparison = Some("GT");
+        self
+    }
+
+    /// Only updates a score if the new one is less, new members are still added.
+    pub fn lt(mut self) -> Self {
+        self.comparison = Some("LT");
+        self
+    }
+
+    /// Returns the number of members added or updated, rather than only added.
+    pub fn ch(mut self) -> Self {
+        self.changed = true;
+        self
+    }
+
+    fn args(&self) -> Vec<&'static str> {
+        let changed = if self.changed { Some("CH") } else { None };
+        self.condition
+            .into_iter()
+            .chain(self.comparison)
+            .chain(changed)
+            .collect()
+    }
+}
+
+impl RedisSerializationProtocol for ZAddOptions {
+    fn serialization(&self) -> Vec<u8> {
+        self.args().serialization()
+    }
+
+    fn count(&self) -> usize {
+        self.args().len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RangeBy {
+    Rank(isize, isize),
+    Score(ScoreBound, ScoreBound),
+    Lex(LexBound, LexBound),
+}
+
+/// The range of ZRANGE: by rank, score or lexicographical order, optionally reversed.
+///
+/// The bounds are always given from low to high, they're swapped for the reversed order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZRange {
+    by: RangeBy,
+    rev: bool,
+    limit: Option<(usize, usize)>,
+    with_scores: bool,
+}
+
+impl ZRange {
+    /// The members from rank start to stop, both inclusive, negative ranks count from the end.
+    pub fn by_rank(start: isize, stop: isize) -> ZRange {
+        ZRange::new(RangeBy::Rank(start, stop))
+    }
+
+    pub fn by_score<Min, Max>(min: Min, max: Max) -> ZRange
+    where
+        Min: Into<ScoreBound>,
+        Max: Into<ScoreBound>,
+    {
+        ZRange::new(RangeBy::Score(min.into(), max.into()))
+    }
+
+    /// Requires all the members to have the same score.
+    pub fn by_lex(min: LexBound, max: LexBound) -> ZRange {
+        ZRange::new(RangeBy::Lex(min, max))
+    }
+
+    fn new(by: RangeBy) -> ZRange {
+        ZRange {
+            by,
+            rev: false,
+            limit: None,
+            with_scores: false,
+        }
+    }
+
+    /// From the highest to the lowest score.
+    pub fn rev(mut self) -> Self {
+        self.rev = true;
+        self
+    }
+
+    /// Skips offset members and returns at most count, for a range by score or lex.
+    pub fn limit(mut self, offset: usize, count: usize) -> Self {
+        self.limit = Some((offset, count));
+        self
+    }
+
+    /// Returns the scores along with the members, e.g. as `(String, f64)` pairs.
+    pub fn with_scores(mut self) -> Self {
+        self.with_scores = true;
+        self
+    }
+
+    fn args(&self) -> Vec<Vec<u8>> {
+        let (mut start, mut stop, by) = match self.by {
+            RangeBy::Rank(start, stop) => (start.serialization(), stop.serialization(), None),
+            RangeBy::Score(min, max) => (min.serialization(), max.serialization(), Some("BYSCORE")),
+            RangeBy::Lex(ref min, ref max) => (min.serialization(), max.serialization(), Some("BYLEX")),
+        };
+        // a reversed range by score or lex starts from the max
+        if self.rev && by.is_some() {
+            std::mem::swap(&mut start, &mut stop);
+        }
+
+        let mut args = vec![start, stop];
+        args.extend(by.map(|by| by.serialization()));
+        if self.rev {
+            args.push("REV".serialization());
+        }
+        if let Some((offset, count)) = self.limit {
+            args.extend(vec![
+                "LIMIT".serialization(),
+                offset.serialization(),
+                count.serialization(),
+            ]);
+        }
+        if self.with_scores {
+            args.push("WITHSCORES".serialization());
+        }
+        args
+    }
+}
+
+impl RedisSerializationProtocol for ZRange {
+    fn serialization(&self) -> Vec<u8> {
+        self.args().concat()
+    }
+
+    fn count(&self) -> usize {
+        self.args().len()
+    }
+}
+
+/// How ZUNION and ZINTER combine the scores of a member in several sets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregate {
+    Sum,
+    Min,
+    Max,
+}
+
+/// The WEIGHTS and AGGREGATE options of ZUNION, ZINTER and their STORE variants.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZAggregateOptions {
+    weights: Vec<f64>,
+    aggregate: Option<Aggregate>,
+}
+
+impl ZAggregateOptions {
+    pub fn new() -> ZAggregateOptions {
+        ZAggregateOptions::default()
+    }
+
+    /// The factors the scores of each set are multiplied by, in the order of the keys.
+    pub fn weights<I: IntoIterator<Item = f64>>(mut self, weights: I) -> Self {
+        self.weights = weights.into_iter().collect();
+        self
+    }
+
+    /// `Sum` by default.
+    pub fn aggregate(mut self, aggregate: Aggregate) -> Self {
+        self.aggregate = Some(aggregate);
+        self
+    }
+
+    fn args(&self) -> Vec<Vec<u8>> {
+        let mut args = Vec::new();
+        if !self.weights.is_empty() {
+            args.push("WEIGHTS".serialization());
+            args.extend(self.weights.iter().map(f64::serialization));
+        }
+        if let Some(aggregate) = self.aggregate {
+            args.push("AGGREGATE".serialization());
+            args.push(
+                match aggregate {
+                    Aggregate::Sum => "SUM",
+                    Aggregate::Min => "MIN",
+                    Aggregate::Max => "MAX",
+                }
+                .serialization(),
+            );
+        }
+        args
+    }
+}
+
+impl RedisSerializationProtocol for ZAggregateOptions {
+    fn serialization(&self) -> Vec<u8> {
+        self.args().concat()
+    }
+
+    fn count(&self) -> usize {
+        self.args().len()
+    }
+}
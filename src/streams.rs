@@ -0,0 +1,617 @@
+//! The IDs, entries, options and replies of the stream commands.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::connection::Reply;
+use crate::error::{ErrorKind, RedisError};
+use crate::protocol::{miss_type, RedisDeserializationProtocol, RedisSerializationProtocol};
+use crate::RedisResult;
+
+/// The ID of a stream entry, `<ms>-<seq>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StreamId {
+    /// The Unix time in milliseconds the entry was added at.
+    pub ms: u64,
+    /// Tells apart the entries added in the same millisecond.
+    pub seq: u64,
+}
+
+impl StreamId {
+    pub fn new(ms: u64, seq: u64) -> StreamId {
+        StreamId { ms, seq }
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+impl FromStr for StreamId {
+    type Err = RedisError;
+
+    /// Parses `<ms>-<seq>`, or `<ms>` which stands for `<ms>-0`.
+    fn from_str(s: &str) -> RedisResult<StreamId> {
+        let invalid = || RedisError::custom(ErrorKind::TypeError, format!("invalid stream ID: {}", s));
+        let (ms, seq) = s.split_once('-').unwrap_or((s, "0"));
+        Ok(StreamId {
+            ms: ms.parse().map_err(|_| invalid())?,
+            seq: seq.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl RedisSerializationProtocol for StreamId {
+    fn serialization(&self) -> Vec<u8> {
+        self.to_string().serialization()
+    }
+}
+
+impl RedisDeserializationProtocol for StreamId {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        <String>::deserialization(reply)?.parse()
+    }
+}
+
+/// A bound of a range of IDs, e.g. the `start` and `end` of XRANGE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamBound {
+    Inclusive(StreamId),
+    Exclusive(StreamId),
+    /// `-`, the lowest ID.
+    Min,
+    /// `+`, the highest ID.
+    Max,
+}
+
+impl From<StreamId> for StreamBound {
+    fn from(id: StreamId) -> Self {
+        StreamBound::Inclusive(id)
+    }
+}
+
+impl RedisSerializationProtocol for StreamBound {
+    fn serialization(&self) -> Vec<u8> {
+        match self {
+            StreamBound::Inclusive(id) => id.serialization(),
+            StreamBound::Exclusive(id) => format!("({}", id).serialization(),
+            StreamBound::Min => "-".serialization(),
+            StreamBound::Max => "+".serialization(),
+        }
+    }
+}
+
+/// Where XREAD and XREADGROUP start reading a stream, or where XGROUP sets a group at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadFrom {
+    /// The entries with a greater ID.
+    After(StreamId),
+    /// `$`, the entries added from now on.
+    Latest,
+    /// `>`, the entries never delivered to the group, for XREADGROUP only.
+    Undelivered,
+}
+
+impl From<StreamId> for ReadFrom {
+    fn from(id: StreamId) -> Self {
+        ReadFrom::After(id)
+    }
+}
+
+impl RedisSerializationProtocol for ReadFrom {
+    fn serialization(&self) -> Vec<u8> {
+        match self {
+            ReadFrom::After(id) => id.serialization(),
+            ReadFrom::Latest => "$".serialization(),
+            ReadFrom::Undelivered => ">".serialization(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrimBy {
+    MaxLen(usize),
+    MinId(StreamId),
+}
+
+/// How XTRIM and XADD trim a stream, by length or by the lowest ID kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamTrim {
+    by: TrimBy,
+    approx: bool,
+    limit: Option<usize>,
+}
+
+impl StreamTrim {
+    /// Keeps the latest maxlen entries.
+    pub fn maxlen(maxlen: usize) -> StreamTrim {
+        StreamTrim::new(TrimBy::MaxLen(maxlen))
+    }
+
+    /// Evicts the entries with an ID lower than id.
+    pub fn minid(id: StreamId) -> StreamTrim {
+        StreamTrim::new(TrimBy::MinId(id))
+    }
+
+    fn new(by: TrimBy) -> StreamTrim {
+        StreamTrim {
+            by,
+            approx: false,
+            limit: None,
+        }
+    }
+
+    /// `~`, lets the server keep a few more entries when it's more efficient.
+    pub fn approx(mut self) -> Self {
+        self.approx = true;
+        self
+    }
+
+    /// Evicts at most limit entries, for an approximate trim.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn args(&self) -> Vec<Vec<u8>> {
+        let (strategy, threshold) = match self.by {
+            TrimBy::MaxLen(maxlen) => ("MAXLEN", maxlen.serialization()),
+            TrimBy::MinId(id) => ("MINID", id.serialization()),
+        };
+        let operator = if self.approx { "~" } else { "=" };
+        let mut args = vec![strategy.serialization(), operator.serialization(), threshold];
+        if let Some(limit) = self.limit {
+            args.extend(vec!["LIMIT".serialization(), limit.serialization()]);
+        }
+        args
+    }
+}
+
+impl RedisSerializationProtocol for StreamTrim {
+    fn serialization(&self) -> Vec<u8> {
+        self.args().concat()
+    }
+
+    fn count(&self) -> usize {
+        self.args().len()
+    }
+}
+
+/// The options of XADD.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct XAddOptions {
+    id: Option<StreamId>,
+    nomkstream: bool,
+    trim: Option<StreamTrim>,
+}
+
+impl XAddOptions {
+    pub fn new() -> XAddOptions {
+        XAddOptions::default()
+    }
+
+    /// Adds the entry with the given ID rather than one generated by the server.
+    pub fn id(mut self, id: StreamId) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Doesn't create the stream if it doesn't exist, no entry is added then.
+    pub fn nomkstream(mut self) -> Self {
+        self.nomkstream = true;
+        self
+    }
+
+    /// Trims the stream once the entry is added.
+    pub fn trim(mut self, trim: StreamTrim) -> Self {
+        self.trim = Some(trim);
+        self
+    }
+
+    fn args(&self) -> Vec<Vec<u8>> {
+        let mut args = Vec::new();
+        if self.nomkstream {
+            args.push("NOMKSTREAM".serialization());
+        }
+        if let Some(trim) = self.trim {
+            args.extend(trim.args());
+        }
+        args.push(match self.id {
+            Some(id) => id.serialization(),
+            None => "*".serialization(),
+        });
+        args
+    }
+}
+
+impl RedisSerializationProtocol for XAddOptions {
+    fn serialization(&self) -> Vec<u8> {
+        self.args().concat()
+    }
+
+    fn count(&self) -> usize {
+        self.args().len()
+    }
+}
+
+/// The options of XREAD and XREADGROUP.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct XReadOptions {
+    count: Option<usize>,
+    block: Option<Duration>,
+    noack: bool,
+}
+
+impl XReadOptions {
+    pub fn new() -> XReadOptions {
+        XReadOptions::default()
+    }
+
+    /// Returns at most count entries per stream.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Waits up to timeout for an entry when there is none, or forever if zero. The read
+    /// timeout of the connection is extended accordingly.
+    pub fn block(mut self, timeout: Duration) -> Self {
+        self.block = Some(timeout);
+        self
+    }
+
+    /// Doesn't add the entries to the pending entries list, for XREADGROUP only.
+    pub fn noack(mut self) -> Self {
+        self.noack = true;
+        self
+    }
+
+    pub(crate) fn block_timeout(&self) -> Option<Duration> {
+        self.block
+    }
+
+    fn args(&self) -> Vec<Vec<u8>> {
+        let mut args = Vec::new();
+        if let Some(count) = self.count {
+            args.extend(vec!["COUNT".serialization(), count.serialization()]);
+        }
+        if let Some(block) = self.block {
+            // rounded up, a sub-millisecond timeout would otherwise block forever as 0
+            let millis = block.as_nanos().div_ceil(1_000_000) as u64;
+            args.extend(vec!["BLOCK".serialization(), millis.serialization()]);
+        }
+        if self.noack {
+            args.push("NOACK".serialization());
+        }
+        args
+    }
+}
+
+impl RedisSerializationProtocol for XReadOptions {
+    fn serialization(&self) -> Vec<u8> {
+        self.args().concat()
+    }
+
+    fn count(&self) -> usize {
+        self.args().len()
+    }
+}
+
+/// The range of the extended form of XPENDING.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingRange {
+    start: StreamBound,
+    end: StreamBound,
+    count: usize,
+    idle: Option<Duration>,
+    consumer: Option<String>,
+}
+
+impl PendingRange {
+    /// At most count pending entries with an ID from start to end.
+    pub fn new(start: StreamBound, end: StreamBound, count: usize) -> PendingRange {
+        PendingRange {
+            start,
+            end,
+            count,
+            idle: None,
+            consumer: None,
+        }
+    }
+
+    /// Only the entries delivered at least min_idle_time ago.
+    pub fn idle(mut self, min_idle_time: Duration) -> Self {
+        self.idle = Some(min_idle_time);
+        self
+    }
+
+    /// Only the entries delivered to consumer.
+    pub fn consumer<C: ToString>(mut self, consumer: C) -> Self {
+        self.consumer = Some(consumer.to_string());
+        self
+    }
+
+    fn args(&self) -> Vec<Vec<u8>> {
+        let mut args = Vec::new();
+        if let Some(idle) = self.idle {
+            args.extend(vec!["IDLE".serialization(), (idle.as_millis() as u64).serialization()]);
+        }
+        args.extend(vec![
+            self.start.serialization(),
+            self.end.serialization(),
+            self.count.serialization(),
+        ]);
+        if let Some(ref consumer) = self.consumer {
+            args.push(consumer.serialization());
+        }
+        args
+    }
+}
+
+impl RedisSerializationProtocol for PendingRange {
+    fn serialization(&self) -> Vec<u8> {
+        self.args().concat()
+    }
+
+    fn count(&self) -> usize {
+        self.args().len()
+    }
+}
+
+/// An entry of a stream, with its fields in the order they were added. The values are decoded
+/// as `V`, e.g. `Vec<u8>` for binary data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamEntry<V = String> {
+    pub id: StreamId,
+    pub fields: Vec<(String, V)>,
+}
+
+impl<V> StreamEntry<V> {
+    /// Returns the value of field, the first one if the entry has it more than once.
+    pub fn get(&self, field: &str) -> Option<&V> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == field)
+            .map(|(_, value)| value)
+    }
+}
+
+impl<V: RedisDeserializationProtocol> RedisDeserializationProtocol for StreamEntry<V> {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        let (id, fields) = pair(reply)?;
+        let fields = match fields {
+            // an entry which was deleted while pending
+            Reply::Nil | Reply::Null => Vec::new(),
+            fields => <Vec<(String, V)>>::deserialization(fields)?,
+        };
+        Ok(StreamEntry {
+            id: <StreamId>::deserialization(id)?,
+            fields,
+        })
+    }
+}
+
+/// The entries XREAD and XREADGROUP read from one stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamKey<V = String> {
+    pub key: String,
+    pub entries: Vec<StreamEntry<V>>,
+}
+
+impl<V: RedisDeserializationProtocol> RedisDeserializationProtocol for StreamKey<V> {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        let (key, entries) = pair(reply)?;
+        Ok(StreamKey {
+            key: <String>::deserialization(key)?,
+            entries: <Vec<StreamEntry<V>>>::deserialization(entries)?,
+        })
+    }
+
+    // RESP3 replies with a map of the keys to their entries
+    fn flat_arity() -> Option<usize> {
+        Some(2)
+    }
+}
+
+/// The summary form of XPENDING.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingSummary {
+    /// The number of pending entries.
+    pub count: usize,
+    /// The lowest ID of the pending entries, `None` if there is none.
+    pub min: Option<StreamId>,
+    pub max: Option<StreamId>,
+    /// The consumers with pending entries, and the number of them.
+    pub consumers: Vec<(String, usize)>,
+}
+
+impl RedisDeserializationProtocol for PendingSummary {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        let (count, min, max, consumers) =
+            <(usize, Option<StreamId>, Option<StreamId>, Option<Vec<(String, usize)>>)>::deserialization(reply)?;
+        Ok(PendingSummary {
+            count,
+            min,
+            max,
+            consumers: consumers.unwrap_or_default(),
+        })
+    }
+}
+
+/// An entry of the extended form of XPENDING.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingEntry {
+    pub id: StreamId,
+    /// The consumer the entry was last delivered to.
+    pub consumer: String,
+    /// The time elapsed since the entry was last delivered.
+    pub idle: Duration,
+    /// The number of times the entry was delivered.
+    pub deliveries: usize,
+}
+
+impl RedisDeserializationProtocol for PendingEntry {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        let (id, consumer, idle, deliveries) = <(StreamId, String, u64, usize)>::deserialization(reply)?;
+        Ok(PendingEntry {
+            id,
+            consumer,
+            idle: Duration::from_millis(idle),
+            deliveries,
+        })
+    }
+}
+
+/// The reply of XAUTOCLAIM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoClaim<V = String> {
+    /// The ID to start the next call from, `0-0` once the whole pending entries list was scanned.
+    pub next: StreamId,
+    pub entries: Vec<StreamEntry<V>>,
+    /// The IDs of the entries which were deleted while pending, removed from the list. Only
+    /// Redis 7 and later report them.
+    pub deleted: Vec<StreamId>,
+}
+
+impl<V: RedisDeserializationProtocol> RedisDeserializationProtocol for AutoClaim<V> {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        let mut elements = match reply {
+            Reply::Arrays(elements) if elements.len() >= 2 => elements.into_iter(),
+            reply => return Err(miss_type(&reply)),
+        };
+        let next = <StreamId>::deserialization(elements.next().unwrap_or(Reply::Nil))?;
+        let entries = entries(elements.next().unwrap_or(Reply::Nil))?;
+        let deleted = match elements.next() {
+            Some(deleted) => <Vec<StreamId>>::deserialization(deleted)?,
+            None => Vec::new(),
+        };
+        Ok(AutoClaim { next, entries, deleted })
+    }
+}
+
+/// The reply of XINFO STREAM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamInfo<V = String> {
+    pub length: usize,
+    pub radix_tree_keys: usize,
+    pub radix_tree_nodes: usize,
+    pub last_generated_id: StreamId,
+    /// The number of consumer groups.
+    pub groups: usize,
+    pub first_entry: Option<StreamEntry<V>>,
+    pub last_entry: Option<StreamEntry<V>>,
+}
+
+impl<V: RedisDeserializationProtocol> RedisDeserializationProtocol for StreamInfo<V> {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        let mut fields = Fields::new(reply)?;
+        Ok(StreamInfo {
+            length: fields.take("length")?,
+            radix_tree_keys: fields.take("radix-tree-keys")?,
+            radix_tree_nodes: fields.take("radix-tree-nodes")?,
+            last_generated_id: fields.take("last-generated-id")?,
+            groups: fields.take("groups")?,
+            first_entry: fields.take("first-entry")?,
+            last_entry: fields.take("last-entry")?,
+        })
+    }
+}
+
+/// A consumer group in the reply of XINFO GROUPS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupInfo {
+    pub name: String,
+    /// The number of consumers.
+    pub consumers: usize,
+    /// The number of pending entries.
+    pub pending: usize,
+    pub last_delivered_id: StreamId,
+}
+
+impl RedisDeserializationProtocol for GroupInfo {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        let mut fields = Fields::new(reply)?;
+        Ok(GroupInfo {
+            name: fields.take("name")?,
+            consumers: fields.take("consumers")?,
+            pending: fields.take("pending")?,
+            last_delivered_id: fields.take("last-delivered-id")?,
+        })
+    }
+}
+
+/// A consumer in the reply of XINFO CONSUMERS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerInfo {
+    pub name: String,
+    /// The number of pending entries.
+    pub pending: usize,
+    /// The time elapsed since the consumer last interacted with the server.
+    pub idle: Duration,
+}
+
+impl RedisDeserializationProtocol for ConsumerInfo {
+    fn deserialization(reply: Reply) -> RedisResult<Self> {
+        let mut fields = Fields::new(reply)?;
+        Ok(ConsumerInfo {
+            name: fields.take("name")?,
+            pending: fields.take("pending")?,
+            idle: Duration::from_millis(fields.take("idle")?),
+        })
+    }
+}
+
+/// Decodes the entries of XCLAIM and XAUTOCLAIM, which are nil for the entries deleted while
+/// pending before Redis 7.
+pub(crate) fn entries<V: RedisDeserializationProtocol>(reply: Reply) -> RedisResult<Vec<StreamEntry<V>>> {
+    let entries = <Vec<Option<StreamEntry<V>>>>::deserialization(reply)?;
+    Ok(entries.into_iter().flatten().collect())
+}
+
+// Splits a two elements array.
+fn pair(reply: Reply) -> RedisResult<(Reply, Reply)> {
+    match reply {
+        Reply::Arrays(elements) if elements.len() == 2 => {
+            let mut elements = elements.into_iter();
+            Ok((elements.next().unwrap(), elements.next().unwrap()))
+        }
+        reply => Err(miss_type(&reply)),
+    }
+}
+
+// The fields of the XINFO replies, a map in RESP3 and a flat array in RESP2.
+struct Fields(HashMap<String, Reply>);
+
+impl Fields {
+    fn new(reply: Reply) -> RedisResult<Fields> {
+        let pairs = match reply {
+            Reply::Map(pairs) => pairs,
+            Reply::Arrays(array) if array.len() % 2 == 0 => {
+                let mut array = array.into_iter();
+                let mut pairs = Vec::new();
+                while let (Some(field), Some(value)) = (array.next(), array.next()) {
+                    pairs.push((field, value));
+                }
+                pairs
+            }
+            reply => return Err(miss_type(&reply)),
+        };
+        let mut fields = HashMap::new();
+        for (field, value) in pairs {
+            fields.insert(<String>::deserialization(field)?, value);
+        }
+        Ok(Fields(fields))
+    }
+
+    fn take<T: RedisDeserializationProtocol>(&mut self, field: &str) -> RedisResult<T> {
+        let value = self
+            .0
+            .remove(field)
+            .ok_or_else(|| RedisError::custom(ErrorKind::TypeError, format!("missing field: {}", field)))?;
+        <T>::deserialization(value)
+    }
+}
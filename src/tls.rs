@@ -0,0 +1,134 @@
+use std::convert::TryFrom;
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::Arc;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{ring, CryptoProvider};
+use rustls::{ClientConfig, ClientConnection, DigitallySignedStruct, RootCertStore, SignatureScheme, StreamOwned};
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+
+use crate::config::TlsConfig;
+use crate::error::{ErrorKind::InvalidConfig, RedisError};
+use crate::RedisResult;
+
+pub(crate) type TlsStream = StreamOwned<ClientConnection, TcpStream>;
+
+/// Connects with TLS according to a `TlsConfig`, the rustls config is built once and shared by
+/// the connections, as it reads the certificate files.
+pub(crate) struct TlsConnector {
+    config: Arc<ClientConfig>,
+    server_name: Option<String>,
+}
+
+impl TlsConnector {
+    pub(crate) fn new(config: &TlsConfig) -> RedisResult<TlsConnector> {
+        Ok(TlsConnector {
+            config: Arc::new(client_config(config)?),
+            server_name: config.server_name.clone(),
+        })
+    }
+
+    /// Wraps `stream` in TLS, `host` is the server name verified unless the config overrides it.
+    pub(crate) fn connect(&self, stream: TcpStream, host: &str) -> RedisResult<TlsStream> {
+        let server_name = self.server_name.as_deref().unwrap_or(host);
+        let server_name = ServerName::try_from(server_name.to_string()).map_err(invalid_config)?;
+
+        let conn = ClientConnection::new(self.config.clone(), server_name).map_err(invalid_config)?;
+        let mut stream = StreamOwned::new(conn, stream);
+        // handshake now, so that a failure is reported by the connect
+        while stream.conn.is_handshaking() {
+            stream.conn.complete_io(&mut stream.sock)?;
+        }
+        Ok(stream)
+    }
+}
+
+fn client_config(config: &TlsConfig) -> RedisResult<ClientConfig> {
+    let provider = Arc::new(ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(invalid_config)?;
+
+    let builder = if config.insecure_skip_verify {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerification(provider)))
+    } else {
+        let mut roots = RootCertStore::empty();
+        match config.ca_file {
+            Some(ref path) => {
+                for cert in read_certs(path)? {
+                    roots.add(cert).map_err(invalid_config)?;
+                }
+            }
+            None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+        builder.with_root_certificates(roots)
+    };
+
+    match (&config.client_cert_file, &config.client_key_file) {
+        (Some(cert), Some(key)) => {
+            let key = PrivateKeyDer::from_pem_file(key).map_err(invalid_config)?;
+            builder
+                .with_client_auth_cert(read_certs(cert)?, key)
+                .map_err(invalid_config)
+        }
+        (None, None) => Ok(builder.with_no_client_auth()),
+        _ => Err(RedisError::custom(
+            InvalidConfig,
+            "client certificate and key must be set together",
+        )),
+    }
+}
+
+fn read_certs(path: &Path) -> RedisResult<Vec<CertificateDer<'static>>> {
+    CertificateDer::pem_file_iter(path)
+        .map_err(invalid_config)?
+        .collect::<Result<_, _>>()
+        .map_err(invalid_config)
+}
+
+fn invalid_config<E: std::fmt::Display>(e: E) -> RedisError {
+    RedisError::custom(InvalidConfig, format!("invalid TLS config: {}", e))
+}
+
+// Accepts any server certificate, signatures are still checked.
+#[derive(Debug)]
+struct NoVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
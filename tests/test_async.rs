@@ -0,0 +1,347 @@
+#![cfg(feature = "tokio")]
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use redisclient::config::RedisConfig;
+use redisclient::sorted_sets::ZRange;
+use redisclient::streams::{ReadFrom, StreamKey, XReadOptions};
+use redisclient::{cmd, AsyncRedisClient, ErrorKind};
+
+#[tokio::test]
+pub async fn test_async_strings() {
+    let client = AsyncRedisClient::new().unwrap();
+
+    client.simple_set("async-key", "Hello").await.unwrap();
+    let value: String = client.get("async-key").await.unwrap();
+    assert_eq!(value, "Hello");
+    let value: Option<String> = client.get("async-missing").await.unwrap();
+    assert_eq!(value, None);
+
+    client.mset(vec![("async-key1", 1), ("async-key2", 2)]).await.unwrap();
+    let values: Vec<isize> = client.mget(vec!["async-key1", "async-key2"]).await.unwrap();
+    assert_eq!(values, vec![1, 2]);
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_select() {
+    let client = AsyncRedisClient::new().unwrap();
+    client.select(4).await.unwrap();
+    client.simple_set("async-select", "Hello").await.unwrap();
+
+    // connections opened after the switch use the database too
+    let (first, second) = tokio::join!(client.get("async-select"), client.get("async-select"));
+    let (first, second): (Option<String>, Option<String>) = (first.unwrap(), second.unwrap());
+    assert_eq!(first, Some("Hello".to_string()));
+    assert_eq!(second, Some("Hello".to_string()));
+
+    let other = AsyncRedisClient::new().unwrap();
+    let value: Option<String> = other.get("async-select").await.unwrap();
+    assert_eq!(value, None);
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_command_and_pipeline() {
+    let client = AsyncRedisClient::new().unwrap();
+
+    let reply: String = cmd("ECHO").arg("Hello").query_async(&client).await.unwrap();
+    assert_eq!(reply, "Hello");
+
+    let mut pipeline = client.pipe();
+    pipeline
+        .simple_set("async-pipeline", 1)
+        .incr("async-pipeline")
+        .get("async-pipeline");
+    let ((), incremented, value): ((), i64, i64) = pipeline.query_async(&client).await.unwrap();
+    assert_eq!(incremented, 2);
+    assert_eq!(value, 2);
+
+    let mut pipeline = client.pipe();
+    pipeline
+        .transaction_mode()
+        .incr("async-pipeline")
+        .incr("async-pipeline");
+    let values: (i64, i64) = pipeline.query_async(&client).await.unwrap();
+    assert_eq!(values, (3, 4));
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_concurrent_tasks() {
+    let client = Arc::new(
+        AsyncRedisClient::with_config(RedisConfig {
+            pool_capacity: 2,
+            ..Default::default()
+        })
+        .unwrap(),
+    );
+
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.incr("async-counter").await.unwrap() })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let value: i64 = client.get("async-counter").await.unwrap();
+    assert_eq!(value, 8);
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_cancelled_command() {
+    let client = AsyncRedisClient::with_config(RedisConfig {
+        pool_capacity: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    client.simple_set("async-cancelled", "value").await.unwrap();
+
+    let mut sleep = cmd("DEBUG");
+    sleep.arg("SLEEP").arg(0.3);
+    let cancelled = tokio::time::timeout(Duration::from_millis(100), sleep.query_async::<()>(&client)).await;
+    assert!(cancelled.is_err());
+
+    // the late reply of DEBUG SLEEP must not be read as the reply of the next command
+    let value: String = client.get("async-cancelled").await.unwrap();
+    assert_eq!(value, "value");
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_read_timeout() {
+    let client = AsyncRedisClient::with_config(RedisConfig {
+        read_timeout: Some(Duration::from_millis(100)),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let err = cmd("DEBUG")
+        .arg("SLEEP")
+        .arg(0.3)
+        .query_async::<()>(&client)
+        .await
+        .err()
+        .unwrap();
+    assert!(err.is_timeout());
+    // DEBUG SLEEP blocks the whole server
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    client.ping().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_reply_read_byte_by_byte() {
+    let mut values: Vec<Option<String>> = (0..2000).map(|i| Some(format!("value-{}", i))).collect();
+    values.push(None);
+    values.push(Some("x".repeat(64 * 1024)));
+
+    let mut reply = format!("*{}\r\n", values.len()).into_bytes();
+    for value in &values {
+        match value {
+            Some(value) => reply.extend(format!("${}\r\n{}\r\n", value.len(), value).as_bytes()),
+            None => reply.extend(b"$-1\r\n"),
+        }
+    }
+
+    // a server answering the first command with `reply`, sent one byte at a time
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.set_nodelay(true).unwrap();
+        let mut buf = [0; 1024];
+        assert!(stream.read(&mut buf).unwrap() > 0);
+        for byte in reply {
+            stream.write_all(&[byte]).unwrap();
+        }
+    });
+
+    let client = AsyncRedisClient::with_config(RedisConfig {
+        address,
+        ..Default::default()
+    })
+    .unwrap();
+    let received: Vec<Option<String>> = cmd("LRANGE")
+        .arg("mylist")
+        .arg(0)
+        .arg(-1)
+        .query_async(&client)
+        .await
+        .unwrap();
+    assert_eq!(received, values);
+    server.join().unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_scan() {
+    let client = AsyncRedisClient::new().unwrap();
+    for i in 0..15 {
+        client.simple_set(format!("async-scan:{}", i), i).await.unwrap();
+    }
+
+    let mut scan = client.scan_match("async-scan:*").count(4);
+    let mut keys: Vec<String> = Vec::new();
+    while let Some(key) = scan.next_item().await {
+        keys.push(key.unwrap());
+    }
+    keys.sort();
+    keys.dedup();
+    assert_eq!(keys.len(), 15);
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_sorted_sets() {
+    let client = AsyncRedisClient::new().unwrap();
+    client
+        .zadd("async-zset", vec![(1.5, "one"), (2.5, "two")])
+        .await
+        .unwrap();
+
+    let items: Vec<(String, f64)> = client
+        .zrange("async-zset", ZRange::by_score(2, f64::INFINITY).with_scores())
+        .await
+        .unwrap();
+    assert_eq!(items, vec![("two".to_string(), 2.5)]);
+    assert_eq!(client.zincrby("async-zset", 1.0, "one").await.unwrap(), 2.5);
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_blpop() {
+    let client = Arc::new(
+        AsyncRedisClient::with_config(RedisConfig {
+            read_timeout: Some(Duration::from_millis(50)),
+            ..Default::default()
+        })
+        .unwrap(),
+    );
+
+    let pusher = {
+        let client = client.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            client.rpush("async-queue", vec!["job"]).await.unwrap();
+        })
+    };
+    let popped: Option<(String, String)> = client.blpop(vec!["async-queue"], Duration::from_secs(2)).await.unwrap();
+    assert_eq!(popped, Some(("async-queue".to_string(), "job".to_string())));
+    pusher.await.unwrap();
+
+    let popped: Option<(String, String)> = client
+        .blpop(vec!["async-queue"], Duration::from_millis(100))
+        .await
+        .unwrap();
+    assert_eq!(popped, None);
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_blpop_outside_pool_capacity() {
+    let client = Arc::new(
+        AsyncRedisClient::with_config(RedisConfig {
+            pool_capacity: 1,
+            pool_checkout_timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        })
+        .unwrap(),
+    );
+
+    let popper = {
+        let client = client.clone();
+        tokio::spawn(async move {
+            let popped: Option<(String, String)> = client
+                .blpop(vec!["async-capacity"], Duration::from_secs(2))
+                .await
+                .unwrap();
+            popped
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // the waiting BLPOP doesn't hold the only connection of the pool
+    client.rpush("async-capacity", vec!["job"]).await.unwrap();
+    let popped = popper.await.unwrap();
+    assert_eq!(popped, Some(("async-capacity".to_string(), "job".to_string())));
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_blocking_pool_capacity() {
+    let client = Arc::new(
+        AsyncRedisClient::with_config(RedisConfig {
+            blocking_pool_capacity: 1,
+            pool_checkout_timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        })
+        .unwrap(),
+    );
+
+    let popper = {
+        let client = client.clone();
+        tokio::spawn(async move {
+            let popped: Option<(String, String)> = client
+                .blpop(vec!["async-blocking"], Duration::from_secs(2))
+                .await
+                .unwrap();
+            popped
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // the only blocking connection is waiting
+    let err = client
+        .blpop::<_, String, String, _>(vec!["async-blocking"], Duration::from_secs(1))
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::PoolTimeout);
+    client.rpush("async-blocking", vec!["job"]).await.unwrap();
+    let popped = popper.await.unwrap();
+    assert_eq!(popped, Some(("async-blocking".to_string(), "job".to_string())));
+
+    client.flushall().await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_async_streams() {
+    let client = AsyncRedisClient::new().unwrap();
+    client
+        .xgroup_create("async-stream", "group", ReadFrom::Latest, true)
+        .await
+        .unwrap();
+    let id = client.xadd("async-stream", vec![("event", "created")]).await.unwrap();
+
+    let options = XReadOptions::new().block(Duration::from_millis(100));
+    let streams: Vec<StreamKey> = client
+        .xreadgroup(
+            "group",
+            "worker",
+            vec![("async-stream", ReadFrom::Undelivered)],
+            options,
+        )
+        .await
+        .unwrap();
+    assert_eq!(streams[0].entries[0].id, id);
+    assert_eq!(client.xack("async-stream", "group", vec![id]).await.unwrap(), 1);
+
+    client.flushall().await.unwrap();
+}
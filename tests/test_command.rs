@@ -0,0 +1,48 @@
+use redisclient::{cmd, RedisClient};
+
+#[test]
+pub fn test_cmd_query() {
+    let client = RedisClient::new().unwrap();
+
+    cmd("SET").arg("key").arg("Hello").query::<()>(&client).unwrap();
+
+    let encoding: String = cmd("OBJECT").arg("ENCODING").arg("key").query(&client).unwrap();
+    assert_eq!(encoding, "embstr");
+
+    let encoding: Option<String> = cmd("OBJECT").arg("ENCODING").arg("nosuchkey").query(&client).unwrap();
+    assert_eq!(encoding, None);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_cmd_with_collection_args() {
+    let client = RedisClient::new().unwrap();
+
+    let amount: usize = cmd("RPUSH")
+        .arg("mylist")
+        .arg(vec!["a", "b", "c"])
+        .query(&client)
+        .unwrap();
+    assert_eq!(amount, 3);
+
+    let elements: Vec<String> = cmd("LRANGE").arg("mylist").arg(0).arg(-1).query(&client).unwrap();
+    assert_eq!(elements, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_cmd_in_pipeline() {
+    let client = RedisClient::new().unwrap();
+
+    let (_, value): ((), String) = client
+        .pipe()
+        .add_command(cmd("SET").arg("key").arg(1))
+        .add_command(cmd("ECHO").arg("Hello"))
+        .query(&client)
+        .unwrap();
+    assert_eq!(value, "Hello");
+
+    client.flushall().unwrap();
+}
@@ -1,8 +1,13 @@
-use redisclient::RedisClient;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use redisclient::config::{ProtocolVersion, RedisConfig};
+use redisclient::hash_map;
+use redisclient::{cmd, RedisClient};
 
 #[test]
 pub fn test_echo() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     let resp = client.echo("Hello world").unwrap();
     assert_eq!(resp, "Hello world".to_string());
@@ -10,7 +15,94 @@ pub fn test_echo() {
 
 #[test]
 pub fn test_ping() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     let _ = client.ping().unwrap();
 }
+
+#[test]
+pub fn test_resp3() {
+    let config = RedisConfig {
+        protocol: ProtocolVersion::Resp3,
+        client_name: Some("resp3-client".to_string()),
+        ..RedisConfig::default()
+    };
+    let client = RedisClient::with_config(config).unwrap();
+
+    client.ping().unwrap();
+    client.hset("myhash", [("field1", 1)]).unwrap();
+    client.hset("myhash", [("field2", 2)]).unwrap();
+
+    let hash: HashMap<String, i64> = client.hgetall("myhash").unwrap();
+    assert_eq!(hash, hash_map! { "field1".to_string() => 1, "field2".to_string() => 2 });
+
+    client.flushall().unwrap();
+}
+
+#[test]
+#[ignore = "needs a server listening on /tmp/redis.sock"]
+pub fn test_unix_socket() {
+    let client = RedisClient::open("unix:///tmp/redis.sock").unwrap();
+
+    client.simple_set("key", "Hello").unwrap();
+    let value: String = client.get("key").unwrap();
+    assert_eq!(value, "Hello");
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_read_timeout() {
+    let config = RedisConfig {
+        read_timeout: Some(Duration::from_millis(200)),
+        ..RedisConfig::default()
+    };
+    let client = RedisClient::with_config(config).unwrap();
+
+    // a blocking command may wait longer than the read timeout
+    let value: Option<String> = client
+        .brpoplpush("nosuchlist", "otherlist", Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(value, None);
+
+    let err = cmd("DEBUG").arg("SLEEP").arg(1).query::<()>(&client).unwrap_err();
+    assert!(err.is_timeout());
+}
+
+#[test]
+pub fn test_connect_timeout() {
+    let config = RedisConfig {
+        // a non-routable address, the connection attempt hangs
+        address: "10.255.255.1:6379".to_string(),
+        connect_timeout: Some(Duration::from_millis(200)),
+        ..RedisConfig::default()
+    };
+    let client = RedisClient::with_config(config).unwrap();
+
+    let err = client.ping().unwrap_err();
+    assert!(err.is_timeout() || err.is_io_error());
+}
+
+#[test]
+pub fn test_select() {
+    let config = RedisConfig {
+        database: 3,
+        client_name: Some("worker".to_string()),
+        ..RedisConfig::default()
+    };
+    let client = RedisClient::with_config(config).unwrap();
+    client.simple_set("key", "Hello").unwrap();
+
+    let name: String = cmd("CLIENT").arg("GETNAME").query(&client).unwrap();
+    assert_eq!(name, "worker");
+
+    let other = RedisClient::new().unwrap();
+    let value: Option<String> = other.get("key").unwrap();
+    assert_eq!(value, None);
+
+    other.select(3).unwrap();
+    let value: Option<String> = other.get("key").unwrap();
+    assert_eq!(value, Some("Hello".to_string()));
+
+    client.flushall().unwrap();
+}
@@ -5,9 +5,9 @@ use std::collections::{BTreeMap, HashMap};
 
 #[test]
 pub fn test_hel() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
-    client.hset("myhash", "field1", "foo").unwrap();
+    client.hset("myhash", [("field1", "foo")]).unwrap();
 
     let res = client.hdel("myhash", vec!["field1"]).unwrap();
     assert_eq!(res, 1);
@@ -20,9 +20,9 @@ pub fn test_hel() {
 
 #[test]
 pub fn test_hexists() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
-    client.hset("myhash", "field1", "foo").unwrap();
+    client.hset("myhash", [("field1", "foo")]).unwrap();
     let exist = client.hexists("myhash", "field1").unwrap();
     assert_eq!(exist, true);
 
@@ -34,24 +34,24 @@ pub fn test_hexists() {
 
 #[test]
 pub fn test_hget() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
-    client.hset("myhash", "field1", "foo").unwrap();
+    client.hset("myhash", [("field1", "foo")]).unwrap();
 
     let value: String = client.hget("myhash", "field1").unwrap();
     assert_eq!(value, String::from("foo"));
 
-    let value: String = client.hget("myhash", "field2").unwrap();
-    assert_eq!(value, String::new());
+    let value: Option<String> = client.hget("myhash", "field2").unwrap();
+    assert_eq!(value, None);
 
     client.flushall().unwrap();
 }
 
 #[test]
 pub fn test_hgetall() {
-    let mut client = RedisClient::new().unwrap();
-    client.hset("myhash", "field1", "Hello").unwrap();
-    client.hset("myhash", "field2", "World").unwrap();
+    let client = RedisClient::new().unwrap();
+    client.hset("myhash", [("field1", "Hello")]).unwrap();
+    client.hset("myhash", [("field2", "World")]).unwrap();
 
     let hash: HashMap<String, String> = client.hgetall("myhash").unwrap();
 
@@ -63,6 +63,10 @@ pub fn test_hgetall() {
         }
     );
 
+    let pairs: Vec<(String, String)> = client.hgetall("myhash").unwrap();
+    assert_eq!(pairs.len(), 2);
+    assert!(pairs.contains(&("field1".to_string(), "Hello".to_string())));
+
     let map: BTreeMap<String, String> = client.hgetall("myhash").unwrap();
     assert_eq!(
         map,
@@ -77,8 +81,8 @@ pub fn test_hgetall() {
 
 #[test]
 pub fn test_hincrby() {
-    let mut client = RedisClient::new().unwrap();
-    client.hset("myhash", "field", 5).unwrap();
+    let client = RedisClient::new().unwrap();
+    client.hset("myhash", [("field", 5)]).unwrap();
 
     let value = client.hincrby("myhash", "field", 1).unwrap();
     assert_eq!(value, 6);
@@ -94,8 +98,8 @@ pub fn test_hincrby() {
 
 #[test]
 pub fn test_hincrbyfloat() {
-    let mut client = RedisClient::new().unwrap();
-    client.hset("mykey", "field", 10.50).unwrap();
+    let client = RedisClient::new().unwrap();
+    client.hset("mykey", [("field", 10.50)]).unwrap();
 
     let value = client.hincrbyfloat("mykey", "field", 0.1).unwrap();
     assert!((value - 10.6).abs() < f64::EPSILON);
@@ -103,7 +107,7 @@ pub fn test_hincrbyfloat() {
     let value = client.hincrbyfloat("mykey", "field", -5_f64).unwrap();
     assert!((value - 5.6).abs() < f64::EPSILON);
 
-    client.hset("mykey", "field", 5.0e3).unwrap();
+    client.hset("mykey", [("field", 5.0e3)]).unwrap();
     let value = client.hincrbyfloat("mykey", "field", 2.0e2).unwrap();
     assert!((value - 5200_f64).abs() < f64::EPSILON);
 
@@ -112,7 +116,7 @@ pub fn test_hincrbyfloat() {
 
 #[test]
 pub fn test_hkeys() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client
         .hmset("myhash", vec![("field1", "Hello"), ("field2", "World")])
@@ -126,7 +130,7 @@ pub fn test_hkeys() {
 
 #[test]
 pub fn test_hlen() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client
         .hmset("myhash", vec![("field1", "Hello"), ("field2", "World")])
@@ -141,22 +145,22 @@ pub fn test_hlen() {
 
 #[test]
 pub fn test_hmget() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client
         .hmset("myhash", vec![("field1", "Hello"), ("field2", "World")])
         .unwrap();
 
-    let values: Vec<String> = client.hmget("myhash", vec!["field1", "field2", "nofield"]).unwrap();
+    let values: Vec<Option<String>> = client.hmget("myhash", vec!["field1", "field2", "nofield"]).unwrap();
 
-    assert_eq!(values, vec!["Hello".to_string(), "World".to_string(), String::new()]);
+    assert_eq!(values, vec![Some("Hello".to_string()), Some("World".to_string()), None]);
 
     client.flushall().unwrap();
 }
 
 #[test]
 pub fn test_hmset() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client
         .hmset("myhash", vec![("field1", "Hello"), ("field2", "World")])
@@ -172,24 +176,62 @@ pub fn test_hmset() {
 }
 
 #[test]
-pub fn test_hscan() {}
+pub fn test_hscan() {
+    let client = RedisClient::new().unwrap();
+    let fields: Vec<(String, usize)> = (0..25).map(|i| (format!("field{}", i), i)).collect();
+    client.hset("hscan-hash", fields.clone()).unwrap();
+
+    let mut items = client
+        .hscan("hscan-hash")
+        .count(10)
+        .collect::<Result<Vec<(String, usize)>, _>>()
+        .unwrap();
+    items.sort();
+    items.dedup();
+    let mut expected = fields;
+    expected.sort();
+    assert_eq!(items, expected);
+
+    let items = client
+        .hscan_match("hscan-hash", "field1?")
+        .collect::<Result<HashMap<String, usize>, _>>()
+        .unwrap();
+    assert_eq!(items.len(), 10);
+    assert_eq!(items["field12"], 12);
+
+    client.flushall().unwrap();
+}
 
 #[test]
 pub fn test_hset() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
-    let amount = client.hset("myhash", "field1", "Hello").unwrap();
+    let amount = client.hset("myhash", [("field1", "Hello")]).unwrap();
     assert_eq!(amount, 1);
 
     let value: String = client.hget("myhash", "field1").unwrap();
     assert_eq!(value, String::from("Hello"));
 
+    let amount = client
+        .hset("myhash", hash_map! { "field1" => "Hi", "field2" => "World" })
+        .unwrap();
+    assert_eq!(amount, 1);
+
+    let hash: HashMap<String, String> = client.hgetall("myhash").unwrap();
+    assert_eq!(
+        hash,
+        hash_map! {
+            "field1".to_string() => "Hi".to_string(),
+            "field2".to_string() => "World".to_string(),
+        }
+    );
+
     client.flushall().unwrap();
 }
 
 #[test]
 pub fn test_hsetnx() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     let amount = client.hsetnx("myhash", "field", "Hello").unwrap();
     assert_eq!(amount, 1);
@@ -205,7 +247,7 @@ pub fn test_hsetnx() {
 
 #[test]
 pub fn test_hstrlen() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client
         .hmset("myhash", vec![("f1", "HelloWorld"), ("f2", "99"), ("f3", "-256")])
@@ -225,10 +267,10 @@ pub fn test_hstrlen() {
 
 #[test]
 pub fn test_hvals() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
-    client.hset("myhash", "field1", "Hello").unwrap();
-    client.hset("myhash", "field2", "World").unwrap();
+    client.hset("myhash", [("field1", "Hello")]).unwrap();
+    client.hset("myhash", [("field2", "World")]).unwrap();
 
     let values: Vec<String> = client.hvals("myhash").unwrap();
     assert_eq!(values, vec!["Hello".to_string(), "World".to_string()]);
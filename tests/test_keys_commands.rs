@@ -2,7 +2,7 @@ use redisclient::{DataType, RedisClient};
 
 #[test]
 pub fn test_del() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
     client.simple_set("key1", "Hello").unwrap();
     client.simple_set("key2", "World").unwrap();
 
@@ -10,12 +10,17 @@ pub fn test_del() {
 
     assert_eq!(amount, 2);
 
+    client.simple_set("key3", "!").unwrap();
+    let keys: &[&str] = &["key3", "key4"];
+    let amount = client.del(keys).unwrap();
+    assert_eq!(amount, 1);
+
     client.flushall().unwrap();
 }
 
 // #[test]
 // pub fn test_dump() {
-//     let mut client = RedisClient::new().unwrap();
+//     let client = RedisClient::new().unwrap();
 //
 //     client.simple_set("mykey", 10).unwrap();
 //     let value = client.dump("mykey").unwrap();
@@ -27,7 +32,7 @@ pub fn test_del() {
 
 #[test]
 pub fn test_exists() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
     client.simple_set("key1", "Hello").unwrap();
 
     let exist = client.exists(vec!["key1"]).unwrap();
@@ -46,7 +51,7 @@ pub fn test_exists() {
 
 #[test]
 pub fn test_expire() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.simple_set("mykey", "Hello").unwrap();
     assert!(client.expire("mykey", 10).unwrap());
@@ -66,7 +71,7 @@ pub fn test_expire() {
 
 #[test]
 pub fn test_keys() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client
         .mset(vec![("firstname", "Jack"), ("lastname", "Stuntman"), ("age", "35")])
@@ -91,7 +96,7 @@ pub fn test_keys() {
 
 #[test]
 pub fn test_persist() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.simple_set("mykey", "Hello").unwrap();
     client.expire("mykey", 10).unwrap();
@@ -109,7 +114,7 @@ pub fn test_persist() {
 #[test]
 #[ignore]
 pub fn test_pexpire() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.simple_set("mykey", "Hello").unwrap();
 
@@ -127,7 +132,7 @@ pub fn test_pexpire() {
 #[test]
 #[ignore]
 pub fn test_pttl() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.simple_set("mykey", "Hello").unwrap();
     client.expire("mykey", 1).unwrap();
@@ -140,7 +145,7 @@ pub fn test_pttl() {
 
 #[test]
 pub fn test_rename() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.simple_set("mykey", "Hello").unwrap();
     client.rename("mykey", "myotherkey").unwrap();
@@ -153,7 +158,7 @@ pub fn test_rename() {
 
 #[test]
 pub fn test_renamenx() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.simple_set("mykey", "Hello").unwrap();
     client.simple_set("myotherkey", "World").unwrap();
@@ -167,7 +172,7 @@ pub fn test_renamenx() {
 
 #[test]
 pub fn test_touch() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.simple_set("key1", "Hello").unwrap();
     client.simple_set("key2", "World").unwrap();
@@ -180,7 +185,7 @@ pub fn test_touch() {
 
 #[test]
 pub fn test_ttl() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
     client.simple_set("mykey", "Hello").unwrap();
     client.expire("mykey", 10).unwrap();
 
@@ -191,20 +196,25 @@ pub fn test_ttl() {
 
 #[test]
 pub fn test_type() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.simple_set("key1", "value").unwrap();
-    // client.lpush("key2", "value");
-    // client.sadd("key3", "value");
+    client.lpush("key2", vec!["value"]).unwrap();
+    client.hset("key3", [("field", "value")]).unwrap();
+    client.zadd("key4", vec![(1.0, "one")]).unwrap();
 
     assert_eq!(client.type_("key1").unwrap(), DataType::String);
+    assert_eq!(client.type_("key2").unwrap(), DataType::List);
+    assert_eq!(client.type_("key3").unwrap(), DataType::Hash);
+    assert_eq!(client.type_("key4").unwrap(), DataType::ZSet);
+    assert_eq!(client.type_("key5").unwrap(), DataType::Other("none".to_string()));
 
     client.flushall().unwrap();
 }
 
 #[test]
 pub fn test_unlink() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.simple_set("key1", "Hello").unwrap();
     client.simple_set("key2", "World").unwrap();
@@ -214,3 +224,33 @@ pub fn test_unlink() {
 
     client.flushall().unwrap();
 }
+
+#[test]
+pub fn test_scan() {
+    let client = RedisClient::new().unwrap();
+    for i in 0..25 {
+        client.simple_set(format!("scan:string:{}", i), i).unwrap();
+    }
+    client.hset("scan:hash", [("field", "value")]).unwrap();
+
+    let mut keys = client
+        .scan_match("scan:*")
+        .count(5)
+        .collect::<Result<Vec<String>, _>>()
+        .unwrap();
+    keys.sort();
+    keys.dedup();
+    assert_eq!(keys.len(), 26);
+
+    let keys = client
+        .scan_match("scan:*")
+        .type_(DataType::Hash)
+        .collect::<Result<Vec<String>, _>>()
+        .unwrap();
+    assert_eq!(keys, vec!["scan:hash".to_string()]);
+
+    let keys: Vec<String> = client.scan().map(Result::unwrap).collect();
+    assert!(keys.len() >= 26);
+
+    client.flushall().unwrap();
+}
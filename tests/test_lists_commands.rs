@@ -1,16 +1,205 @@
+use std::net::TcpListener;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use redisclient::client::LPosOptions;
 use redisclient::client::ListBeforeOrAfter::Before;
-use redisclient::RedisClient;
+use redisclient::client::ListDirection::{Left, Right};
+use redisclient::config::RedisConfig;
+use redisclient::{ErrorKind, RedisClient};
+
+fn push_later(key: &'static str, element: &'static str) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        RedisClient::new().unwrap().rpush(key, vec![element]).unwrap();
+    })
+}
+
+#[test]
+pub fn test_blpop() {
+    let client = RedisClient::new().unwrap();
+    client.rpush("blpop-list2", vec!["a", "b"]).unwrap();
+
+    let popped: Option<(String, String)> = client
+        .blpop(vec!["blpop-list1", "blpop-list2"], Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(popped, Some(("blpop-list2".to_string(), "a".to_string())));
+    let popped: Option<(Vec<u8>, String)> = client
+        .brpop(vec!["blpop-list1", "blpop-list2"], Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(popped, Some((b"blpop-list2".to_vec(), "b".to_string())));
+
+    let popped: Option<(String, String)> = client.blpop(vec!["blpop-list1"], Duration::from_millis(100)).unwrap();
+    assert_eq!(popped, None);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_blpop_longer_than_read_timeout() {
+    let client = RedisClient::with_config(RedisConfig {
+        read_timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let pusher = push_later("blpop-queue", "job");
+    let popped: Option<(String, String)> = client.blpop(vec!["blpop-queue"], Duration::from_secs(2)).unwrap();
+    assert_eq!(popped, Some(("blpop-queue".to_string(), "job".to_string())));
+    pusher.join().unwrap();
+
+    // waits forever with a zero timeout
+    let pusher = push_later("blpop-queue", "job");
+    let popped: Option<(String, String)> = client.brpop(vec!["blpop-queue"], Duration::ZERO).unwrap();
+    assert_eq!(popped, Some(("blpop-queue".to_string(), "job".to_string())));
+    pusher.join().unwrap();
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_blpop_with_read_timeout() {
+    // a server which never replies, the configured read timeout would wait forever
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        let mut streams = Vec::new();
+        for stream in listener.incoming() {
+            streams.push(stream.unwrap());
+        }
+    });
+    let client = RedisClient::with_config(RedisConfig {
+        address,
+        read_timeout: None,
+        ..Default::default()
+    })
+    .unwrap();
+
+    let start = Instant::now();
+    let err = client
+        .with_read_timeout(Some(Duration::from_millis(100)))
+        .blpop::<_, String, String, _>(vec!["blpop-unanswered"], Duration::from_millis(200))
+        .unwrap_err();
+    assert!(err.is_timeout());
+    assert!(start.elapsed() >= Duration::from_millis(300));
+}
+
+#[test]
+pub fn test_blpop_outside_pool_capacity() {
+    let client = RedisClient::with_config(RedisConfig {
+        pool_capacity: 1,
+        pool_checkout_timeout: Some(Duration::from_millis(100)),
+        ..Default::default()
+    })
+    .unwrap();
+
+    thread::scope(|scope| {
+        let popper = scope.spawn(|| {
+            let popped: Option<(String, String)> =
+                client.blpop(vec!["blpop-capacity"], Duration::from_secs(2)).unwrap();
+            popped
+        });
+        thread::sleep(Duration::from_millis(100));
+
+        // the waiting BLPOP doesn't hold the only connection of the pool
+        client.rpush("blpop-capacity", vec!["job"]).unwrap();
+        let popped = popper.join().unwrap();
+        assert_eq!(popped, Some(("blpop-capacity".to_string(), "job".to_string())));
+    });
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_blmove() {
+    let client = RedisClient::new().unwrap();
+    client.rpush("blmove-source", vec!["one", "two"]).unwrap();
+
+    let moved: Option<String> = client
+        .blmove(
+            "blmove-source",
+            "blmove-destination",
+            Right,
+            Left,
+            Duration::from_secs(1),
+        )
+        .unwrap();
+    assert_eq!(moved, Some("two".to_string()));
+    let moved: Option<String> = client
+        .blmove(
+            "blmove-source",
+            "blmove-destination",
+            Left,
+            Left,
+            Duration::from_secs(1),
+        )
+        .unwrap();
+    assert_eq!(moved, Some("one".to_string()));
+    let values: Vec<String> = client.lrange("blmove-destination", 0, -1).unwrap();
+    assert_eq!(values, vec!["one", "two"]);
+
+    let moved: Option<String> = client
+        .blmove(
+            "blmove-source",
+            "blmove-destination",
+            Left,
+            Left,
+            Duration::from_secs(1),
+        )
+        .unwrap();
+    assert_eq!(moved, None);
+
+    client.flushall().unwrap();
+}
 
 #[test]
-#[ignore]
 pub fn test_brpoplpush() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
+    client.rpush("brpoplpush-source", vec!["one", "two"]).unwrap();
+
+    let moved: String = client
+        .brpoplpush("brpoplpush-source", "brpoplpush-destination", Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(moved, "two");
+
+    let pusher = push_later("brpoplpush-empty", "three");
+    let moved: Option<String> = client
+        .brpoplpush("brpoplpush-empty", "brpoplpush-destination", Duration::ZERO)
+        .unwrap();
+    assert_eq!(moved, Some("three".to_string()));
+    pusher.join().unwrap();
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_lmpop() {
+    let client = RedisClient::new().unwrap();
+    client.rpush("lmpop-list2", vec!["a", "b", "c"]).unwrap();
+
+    let popped: Option<(String, Vec<String>)> = client.lmpop(vec!["lmpop-list1", "lmpop-list2"], Left, 2).unwrap();
+    assert_eq!(
+        popped,
+        Some(("lmpop-list2".to_string(), vec!["a".to_string(), "b".to_string()]))
+    );
+    let popped: Option<(String, Vec<String>)> = client
+        .blmpop(Duration::from_secs(1), vec!["lmpop-list2"], Right, 5)
+        .unwrap();
+    assert_eq!(popped, Some(("lmpop-list2".to_string(), vec!["c".to_string()])));
+
+    let popped: Option<(String, Vec<String>)> = client.lmpop(vec!["lmpop-list2"], Left, 1).unwrap();
+    assert_eq!(popped, None);
+    let popped: Option<(String, Vec<String>)> = client
+        .blmpop(Duration::from_secs(1), vec!["lmpop-list2"], Left, 1)
+        .unwrap();
+    assert_eq!(popped, None);
+
     client.flushall().unwrap();
 }
 
 #[test]
 pub fn test_lindex() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.lpush("mylist", vec!["World"]).unwrap();
     client.lpush("mylist", vec!["Hello"]).unwrap();
@@ -24,8 +213,12 @@ pub fn test_lindex() {
         "World".to_string()
     );
     assert_eq!(
-        client.lindex::<&'static str, String>("mylist", 3).unwrap(),
-        "".to_string()
+        client.lindex::<&'static str, Option<String>>("mylist", 3).unwrap(),
+        None
+    );
+    assert_eq!(
+        client.lindex::<&'static str, String>("mylist", 3).unwrap_err().kind(),
+        ErrorKind::NilReply
     );
 
     client.flushall().unwrap();
@@ -33,7 +226,7 @@ pub fn test_lindex() {
 
 #[test]
 pub fn test_linsert() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.rpush("mylist", vec!["Hello"]).unwrap();
     client.rpush("mylist", vec!["World"]).unwrap();
@@ -51,7 +244,7 @@ pub fn test_linsert() {
 
 #[test]
 pub fn test_llen() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.lpush("mylist", vec!["World"]).unwrap();
     client.lpush("mylist", vec!["Hello"]).unwrap();
@@ -61,9 +254,30 @@ pub fn test_llen() {
     client.flushall().unwrap();
 }
 
+#[test]
+pub fn test_lmove() {
+    let client = RedisClient::new().unwrap();
+    client.rpush("lmove-source", vec!["one", "two", "three"]).unwrap();
+
+    let moved: Option<String> = client.lmove("lmove-source", "lmove-destination", Right, Left).unwrap();
+    assert_eq!(moved, Some("three".to_string()));
+    let moved: Option<String> = client.lmove("lmove-source", "lmove-destination", Left, Right).unwrap();
+    assert_eq!(moved, Some("one".to_string()));
+
+    let values: Vec<String> = client.lrange("lmove-source", 0, -1).unwrap();
+    assert_eq!(values, vec!["two"]);
+    let values: Vec<String> = client.lrange("lmove-destination", 0, -1).unwrap();
+    assert_eq!(values, vec!["three", "one"]);
+
+    let moved: Option<String> = client.lmove("lmove-missing", "lmove-destination", Left, Left).unwrap();
+    assert_eq!(moved, None);
+
+    client.flushall().unwrap();
+}
+
 #[test]
 pub fn test_lpop() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.rpush("mylist", vec!["one", "two", "three"]).unwrap();
     let element: String = client.lpop("mylist").unwrap();
@@ -75,9 +289,70 @@ pub fn test_lpop() {
     client.flushall().unwrap();
 }
 
+#[test]
+pub fn test_lpop_count() {
+    let client = RedisClient::new().unwrap();
+
+    client.rpush("lpop-count", vec!["one", "two", "three"]).unwrap();
+    let elements: Vec<String> = client.lpop_count("lpop-count", 2).unwrap();
+    assert_eq!(elements, vec!["one", "two"]);
+    let elements: Vec<String> = client.lpop_count("lpop-count", 2).unwrap();
+    assert_eq!(elements, vec!["three"]);
+    let elements: Vec<String> = client.lpop_count("lpop-count", 2).unwrap();
+    assert!(elements.is_empty());
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_lpos() {
+    let client = RedisClient::new().unwrap();
+    client
+        .rpush("lpos-list", vec!["a", "b", "c", "1", "2", "3", "c", "c"])
+        .unwrap();
+
+    assert_eq!(client.lpos("lpos-list", "c", LPosOptions::new()).unwrap(), Some(2));
+    assert_eq!(
+        client.lpos("lpos-list", "c", LPosOptions::new().rank(2)).unwrap(),
+        Some(6)
+    );
+    assert_eq!(
+        client.lpos("lpos-list", "c", LPosOptions::new().rank(-1)).unwrap(),
+        Some(7)
+    );
+    assert_eq!(client.lpos("lpos-list", "z", LPosOptions::new()).unwrap(), None);
+    assert_eq!(
+        client.lpos("lpos-list", "c", LPosOptions::new().maxlen(2)).unwrap(),
+        None
+    );
+
+    assert_eq!(
+        client.lpos_count("lpos-list", "c", 2, LPosOptions::new()).unwrap(),
+        vec![2, 6]
+    );
+    assert_eq!(
+        client
+            .lpos_count("lpos-list", "c", 0, LPosOptions::new().rank(-1))
+            .unwrap(),
+        vec![7, 6, 2]
+    );
+    assert_eq!(
+        client
+            .lpos_count("lpos-list", "c", 0, LPosOptions::new().maxlen(7))
+            .unwrap(),
+        vec![2, 6]
+    );
+    assert!(client
+        .lpos_count("lpos-list", "z", 0, LPosOptions::new())
+        .unwrap()
+        .is_empty());
+
+    client.flushall().unwrap();
+}
+
 #[test]
 pub fn test_lpush() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.lpush("mylist", vec!["world", "hello"]).unwrap();
     let elements: Vec<String> = client.lrange("mylist", 0, -1).unwrap();
@@ -89,7 +364,7 @@ pub fn test_lpush() {
 
 #[test]
 pub fn test_lpushx() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.lpush("mylist", vec!["World"]).unwrap();
 
@@ -111,7 +386,7 @@ pub fn test_lpushx() {
 
 #[test]
 pub fn test_lrange() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
     client.rpush("mylist", vec!["one", "two", "three"]).unwrap();
 
     let elements: Vec<String> = client.lrange("mylist", 0, 0).unwrap();
@@ -126,7 +401,7 @@ pub fn test_lrange() {
 
 #[test]
 pub fn test_lrem() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
     client.rpush("mylist", vec!["hello", "hello", "foo", "hello"]).unwrap();
 
     let amount = client.lrem("mylist", -2, "hello").unwrap();
@@ -140,7 +415,7 @@ pub fn test_lrem() {
 
 #[test]
 pub fn test_lset() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
     client.rpush("mylist", vec!["onw", "two", "three"]).unwrap();
     client.lset("mylist", 0, "four").unwrap();
     client.lset("mylist", -2, "five").unwrap();
@@ -156,7 +431,7 @@ pub fn test_lset() {
 
 #[test]
 pub fn test_ltrim() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.rpush("mylist", vec!["one", "two", "three"]).unwrap();
     client.ltrim("mylist", 1, -1).unwrap();
@@ -169,7 +444,7 @@ pub fn test_ltrim() {
 
 #[test]
 pub fn test_rpop() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.rpush("mylist", vec!["one", "two", "three"]).unwrap();
     let element: String = client.rpop("mylist").unwrap();
@@ -181,9 +456,22 @@ pub fn test_rpop() {
     client.flushall().unwrap();
 }
 
+#[test]
+pub fn test_rpop_count() {
+    let client = RedisClient::new().unwrap();
+
+    client.rpush("rpop-count", vec!["one", "two", "three"]).unwrap();
+    let elements: Vec<String> = client.rpop_count("rpop-count", 2).unwrap();
+    assert_eq!(elements, vec!["three", "two"]);
+    let elements: Vec<String> = client.rpop_count("rpop-missing", 2).unwrap();
+    assert!(elements.is_empty());
+
+    client.flushall().unwrap();
+}
+
 #[test]
 pub fn test_rpoplpush() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.rpush("mylist", vec!["one", "two", "three"]).unwrap();
     let element: String = client.rpoplpush("mylist", "myotherlist").unwrap();
@@ -200,7 +488,7 @@ pub fn test_rpoplpush() {
 
 #[test]
 pub fn test_rpush() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.rpush("mylist", vec!["Hello", "World"]).unwrap();
     let elements: Vec<String> = client.lrange("mylist", 0, -1).unwrap();
@@ -212,7 +500,7 @@ pub fn test_rpush() {
 
 #[test]
 pub fn test_rpushx() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.rpush("mylist", vec!["Hello"]).unwrap();
     client.rpushx("mylist", vec!["World"]).unwrap();
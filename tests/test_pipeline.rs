@@ -0,0 +1,84 @@
+use redisclient::pipeline::Pipeline;
+use redisclient::{ErrorKind, RedisClient};
+
+#[test]
+pub fn test_pipeline_query_tuple() {
+    let client = RedisClient::new().unwrap();
+
+    let (_, _, value, length): ((), usize, String, usize) = client
+        .pipe()
+        .simple_set("pipe_key", "Hello")
+        .hset("pipe_hash", [("field", 1)])
+        .get("pipe_key")
+        .hlen("pipe_hash")
+        .query(&client)
+        .unwrap();
+    assert_eq!(value, "Hello".to_string());
+    assert_eq!(length, 1);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_pipeline_query_vec() {
+    let client = RedisClient::new().unwrap();
+
+    let mut pipe = Pipeline::with_capacity(100);
+    for i in 0..100 {
+        pipe.incr("pipe_counter");
+        pipe.hset("pipe_hash", [(i, i)]);
+    }
+    let replies: Vec<i64> = pipe.query(&client).unwrap();
+    assert_eq!(replies.len(), 200);
+    assert_eq!(replies[198], 100);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_pipeline_error_keeps_connection_usable() {
+    let client = RedisClient::new().unwrap();
+
+    client.simple_set("pipe_key", "Hello").unwrap();
+    let result: Result<(i64, String), _> = client.pipe().incr("pipe_key").get("pipe_key").query(&client);
+    assert!(result.is_err());
+
+    assert_eq!(client.get::<_, String>("pipe_key").unwrap(), "Hello".to_string());
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_pipeline_transaction() {
+    let client = RedisClient::new().unwrap();
+
+    let (first, second): (i64, i64) = client
+        .pipe()
+        .transaction_mode()
+        .incrby("pipe_counter", 5)
+        .incrby("pipe_counter", 5)
+        .query(&client)
+        .unwrap();
+    assert_eq!(first, 5);
+    assert_eq!(second, 10);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_pipeline_transaction_execabort() {
+    let client = RedisClient::new().unwrap();
+
+    let err = client
+        .pipe()
+        .transaction_mode()
+        .incr("pipe_counter")
+        .del(Vec::<&str>::new())
+        .query::<(i64, usize)>(&client)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ExecAbort);
+
+    assert_eq!(client.exists(vec!["pipe_counter"]).unwrap(), 0);
+
+    client.flushall().unwrap();
+}
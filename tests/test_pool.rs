@@ -0,0 +1,181 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use redisclient::config::RedisConfig;
+use redisclient::{cmd, ErrorKind, RedisClient};
+
+#[test]
+pub fn test_client_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<RedisClient>();
+}
+
+#[test]
+pub fn test_share_client_between_threads() {
+    let client = Arc::new(RedisClient::new().unwrap());
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let client = client.clone();
+            thread::spawn(move || {
+                for _ in 0..10 {
+                    client.incr(format!("shared-counter-{}", i)).unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    for i in 0..4 {
+        let value: i64 = client.get(format!("shared-counter-{}", i)).unwrap();
+        assert_eq!(value, 10);
+    }
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_pool_checkout_timeout() {
+    let config = RedisConfig {
+        pool_capacity: 1,
+        pool_checkout_timeout: Some(Duration::from_millis(100)),
+        ..Default::default()
+    };
+    let client = RedisClient::with_config(config).unwrap();
+
+    let pinned = client.pin().unwrap();
+    pinned.simple_set("pool-key", "value").unwrap();
+
+    let err = client.get::<_, String>("pool-key").err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::PoolTimeout);
+    assert!(err.is_timeout());
+
+    drop(pinned);
+    let value: String = client.get("pool-key").unwrap();
+    assert_eq!(value, "value");
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_blocking_pool_capacity() {
+    let client = RedisClient::with_config(RedisConfig {
+        blocking_pool_capacity: 1,
+        pool_checkout_timeout: Some(Duration::from_millis(100)),
+        ..Default::default()
+    })
+    .unwrap();
+
+    thread::scope(|scope| {
+        let popper = scope.spawn(|| {
+            let popped: Option<(String, String)> = client.blpop(vec!["pool-blocking"], Duration::from_secs(2)).unwrap();
+            popped
+        });
+        thread::sleep(Duration::from_millis(100));
+
+        // the only blocking connection is waiting, other commands still have theirs
+        let err = client
+            .blpop::<_, String, String, _>(vec!["pool-blocking"], Duration::from_secs(1))
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::PoolTimeout);
+        client.rpush("pool-blocking", vec!["job"]).unwrap();
+        let popped = popper.join().unwrap();
+        assert_eq!(popped, Some(("pool-blocking".to_string(), "job".to_string())));
+    });
+
+    // it's back in the pool
+    let popped: Option<(String, String)> = client.blpop(vec!["pool-blocking"], Duration::from_millis(100)).unwrap();
+    assert_eq!(popped, None);
+
+    client.flushall().unwrap();
+}
+
+fn client_id(client: &RedisClient) -> i64 {
+    cmd("CLIENT").arg("ID").query(client).unwrap()
+}
+
+fn single_connection_client(config: RedisConfig) -> RedisClient {
+    RedisClient::with_config(RedisConfig {
+        pool_capacity: 1,
+        ..config
+    })
+    .unwrap()
+}
+
+#[test]
+pub fn test_error_reply_keeps_connection() {
+    let client = single_connection_client(RedisConfig::default());
+    let id = client_id(&client);
+
+    let err = cmd("CLIENT")
+        .arg("NO-SUCH-SUBCOMMAND")
+        .query::<()>(&client)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::FromServer);
+    assert_eq!(client_id(&client), id);
+}
+
+#[test]
+pub fn test_io_error_discards_connection() {
+    let client = single_connection_client(RedisConfig {
+        read_timeout: Some(Duration::from_millis(100)),
+        ..Default::default()
+    });
+    let id = client_id(&client);
+
+    let err = cmd("DEBUG").arg("SLEEP").arg(0.3).query::<()>(&client).err().unwrap();
+    assert!(err.is_unrecoverable());
+    // DEBUG SLEEP blocks the whole server
+    thread::sleep(Duration::from_millis(300));
+
+    // the late reply of DEBUG SLEEP must not be read as the reply of the next command
+    assert_ne!(client_id(&client), id);
+    client.ping().unwrap();
+}
+
+#[test]
+pub fn test_pool_max_lifetime() {
+    let client = single_connection_client(RedisConfig {
+        pool_max_lifetime: Some(Duration::from_millis(50)),
+        ..Default::default()
+    });
+    let id = client_id(&client);
+    assert_eq!(client_id(&client), id);
+
+    thread::sleep(Duration::from_millis(100));
+    assert_ne!(client_id(&client), id);
+}
+
+#[test]
+pub fn test_pool_idle_timeout() {
+    let client = single_connection_client(RedisConfig {
+        pool_idle_timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    });
+    let id = client_id(&client);
+    assert_eq!(client_id(&client), id);
+
+    thread::sleep(Duration::from_millis(100));
+    assert_ne!(client_id(&client), id);
+}
+
+#[test]
+pub fn test_pool_health_check() {
+    let client = single_connection_client(RedisConfig {
+        pool_health_check_after: Some(Duration::from_millis(1)),
+        ..Default::default()
+    });
+    let other = RedisClient::new().unwrap();
+    let id = client_id(&client);
+
+    let killed: i64 = cmd("CLIENT").arg("KILL").arg("ID").arg(id).query(&other).unwrap();
+    assert_eq!(killed, 1);
+    thread::sleep(Duration::from_millis(10));
+
+    assert_ne!(client_id(&client), id);
+}
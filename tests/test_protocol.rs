@@ -1,15 +1,81 @@
-use redisclient::connection::Reply;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use redisclient::connection::{parse_reply, Reply};
 use redisclient::protocol::{RedisDeserializationProtocol, RedisSerializationProtocol};
+use redisclient::streams::{StreamEntry, StreamId, StreamKey, XReadOptions};
+use redisclient::{hash_map, hash_set, DataType, ErrorKind};
+
+#[test]
+pub fn test_vector_serialization() {
+    let data = b"Hello world".to_vec();
+
+    let got = data.serialization();
+
+    let expected = Vec::from("$11\r\nHello world\r\n");
+    assert_eq!(expected, got);
+}
+
+#[test]
+pub fn test_collection_serialization() {
+    let data = vec!["foo", "bar"];
+    assert_eq!(b"$3\r\nfoo\r\n$3\r\nbar\r\n".to_vec(), data.serialization());
+    assert_eq!(2, data.count());
+
+    let data = hash_map! { "field" => 1 };
+    assert_eq!(b"$5\r\nfield\r\n$1\r\n1\r\n".to_vec(), data.serialization());
+    assert_eq!(2, data.count());
+
+    let data = hash_set! { "member" };
+    assert_eq!(b"$6\r\nmember\r\n".to_vec(), data.serialization());
+    assert_eq!(1, data.count());
+
+    let data = [("a", 1), ("b", 2)];
+    assert_eq!(4, data.count());
+
+    let data: Vec<String> = Vec::new();
+    assert_eq!(0, data.count());
+    // an empty byte string is still one argument
+    assert_eq!(1, Vec::<u8>::new().count());
+}
+
+#[test]
+pub fn test_bytes_serialization() {
+    let expected = b"$3\r\n\xff\x00\r\r\n".to_vec();
+
+    let data: &[u8] = &[0xff, 0x00, b'\r'];
+    assert_eq!(expected, data.serialization());
+    assert_eq!(expected, [0xffu8, 0x00, b'\r'].serialization());
+}
+
+#[test]
+pub fn test_bytes_deserialization() {
+    let reply = Reply::BulkStrings(vec![0xff, 0x00, 0xfe]);
+
+    assert_eq!(
+        <Vec<u8>>::deserialization(reply.clone()).unwrap(),
+        vec![0xff, 0x00, 0xfe]
+    );
+    assert_eq!(<[u8; 3]>::deserialization(reply.clone()).unwrap(), [0xff, 0x00, 0xfe]);
+    assert!(<[u8; 4]>::deserialization(reply.clone()).is_err());
+    assert!(<String>::deserialization(reply).is_err());
 
-// #[test]
-// pub fn test_vector_serialization() {
-//     let data = b"Hello world".to_vec();
-//
-//     let got = data.serialization();
-//
-//     let expected = Vec::from("$11\r\nHello world\r\n");
-//     assert_eq!(expected, got);
-// }
+    // an array of integers is still a sequence of numbers
+    let reply = Reply::Arrays(vec![Reply::Integers(b"1".to_vec()), Reply::Integers(b"2".to_vec())]);
+    assert_eq!(<Vec<u8>>::deserialization(reply).unwrap(), vec![1, 2]);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+pub fn test_bytes_crate() {
+    let data = bytes::Bytes::from_static(b"\xffHello");
+
+    assert_eq!(b"$6\r\n\xffHello\r\n".to_vec(), data.serialization());
+    assert_eq!(
+        <bytes::Bytes>::deserialization(Reply::BulkStrings(data.to_vec())).unwrap(),
+        data
+    );
+}
 
 #[test]
 pub fn test_string_serialization() {
@@ -72,3 +138,318 @@ pub fn test_f64_serialization() {
     let expected = Vec::from("$5\r\n-1.23\r\n");
     assert_eq!(expected, got);
 }
+
+#[test]
+pub fn test_parse_nested_arrays() {
+    let mut data: &[u8] = b"*2\r\n$1\r\n0\r\n*2\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n";
+
+    let got = parse_reply(&mut data).unwrap();
+
+    let expected = Reply::Arrays(vec![
+        Reply::BulkStrings(b"0".to_vec()),
+        Reply::Arrays(vec![
+            Reply::BulkStrings(b"key1".to_vec()),
+            Reply::BulkStrings(b"key2".to_vec()),
+        ]),
+    ]);
+    assert_eq!(expected, got);
+    assert!(data.is_empty());
+}
+
+#[test]
+pub fn test_parse_mixed_array() {
+    let mut data: &[u8] = b"*5\r\n:1\r\n$-1\r\n*-1\r\n-ERR wrong\r\n*0\r\n";
+
+    let got = parse_reply(&mut data).unwrap();
+
+    let expected = Reply::Arrays(vec![
+        Reply::Integers(b"1".to_vec()),
+        Reply::Nil,
+        Reply::Nil,
+        Reply::Errors("ERR wrong".to_string()),
+        Reply::Arrays(vec![]),
+    ]);
+    assert_eq!(expected, got);
+}
+
+#[test]
+pub fn test_parse_null_array() {
+    let mut data: &[u8] = b"*-1\r\n";
+
+    let got = parse_reply(&mut data).unwrap();
+
+    assert_eq!(Reply::Nil, got);
+}
+
+#[test]
+pub fn test_parse_incomplete_reply() {
+    let mut data: &[u8] = b"*2\r\n$3\r\nfoo\r\n$3\r\nba";
+
+    let got = parse_reply(&mut data).unwrap_err();
+
+    assert_eq!(
+        got.as_io_error().map(|e| e.kind()),
+        Some(std::io::ErrorKind::UnexpectedEof)
+    );
+}
+
+#[test]
+pub fn test_parse_oversized_lengths() {
+    let mut data: &[u8] = b"$536870913\r\nfoo\r\n";
+    let got = parse_reply(&mut data).unwrap_err();
+    assert_eq!(got.kind(), ErrorKind::ResponseError);
+
+    // the announced length is not trusted for the allocation
+    let mut data: &[u8] = b"*9223372036854775807\r\n:1\r\n";
+    let got = parse_reply(&mut data).unwrap_err();
+    assert_eq!(
+        got.as_io_error().map(|e| e.kind()),
+        Some(std::io::ErrorKind::UnexpectedEof)
+    );
+
+    let mut data: &[u8] = b"%9223372036854775807\r\n";
+    assert!(parse_reply(&mut data).is_err());
+}
+
+#[test]
+pub fn test_error_reply_into_result() {
+    let reply = Reply::Errors("EXECABORT Transaction discarded because of previous errors.".to_string());
+
+    let got = reply.into_result().unwrap_err();
+
+    assert_eq!(redisclient::ErrorKind::ExecAbort, got.kind());
+}
+
+#[test]
+pub fn test_parse_resp3_types() {
+    let mut data: &[u8] =
+        b"*7\r\n_\r\n,2.5\r\n,-inf\r\n#t\r\n(3492890328409238509324850943850943825024385\r\n=15\r\ntxt:Some string\r\n!10\r\nERR failed\r\n";
+
+    let got = parse_reply(&mut data).unwrap();
+
+    let expected = Reply::Arrays(vec![
+        Reply::Null,
+        Reply::Double(2.5),
+        Reply::Double(f64::NEG_INFINITY),
+        Reply::Boolean(true),
+        Reply::BigNumber(b"3492890328409238509324850943850943825024385".to_vec()),
+        Reply::VerbatimString {
+            format: "txt".to_string(),
+            text: b"Some string".to_vec(),
+        },
+        Reply::Errors("ERR failed".to_string()),
+    ]);
+    assert_eq!(expected, got);
+}
+
+#[test]
+pub fn test_parse_resp3_aggregates() {
+    let mut data: &[u8] =
+        b"|1\r\n$3\r\nttl\r\n:3600\r\n%2\r\n$5\r\nfirst\r\n:1\r\n$6\r\nsecond\r\n~2\r\n$1\r\na\r\n$1\r\nb\r\n";
+
+    let got = parse_reply(&mut data).unwrap();
+
+    let expected = Reply::Attribute {
+        attributes: vec![(Reply::BulkStrings(b"ttl".to_vec()), Reply::Integers(b"3600".to_vec()))],
+        reply: Box::new(Reply::Map(vec![
+            (Reply::BulkStrings(b"first".to_vec()), Reply::Integers(b"1".to_vec())),
+            (
+                Reply::BulkStrings(b"second".to_vec()),
+                Reply::Set(vec![
+                    Reply::BulkStrings(b"a".to_vec()),
+                    Reply::BulkStrings(b"b".to_vec()),
+                ]),
+            ),
+        ])),
+    };
+    assert_eq!(expected, got);
+}
+
+#[test]
+pub fn test_map_deserialization() {
+    let reply = Reply::Map(vec![
+        (
+            Reply::BulkStrings(b"field1".to_vec()),
+            Reply::BulkStrings(b"1".to_vec()),
+        ),
+        (Reply::BulkStrings(b"field2".to_vec()), Reply::Integers(b"2".to_vec())),
+    ]);
+
+    let got = <HashMap<String, u64>>::deserialization(reply).unwrap();
+
+    let expected = hash_map! { "field1".to_string() => 1, "field2".to_string() => 2 };
+    assert_eq!(expected, got);
+}
+
+#[test]
+pub fn test_resp3_scalars_deserialization() {
+    assert_eq!(<f64>::deserialization(Reply::Double(1.5)).unwrap(), 1.5);
+    assert!(<bool>::deserialization(Reply::Boolean(true)).unwrap());
+    assert_eq!(
+        <HashSet<String>>::deserialization(Reply::Set(vec![Reply::BulkStrings(b"a".to_vec())])).unwrap(),
+        hash_set! { "a".to_string() }
+    );
+}
+
+#[test]
+pub fn test_parse_status() {
+    let mut data: &[u8] = b"+QUEUED\r\n";
+
+    let got = parse_reply(&mut data).unwrap();
+
+    assert_eq!(Reply::Status(b"QUEUED".to_vec()), got);
+}
+
+#[test]
+pub fn test_data_type_deserialization() {
+    let reply = Reply::Status(b"ReJSON-RL".to_vec());
+
+    let got = <DataType>::deserialization(reply).unwrap();
+
+    assert_eq!(DataType::Other("ReJSON-RL".to_string()), got);
+}
+
+#[test]
+pub fn test_option_deserialization() {
+    assert_eq!(<Option<String>>::deserialization(Reply::Nil).unwrap(), None);
+    assert_eq!(<Option<u64>>::deserialization(Reply::Null).unwrap(), None);
+    assert_eq!(
+        <Option<String>>::deserialization(Reply::BulkStrings(b"foo".to_vec())).unwrap(),
+        Some("foo".to_string())
+    );
+}
+
+#[test]
+pub fn test_nil_deserialization() {
+    assert_eq!(
+        <String>::deserialization(Reply::Nil).unwrap_err().kind(),
+        ErrorKind::NilReply
+    );
+    assert_eq!(
+        <u64>::deserialization(Reply::Null).unwrap_err().kind(),
+        ErrorKind::NilReply
+    );
+    assert_eq!(
+        <u64>::deserialization(Reply::Status(b"OK".to_vec()))
+            .unwrap_err()
+            .kind(),
+        ErrorKind::TypeError
+    );
+}
+
+#[test]
+pub fn test_flat_pairs_deserialization() {
+    let reply = Reply::Arrays(vec![
+        Reply::BulkStrings(b"one".to_vec()),
+        Reply::BulkStrings(b"1".to_vec()),
+        Reply::BulkStrings(b"two".to_vec()),
+        Reply::BulkStrings(b"2.5".to_vec()),
+    ]);
+
+    let got = <Vec<(String, f64)>>::deserialization(reply.clone()).unwrap();
+    assert_eq!(got, vec![("one".to_string(), 1.0), ("two".to_string(), 2.5)]);
+
+    assert!(<Vec<(String, f64, String)>>::deserialization(reply).is_err());
+}
+
+#[test]
+pub fn test_nested_pairs_deserialization() {
+    let reply = Reply::Arrays(vec![
+        Reply::Arrays(vec![Reply::BulkStrings(b"one".to_vec()), Reply::Double(1.0)]),
+        Reply::Arrays(vec![Reply::BulkStrings(b"two".to_vec()), Reply::Double(2.5)]),
+    ]);
+    let got = <Vec<(String, f64)>>::deserialization(reply).unwrap();
+    assert_eq!(got, vec![("one".to_string(), 1.0), ("two".to_string(), 2.5)]);
+
+    let reply = Reply::Map(vec![(
+        Reply::BulkStrings(b"field".to_vec()),
+        Reply::BulkStrings(b"value".to_vec()),
+    )]);
+    let got = <Vec<(String, String)>>::deserialization(reply).unwrap();
+    assert_eq!(got, vec![("field".to_string(), "value".to_string())]);
+}
+
+#[test]
+pub fn test_nested_containers_deserialization() {
+    // e.g. a SCAN reply
+    let reply = Reply::Arrays(vec![
+        Reply::BulkStrings(b"17".to_vec()),
+        Reply::Arrays(vec![
+            Reply::BulkStrings(b"key:1".to_vec()),
+            Reply::BulkStrings(b"key:2".to_vec()),
+        ]),
+    ]);
+    let got = <(u64, Vec<String>)>::deserialization(reply).unwrap();
+    assert_eq!(got, (17, vec!["key:1".to_string(), "key:2".to_string()]));
+
+    let reply = Reply::Arrays(vec![
+        Reply::Arrays(vec![Reply::Integers(b"1".to_vec()), Reply::Integers(b"2".to_vec())]),
+        Reply::Arrays(vec![]),
+    ]);
+    let got = <Vec<Vec<u64>>>::deserialization(reply).unwrap();
+    assert_eq!(got, vec![vec![1, 2], vec![]]);
+
+    let reply = Reply::Arrays(vec![Reply::BulkStrings(b"Hello".to_vec()), Reply::Nil]);
+    let got = <Vec<Option<String>>>::deserialization(reply).unwrap();
+    assert_eq!(got, vec![Some("Hello".to_string()), None]);
+}
+
+#[test]
+pub fn test_stream_deserialization() {
+    let id: StreamId = "1526919030474-55".parse().unwrap();
+    assert_eq!(id, StreamId::new(1526919030474, 55));
+    assert_eq!(id.to_string(), "1526919030474-55");
+    assert_eq!(
+        "1526919030474".parse::<StreamId>().unwrap(),
+        StreamId::new(1526919030474, 0)
+    );
+    assert!("1526919030474-x".parse::<StreamId>().is_err());
+
+    // RESP2 nests the streams in arrays, RESP3 replies with a map
+    let resp2 = b"*1\r\n*2\r\n$6\r\nstream\r\n*1\r\n*2\r\n$3\r\n1-0\r\n*2\r\n$1\r\nn\r\n$1\r\n1\r\n";
+    let resp3 = b"%1\r\n$6\r\nstream\r\n*1\r\n*2\r\n$3\r\n1-0\r\n*2\r\n$1\r\nn\r\n$1\r\n1\r\n";
+    for data in [&resp2[..], &resp3[..]] {
+        let reply = parse_reply(&mut &data[..]).unwrap();
+        let streams = <Vec<StreamKey>>::deserialization(reply).unwrap();
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].key, "stream");
+        assert_eq!(streams[0].entries[0].id, StreamId::new(1, 0));
+        assert_eq!(streams[0].entries[0].get("n").unwrap(), "1");
+    }
+
+    // the fields keep their order and duplicates, and binary values decode as bytes
+    let data = b"*2\r\n$3\r\n1-0\r\n*6\r\n$1\r\nb\r\n$1\r\n\xff\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n";
+    let reply = parse_reply(&mut &data[..]).unwrap();
+    let entry = <StreamEntry<Vec<u8>>>::deserialization(reply).unwrap();
+    assert_eq!(
+        entry.fields,
+        vec![
+            ("b".to_string(), b"\xff".to_vec()),
+            ("a".to_string(), b"1".to_vec()),
+            ("b".to_string(), b"2".to_vec()),
+        ]
+    );
+    assert_eq!(entry.get("b").unwrap(), b"\xff");
+}
+
+#[test]
+pub fn test_xread_options_serialization() {
+    let options = XReadOptions::new().count(10).block(Duration::from_millis(1500));
+    assert_eq!(
+        options.serialization(),
+        b"$5\r\nCOUNT\r\n$2\r\n10\r\n$5\r\nBLOCK\r\n$4\r\n1500\r\n".to_vec()
+    );
+    assert_eq!(RedisSerializationProtocol::count(&options), 4);
+
+    // zero blocks forever, any other timeout is rounded up to at least a millisecond
+    let block = |timeout| XReadOptions::new().block(timeout).serialization();
+    assert_eq!(block(Duration::ZERO), b"$5\r\nBLOCK\r\n$1\r\n0\r\n".to_vec());
+    assert_eq!(block(Duration::from_micros(1)), b"$5\r\nBLOCK\r\n$1\r\n1\r\n".to_vec());
+    assert_eq!(
+        block(Duration::from_micros(2500)),
+        b"$5\r\nBLOCK\r\n$1\r\n3\r\n".to_vec()
+    );
+
+    let options = XReadOptions::new().noack();
+    assert_eq!(options.serialization(), b"$5\r\nNOACK\r\n".to_vec());
+}
@@ -0,0 +1,108 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+use redisclient::config::{RedisConfig, RetryPolicy};
+use redisclient::sorted_sets::ZAddOptions;
+use redisclient::{cmd, ErrorKind, RedisClient};
+
+fn retrying_client(address: String) -> RedisClient {
+    RedisClient::with_config(RedisConfig {
+        address,
+        retry: RetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+// A server answering `+PONG` to every command, except for the first `drops` connections
+// which are closed once a command is received.
+fn flaky_server(listener: TcpListener, drops: usize) {
+    thread::spawn(move || {
+        for (i, stream) in listener.incoming().enumerate() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0; 1024];
+            while let Ok(n) = stream.read(&mut buf) {
+                if n == 0 || i < drops {
+                    break;
+                }
+                stream.write_all(b"+PONG\r\n").unwrap();
+            }
+        }
+    });
+}
+
+#[test]
+pub fn test_retry_refused_connection() {
+    // nothing listens on the port until the server starts, like during a failover
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    drop(listener);
+
+    let client = retrying_client(address.clone());
+    let server = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        flaky_server(TcpListener::bind(address).unwrap(), 0);
+    });
+
+    // not idempotent, but it couldn't be sent before the server started
+    let reply: String = cmd("INCR").arg("key").query(&client).unwrap();
+    assert_eq!(reply, "PONG");
+    server.join().unwrap();
+}
+
+#[test]
+pub fn test_retry_dropped_connection() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = retrying_client(listener.local_addr().unwrap().to_string());
+    flaky_server(listener, 2);
+
+    client.ping().unwrap();
+}
+
+#[test]
+pub fn test_no_retry_once_sent() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = retrying_client(listener.local_addr().unwrap().to_string());
+    flaky_server(listener, 1);
+
+    let err = cmd("PING").non_retryable().query::<String>(&client).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::Io);
+    let reply: String = cmd("INCR").arg("key").query(&client).unwrap();
+    assert_eq!(reply, "PONG");
+}
+
+#[test]
+pub fn test_no_retry_of_non_idempotent_options() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = retrying_client(listener.local_addr().unwrap().to_string());
+    flaky_server(listener, 1);
+
+    // ZADD is idempotent, unless INCR makes it add to the score
+    let err = client
+        .zadd_incr("myzset", 1.0, "one", ZAddOptions::new())
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::Io);
+}
+
+#[test]
+pub fn test_retry_disabled_by_default() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    drop(listener);
+
+    let client = RedisClient::with_config(RedisConfig {
+        address,
+        ..Default::default()
+    })
+    .unwrap();
+    let err = client.ping().err().unwrap();
+    assert!(err.is_connection_refuse());
+}
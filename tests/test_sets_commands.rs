@@ -5,18 +5,21 @@ use redisclient::RedisClient;
 
 #[test]
 pub fn test_sadd() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("myset", hash_set!["Hello", "World", "World"]).unwrap();
     let members: HashSet<String> = client.smembers("myset").unwrap();
     assert_eq!(members, hash_set!("Hello".to_string(), "World".to_string()));
 
+    let amount = client.sadd("myset", vec!["World", "!"]).unwrap();
+    assert_eq!(amount, 1);
+
     client.flushall().unwrap();
 }
 
 #[test]
 pub fn test_scard() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("myset", hash_set!["Hello", "World"]).unwrap();
     let amount = client.scard("myset").unwrap();
@@ -27,7 +30,7 @@ pub fn test_scard() {
 
 #[test]
 pub fn test_sdiff() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("key1", hash_set!["a", "b", "c"]).unwrap();
     client.sadd("key2", hash_set!["c", "d", "e"]).unwrap();
@@ -39,7 +42,7 @@ pub fn test_sdiff() {
 
 #[test]
 pub fn test_sdiffstore() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("key1", hash_set!["a", "b", "c"]).unwrap();
     client.sadd("key2", hash_set!["c", "d", "e"]).unwrap();
@@ -53,7 +56,7 @@ pub fn test_sdiffstore() {
 
 #[test]
 pub fn test_sinter() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("key1", hash_set!("a", "b", "c")).unwrap();
     client.sadd("key2", hash_set!("c", "d", "e")).unwrap();
@@ -66,7 +69,7 @@ pub fn test_sinter() {
 
 #[test]
 pub fn test_sinterstore() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("key1", hash_set!("a", "b", "c")).unwrap();
     client.sadd("key2", hash_set!("c", "d", "e")).unwrap();
@@ -81,7 +84,7 @@ pub fn test_sinterstore() {
 
 #[test]
 pub fn test_sismember() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("myset", hash_set!["one"]).unwrap();
     assert!(client.sismember("myset", "one".to_string()).unwrap());
@@ -93,7 +96,7 @@ pub fn test_sismember() {
 #[test]
 #[ignore]
 pub fn test_smismember() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("myset", hash_set!("one")).unwrap();
     assert_eq!(
@@ -108,7 +111,7 @@ pub fn test_smismember() {
 
 #[test]
 pub fn test_smove() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("myset", hash_set!("one", "two")).unwrap();
     client.sadd("myotherset", hash_set!("three")).unwrap();
@@ -126,7 +129,7 @@ pub fn test_smove() {
 
 #[test]
 pub fn test_spop() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("myset", hash_set!("one", "two", "three")).unwrap();
     let elements: HashSet<String> = client.spop("myset", Some(3)).unwrap();
@@ -141,13 +144,13 @@ pub fn test_spop() {
 #[test]
 #[ignore]
 pub fn test_srandmember() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
     client.flushall().unwrap();
 }
 
 #[test]
 pub fn test_srem() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("myset", hash_set!("one", "two", "three")).unwrap();
     assert_eq!(client.srem("myset", hash_set!("one")).unwrap(), 1);
@@ -161,7 +164,7 @@ pub fn test_srem() {
 
 #[test]
 pub fn test_sunion() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("key1", hash_set!("a", "b")).unwrap();
     client.sadd("key2", hash_set!("c", "d", "e")).unwrap();
@@ -183,7 +186,7 @@ pub fn test_sunion() {
 
 #[test]
 pub fn test_sunionstore() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     client.sadd("key1", hash_set!("a", "b", "c")).unwrap();
     client.sadd("key2", hash_set!("c", "d", "e")).unwrap();
@@ -204,3 +207,26 @@ pub fn test_sunionstore() {
 
     client.flushall().unwrap();
 }
+
+#[test]
+pub fn test_sscan() {
+    let client = RedisClient::new().unwrap();
+    client.sadd("sscan-set", vec!["one", "two", "three"]).unwrap();
+
+    let members = client
+        .sscan("sscan-set")
+        .collect::<Result<HashSet<String>, _>>()
+        .unwrap();
+    assert_eq!(
+        members,
+        hash_set!["one".to_string(), "two".to_string(), "three".to_string()]
+    );
+
+    let members = client
+        .sscan_match("sscan-set", "t*")
+        .collect::<Result<HashSet<String>, _>>()
+        .unwrap();
+    assert_eq!(members, hash_set!["two".to_string(), "three".to_string()]);
+
+    client.flushall().unwrap();
+}
@@ -1,22 +1,106 @@
+use std::time::Duration;
+
+use redisclient::sorted_sets::{Aggregate, LexBound, ScoreBound, ZAddOptions, ZAggregateOptions, ZRange};
 use redisclient::RedisClient;
 
+#[test]
 pub fn test_zadd() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
+
+    assert_eq!(client.zadd("myzset", vec![(1.0, "one")]).unwrap(), 1);
+    assert_eq!(client.zadd("myzset", vec![(1.0, "uno")]).unwrap(), 1);
+    assert_eq!(client.zadd("myzset", vec![(2.0, "two"), (3.0, "three")]).unwrap(), 2);
+
+    let items: Vec<(String, f64)> = client.zrange("myzset", ZRange::by_rank(0, -1).with_scores()).unwrap();
+    assert_eq!(
+        items,
+        vec![
+            ("one".to_string(), 1.0),
+            ("uno".to_string(), 1.0),
+            ("two".to_string(), 2.0),
+            ("three".to_string(), 3.0)
+        ]
+    );
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_zadd_options() {
+    let client = RedisClient::new().unwrap();
+    client.zadd("myzset", vec![(1.5, "one")]).unwrap();
+
+    let nx = ZAddOptions::new().nx();
+    assert_eq!(
+        client
+            .zadd_options("myzset", vec![(5.0, "one"), (2.0, "two")], nx)
+            .unwrap(),
+        1
+    );
+    assert_eq!(client.zscore("myzset", "one").unwrap(), Some(1.5));
+
+    let xx = ZAddOptions::new().xx().ch();
+    assert_eq!(
+        client
+            .zadd_options("myzset", vec![(2.5, "two"), (3.0, "three")], xx)
+            .unwrap(),
+        1
+    );
+    assert_eq!(client.zscore("myzset", "three").unwrap(), None);
+
+    let gt = ZAddOptions::new().gt().ch();
+    assert_eq!(
+        client
+            .zadd_options("myzset", vec![(1.0, "one"), (4.0, "two")], gt)
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        client.zmscore("myzset", vec!["one", "two", "three"]).unwrap(),
+        vec![Some(1.5), Some(4.0), None]
+    );
+
+    assert_eq!(
+        client.zadd_incr("myzset", 0.5, "one", ZAddOptions::new()).unwrap(),
+        Some(2.0)
+    );
+    assert_eq!(
+        client
+            .zadd_incr("myzset", 1.0, "four", ZAddOptions::new().xx())
+            .unwrap(),
+        None
+    );
 
-    assert_eq!(client.zadd("myzset", vec![(1, "one")]).unwrap(), 1);
-    assert_eq!(client.zadd("myzset", vec![(1, "uno")]).unwrap(), 1);
-    assert_eq!(client.zadd("myzset", vec![(2, "two"), (3, "three")]).unwrap(), 2);
+    client.flushall().unwrap();
+}
 
-    // let _ = client.zrange("myzset", 0, -1, "WITHSCORES");
+#[test]
+pub fn test_bzpop() {
+    let client = RedisClient::new().unwrap();
+    client.zadd("bzpop-zset2", vec![(1.0, "one"), (2.0, "two")]).unwrap();
+
+    let popped: Option<(String, String, f64)> = client
+        .bzpopmin(vec!["bzpop-zset1", "bzpop-zset2"], Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(popped, Some(("bzpop-zset2".to_string(), "one".to_string(), 1.0)));
+    let popped: Option<(String, String, f64)> = client
+        .bzpopmax(vec!["bzpop-zset1", "bzpop-zset2"], Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(popped, Some(("bzpop-zset2".to_string(), "two".to_string(), 2.0)));
+
+    let popped: Option<(String, String, f64)> = client
+        .bzpopmin(vec!["bzpop-zset2"], Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(popped, None);
 
     client.flushall().unwrap();
 }
 
 #[test]
 pub fn test_zcard() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
-    assert_eq!(client.zadd("myzset", vec![(1, "one"), (2, "two")]).unwrap(), 2);
+    assert_eq!(client.zadd("myzset", vec![(1.0, "one"), (2.0, "two")]).unwrap(), 2);
     assert_eq!(client.zcard("myzset").unwrap(), 2);
 
     client.flushall().unwrap();
@@ -24,15 +108,24 @@ pub fn test_zcard() {
 
 #[test]
 pub fn test_zcount() {
-    let mut client = RedisClient::new().unwrap();
+    let client = RedisClient::new().unwrap();
 
     assert_eq!(
         client
-            .zadd("myzset", vec![(1, "one"), (2, "two"), (3, "three")])
+            .zadd("myzset", vec![(1.0, "one"), (2.0, "two"), (3.0, "three")])
             .unwrap(),
         3
     );
 
+    assert_eq!(
+        client
+            .zcount("myzset", ScoreBound::NegInfinity, ScoreBound::PosInfinity)
+            .unwrap(),
+        3
+    );
+    assert_eq!(client.zcount("myzset", f64::NEG_INFINITY, f64::INFINITY).unwrap(), 3);
+    assert_eq!(client.zcount("myzset", ScoreBound::Exclusive(1.0), 3).unwrap(), 2);
+    assert_eq!(client.zcount("myzset", 1.5, 2.5).unwrap(), 1);
     assert_eq!(
         client.zcount("myzset", isize::min_value(), isize::max_value()).unwrap(),
         3
@@ -41,3 +134,192 @@ pub fn test_zcount() {
 
     client.flushall().unwrap();
 }
+
+#[test]
+pub fn test_zincrby() {
+    let client = RedisClient::new().unwrap();
+    client.zadd("myzset", vec![(1.0, "one")]).unwrap();
+
+    assert_eq!(client.zincrby("myzset", 2.5, "one").unwrap(), 3.5);
+    assert_eq!(client.zincrby("myzset", -1.0, "two").unwrap(), -1.0);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_zrange() {
+    let client = RedisClient::new().unwrap();
+    client
+        .zadd("myzset", vec![(1.0, "one"), (2.0, "two"), (3.0, "three")])
+        .unwrap();
+
+    let members: Vec<String> = client.zrange("myzset", ZRange::by_rank(0, 1)).unwrap();
+    assert_eq!(members, vec!["one", "two"]);
+    let members: Vec<String> = client.zrange("myzset", ZRange::by_rank(0, 0).rev()).unwrap();
+    assert_eq!(members, vec!["three"]);
+
+    let members: Vec<String> = client
+        .zrange("myzset", ZRange::by_score(ScoreBound::Exclusive(1.0), f64::INFINITY))
+        .unwrap();
+    assert_eq!(members, vec!["two", "three"]);
+    let items: Vec<(String, f64)> = client
+        .zrange("myzset", ZRange::by_score(1, 3).rev().limit(1, 1).with_scores())
+        .unwrap();
+    assert_eq!(items, vec![("two".to_string(), 2.0)]);
+
+    client
+        .zadd("lexzset", vec![(0.0, "a"), (0.0, "b"), (0.0, "c")])
+        .unwrap();
+    let members: Vec<String> = client
+        .zrange("lexzset", ZRange::by_lex(LexBound::exclusive("a"), LexBound::Max))
+        .unwrap();
+    assert_eq!(members, vec!["b", "c"]);
+    let members: Vec<String> = client
+        .zrange("lexzset", ZRange::by_lex(LexBound::Min, LexBound::inclusive("b")).rev())
+        .unwrap();
+    assert_eq!(members, vec!["b", "a"]);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_zrank() {
+    let client = RedisClient::new().unwrap();
+    client
+        .zadd("myzset", vec![(1.0, "one"), (2.0, "two"), (3.0, "three")])
+        .unwrap();
+
+    assert_eq!(client.zrank("myzset", "three").unwrap(), Some(2));
+    assert_eq!(client.zrevrank("myzset", "three").unwrap(), Some(0));
+    assert_eq!(client.zrank("myzset", "four").unwrap(), None);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_zrem() {
+    let client = RedisClient::new().unwrap();
+    let members = vec![(1.0, "a"), (2.0, "b"), (3.0, "c"), (4.0, "d"), (5.0, "e"), (6.0, "f")];
+    client.zadd("myzset", members).unwrap();
+
+    assert_eq!(client.zrem("myzset", vec!["a", "z"]).unwrap(), 1);
+    assert_eq!(client.zremrangebyrank("myzset", 0, 0).unwrap(), 1);
+    assert_eq!(
+        client
+            .zremrangebyscore("myzset", ScoreBound::Exclusive(5.0), f64::INFINITY)
+            .unwrap(),
+        1
+    );
+    assert_eq!(client.zcard("myzset").unwrap(), 3);
+
+    client
+        .zadd("lexzset", vec![(0.0, "a"), (0.0, "b"), (0.0, "c")])
+        .unwrap();
+    assert_eq!(
+        client
+            .zremrangebylex("lexzset", LexBound::inclusive("b"), LexBound::Max)
+            .unwrap(),
+        2
+    );
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_zpop() {
+    let client = RedisClient::new().unwrap();
+    client
+        .zadd("myzset", vec![(1.0, "one"), (2.0, "two"), (3.0, "three")])
+        .unwrap();
+
+    let items: Vec<(String, f64)> = client.zpopmin("myzset", 1).unwrap();
+    assert_eq!(items, vec![("one".to_string(), 1.0)]);
+    let items: Vec<(String, f64)> = client.zpopmax("myzset", 5).unwrap();
+    assert_eq!(items, vec![("three".to_string(), 3.0), ("two".to_string(), 2.0)]);
+    let items: Vec<(String, f64)> = client.zpopmin("myzset", 1).unwrap();
+    assert!(items.is_empty());
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_zrandmember() {
+    let client = RedisClient::new().unwrap();
+    client.zadd("myzset", vec![(1.0, "one"), (2.0, "two")]).unwrap();
+
+    let member: String = client.zrandmember("myzset").unwrap();
+    assert!(member == "one" || member == "two");
+    let member: Option<String> = client.zrandmember("nozset").unwrap();
+    assert_eq!(member, None);
+
+    let mut items: Vec<(String, f64)> = client.zrandmember_count("myzset", 5, true).unwrap();
+    items.sort_by(|a, b| a.1.total_cmp(&b.1));
+    assert_eq!(items, vec![("one".to_string(), 1.0), ("two".to_string(), 2.0)]);
+    let members: Vec<String> = client.zrandmember_count("myzset", -3, false).unwrap();
+    assert_eq!(members.len(), 3);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_zunion_zinter_zdiff() {
+    let client = RedisClient::new().unwrap();
+    client.zadd("zset1", vec![(1.0, "one"), (2.0, "two")]).unwrap();
+    client
+        .zadd("zset2", vec![(1.0, "one"), (2.0, "two"), (3.0, "three")])
+        .unwrap();
+
+    let items: Vec<(String, f64)> = client
+        .zunion(vec!["zset1", "zset2"], ZAggregateOptions::new(), true)
+        .unwrap();
+    assert_eq!(
+        items,
+        vec![
+            ("one".to_string(), 2.0),
+            ("three".to_string(), 3.0),
+            ("two".to_string(), 4.0)
+        ]
+    );
+
+    let options = ZAggregateOptions::new()
+        .weights(vec![2.0, 3.0])
+        .aggregate(Aggregate::Max);
+    let items: Vec<(String, f64)> = client.zinter(vec!["zset1", "zset2"], options, true).unwrap();
+    assert_eq!(items, vec![("one".to_string(), 3.0), ("two".to_string(), 6.0)]);
+
+    let members: Vec<String> = client.zdiff(vec!["zset2", "zset1"], false).unwrap();
+    assert_eq!(members, vec!["three"]);
+
+    let options = ZAggregateOptions::new().aggregate(Aggregate::Min);
+    assert_eq!(client.zunionstore("out", vec!["zset1", "zset2"], options).unwrap(), 3);
+    assert_eq!(
+        client
+            .zinterstore("out", vec!["zset1", "zset2"], ZAggregateOptions::new())
+            .unwrap(),
+        2
+    );
+    assert_eq!(client.zscore("out", "two").unwrap(), Some(4.0));
+    assert_eq!(client.zdiffstore("out", vec!["zset2", "zset1"]).unwrap(), 1);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_zscan() {
+    let client = RedisClient::new().unwrap();
+    client.zadd("zscan-zset", vec![(1.0, "one"), (2.0, "two")]).unwrap();
+
+    let items = client
+        .zscan("zscan-zset")
+        .collect::<Result<Vec<(String, f64)>, _>>()
+        .unwrap();
+    assert_eq!(items, vec![("one".to_string(), 1.0), ("two".to_string(), 2.0)]);
+
+    let items = client
+        .zscan_match("zscan-zset", "t*")
+        .collect::<Result<Vec<(String, f64)>, _>>()
+        .unwrap();
+    assert_eq!(items, vec![("two".to_string(), 2.0)]);
+
+    client.flushall().unwrap();
+}
@@ -0,0 +1,296 @@
+use std::thread;
+use std::time::Duration;
+
+use redisclient::config::RedisConfig;
+use redisclient::streams::{
+    AutoClaim, PendingRange, ReadFrom, StreamBound, StreamEntry, StreamId, StreamInfo, StreamKey, StreamTrim,
+    XAddOptions, XReadOptions,
+};
+use redisclient::{DataType, RedisClient};
+
+#[test]
+pub fn test_xadd() {
+    let client = RedisClient::new().unwrap();
+
+    let first = client
+        .xadd("xadd-stream", vec![("name", "Sara"), ("surname", "OConnor")])
+        .unwrap();
+    let second = client.xadd("xadd-stream", vec![("field1", "value1")]).unwrap();
+    assert!(second > first);
+    assert_eq!(client.xlen("xadd-stream").unwrap(), 2);
+    assert_eq!(client.type_("xadd-stream").unwrap(), DataType::Stream);
+
+    let options = XAddOptions::new().id(StreamId::new(u64::MAX - 1, 0));
+    let id = client.xadd_options("xadd-stream", vec![("a", 1)], options).unwrap();
+    assert_eq!(id, Some(StreamId::new(u64::MAX - 1, 0)));
+    assert!(client.xadd_options("xadd-stream", vec![("a", 1)], options).is_err());
+
+    let options = XAddOptions::new().nomkstream();
+    assert_eq!(
+        client.xadd_options("xadd-missing", vec![("a", 1)], options).unwrap(),
+        None
+    );
+    assert_eq!(client.xlen("xadd-missing").unwrap(), 0);
+
+    let options = XAddOptions::new().trim(StreamTrim::maxlen(2));
+    client.xadd_options("xadd-stream", vec![("a", 2)], options).unwrap();
+    assert_eq!(client.xlen("xadd-stream").unwrap(), 2);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_xrange() {
+    let client = RedisClient::new().unwrap();
+    for i in 1..=4 {
+        let options = XAddOptions::new().id(StreamId::new(i, 0));
+        client.xadd_options("xrange-stream", vec![("n", i)], options).unwrap();
+    }
+
+    let entries: Vec<StreamEntry> = client
+        .xrange("xrange-stream", StreamBound::Min, StreamBound::Max, None)
+        .unwrap();
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[0].id, StreamId::new(1, 0));
+    assert_eq!(entries[0].get("n").unwrap(), "1");
+
+    let entries: Vec<StreamEntry> = client
+        .xrange(
+            "xrange-stream",
+            StreamBound::Exclusive(StreamId::new(1, 0)),
+            StreamBound::Max,
+            Some(2),
+        )
+        .unwrap();
+    let ids: Vec<StreamId> = entries.iter().map(|entry| entry.id).collect();
+    assert_eq!(ids, vec![StreamId::new(2, 0), StreamId::new(3, 0)]);
+
+    let entries: Vec<StreamEntry<Vec<u8>>> = client
+        .xrevrange("xrange-stream", StreamBound::Max, StreamBound::Min, Some(1))
+        .unwrap();
+    assert_eq!(entries[0].id, StreamId::new(4, 0));
+    assert_eq!(entries[0].fields, vec![("n".to_string(), b"4".to_vec())]);
+
+    assert_eq!(
+        client
+            .xdel("xrange-stream", vec![StreamId::new(1, 0), StreamId::new(9, 0)])
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        client
+            .xtrim("xrange-stream", StreamTrim::minid(StreamId::new(3, 0)))
+            .unwrap(),
+        1
+    );
+    assert_eq!(client.xlen("xrange-stream").unwrap(), 2);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_xread() {
+    let client = RedisClient::new().unwrap();
+    let first = client.xadd("xread-stream1", vec![("n", 1)]).unwrap();
+    let second = client.xadd("xread-stream1", vec![("n", 2)]).unwrap();
+
+    let streams: Vec<StreamKey> = client
+        .xread(
+            vec![
+                ("xread-stream1", ReadFrom::After(StreamId::default())),
+                ("xread-stream2", ReadFrom::After(StreamId::default())),
+            ],
+            XReadOptions::new().count(1),
+        )
+        .unwrap();
+    assert_eq!(streams.len(), 1);
+    assert_eq!(streams[0].key, "xread-stream1");
+    assert_eq!(streams[0].entries.len(), 1);
+    assert_eq!(streams[0].entries[0].id, first);
+
+    let streams: Vec<StreamKey> = client
+        .xread(vec![("xread-stream1", ReadFrom::After(second))], XReadOptions::new())
+        .unwrap();
+    assert!(streams.is_empty());
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_xread_block() {
+    let client = RedisClient::with_config(RedisConfig {
+        read_timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    })
+    .unwrap();
+    let options = XReadOptions::new().block(Duration::from_millis(1000));
+
+    let producer = thread::spawn(|| {
+        thread::sleep(Duration::from_millis(200));
+        RedisClient::new()
+            .unwrap()
+            .xadd("xread-block", vec![("event", "created")])
+            .unwrap()
+    });
+    let streams: Vec<StreamKey> = client.xread(vec![("xread-block", ReadFrom::Latest)], options).unwrap();
+    let id = producer.join().unwrap();
+    assert_eq!(streams[0].entries[0].id, id);
+    assert_eq!(streams[0].entries[0].get("event").unwrap(), "created");
+
+    let options = XReadOptions::new().block(Duration::from_millis(100));
+    let streams: Vec<StreamKey> = client.xread(vec![("xread-block", ReadFrom::Latest)], options).unwrap();
+    assert!(streams.is_empty());
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_xgroup() {
+    let client = RedisClient::new().unwrap();
+
+    assert!(client
+        .xgroup_create("xgroup-stream", "group", ReadFrom::Latest, false)
+        .is_err());
+    client
+        .xgroup_create("xgroup-stream", "group", ReadFrom::Latest, true)
+        .unwrap();
+    let err = client
+        .xgroup_create("xgroup-stream", "group", ReadFrom::Latest, true)
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("BUSYGROUP"));
+
+    let id = client.xadd("xgroup-stream", vec![("n", 1)]).unwrap();
+    let groups = client.xinfo_groups("xgroup-stream").unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name, "group");
+    assert_eq!(groups[0].last_delivered_id, StreamId::default());
+
+    client.xgroup_setid("xgroup-stream", "group", ReadFrom::Latest).unwrap();
+    let groups = client.xinfo_groups("xgroup-stream").unwrap();
+    assert_eq!(groups[0].last_delivered_id, id);
+
+    let info: StreamInfo = client.xinfo_stream("xgroup-stream").unwrap();
+    assert_eq!(info.length, 1);
+    assert_eq!(info.groups, 1);
+    assert_eq!(info.last_generated_id, id);
+    assert_eq!(info.first_entry.map(|entry| entry.id), Some(id));
+
+    assert!(client.xgroup_destroy("xgroup-stream", "group").unwrap());
+    assert!(!client.xgroup_destroy("xgroup-stream", "group").unwrap());
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_xreadgroup() {
+    let client = RedisClient::new().unwrap();
+    client
+        .xgroup_create("xreadgroup-stream", "group", ReadFrom::Latest, true)
+        .unwrap();
+    let first = client.xadd("xreadgroup-stream", vec![("n", 1)]).unwrap();
+    let second = client.xadd("xreadgroup-stream", vec![("n", 2)]).unwrap();
+
+    let keys = vec![("xreadgroup-stream", ReadFrom::Undelivered)];
+    let streams: Vec<StreamKey> = client
+        .xreadgroup("group", "alice", keys.clone(), XReadOptions::new().count(1))
+        .unwrap();
+    assert_eq!(streams[0].entries[0].id, first);
+    let streams: Vec<StreamKey> = client
+        .xreadgroup("group", "bob", keys.clone(), XReadOptions::new())
+        .unwrap();
+    assert_eq!(streams[0].entries[0].id, second);
+    let streams: Vec<StreamKey> = client.xreadgroup("group", "bob", keys, XReadOptions::new()).unwrap();
+    assert!(streams.is_empty());
+
+    // the history of alice's pending entries
+    let history = vec![("xreadgroup-stream", ReadFrom::After(StreamId::default()))];
+    let streams: Vec<StreamKey> = client
+        .xreadgroup("group", "alice", history.clone(), XReadOptions::new())
+        .unwrap();
+    assert_eq!(streams[0].entries.len(), 1);
+    assert_eq!(streams[0].entries[0].id, first);
+
+    let summary = client.xpending("xreadgroup-stream", "group").unwrap();
+    assert_eq!(summary.count, 2);
+    assert_eq!(summary.min, Some(first));
+    assert_eq!(summary.max, Some(second));
+    let mut consumers = summary.consumers;
+    consumers.sort();
+    assert_eq!(consumers, vec![("alice".to_string(), 1), ("bob".to_string(), 1)]);
+
+    let range = PendingRange::new(StreamBound::Min, StreamBound::Max, 10).consumer("bob");
+    let pending = client.xpending_range("xreadgroup-stream", "group", range).unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, second);
+    assert_eq!(pending[0].consumer, "bob");
+    assert_eq!(pending[0].deliveries, 1);
+
+    assert_eq!(
+        client.xack("xreadgroup-stream", "group", vec![first, first]).unwrap(),
+        1
+    );
+    let streams: Vec<StreamKey> = client
+        .xreadgroup("group", "alice", history, XReadOptions::new())
+        .unwrap();
+    assert!(streams[0].entries.is_empty());
+
+    let consumers = client.xinfo_consumers("xreadgroup-stream", "group").unwrap();
+    assert_eq!(consumers.len(), 2);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_xclaim() {
+    let client = RedisClient::new().unwrap();
+    client
+        .xgroup_create("xclaim-stream", "group", ReadFrom::Latest, true)
+        .unwrap();
+    let first = client.xadd("xclaim-stream", vec![("n", 1)]).unwrap();
+    let second = client.xadd("xclaim-stream", vec![("n", 2)]).unwrap();
+    let third = client.xadd("xclaim-stream", vec![("n", 3)]).unwrap();
+    let _: Vec<StreamKey> = client
+        .xreadgroup(
+            "group",
+            "alice",
+            vec![("xclaim-stream", ReadFrom::Undelivered)],
+            XReadOptions::new(),
+        )
+        .unwrap();
+
+    let claimed: Vec<StreamEntry> = client
+        .xclaim("xclaim-stream", "group", "bob", Duration::from_secs(60), vec![first])
+        .unwrap();
+    assert!(claimed.is_empty());
+    thread::sleep(Duration::from_millis(20));
+    let claimed: Vec<StreamEntry> = client
+        .xclaim("xclaim-stream", "group", "bob", Duration::from_millis(10), vec![first])
+        .unwrap();
+    assert_eq!(claimed.len(), 1);
+    assert_eq!(claimed[0].get("n").unwrap(), "1");
+
+    client.xdel("xclaim-stream", vec![third]).unwrap();
+    thread::sleep(Duration::from_millis(20));
+    let claim: AutoClaim = client
+        .xautoclaim(
+            "xclaim-stream",
+            "group",
+            "carol",
+            Duration::from_millis(10),
+            StreamId::default(),
+            Some(10),
+        )
+        .unwrap();
+    assert_eq!(claim.next, StreamId::default());
+    let ids: Vec<StreamId> = claim.entries.iter().map(|entry| entry.id).collect();
+    assert_eq!(ids, vec![first, second]);
+    assert_eq!(claim.deleted, vec![third]);
+
+    let range = PendingRange::new(StreamBound::Min, StreamBound::Max, 10).consumer("carol");
+    let pending = client.xpending_range("xclaim-stream", "group", range).unwrap();
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].deliveries, 3);
+
+    client.flushall().unwrap();
+}
@@ -0,0 +1,38 @@
+use redisclient::RedisClient;
+
+#[test]
+pub fn test_get() {
+    let client = RedisClient::new().unwrap();
+
+    client.simple_set("mykey", "Hello").unwrap();
+
+    let value: Option<String> = client.get("mykey").unwrap();
+    assert_eq!(value, Some("Hello".to_string()));
+
+    let value: Option<String> = client.get("nonexisting").unwrap();
+    assert_eq!(value, None);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_binary_values() {
+    let client = RedisClient::new().unwrap();
+
+    let blob: &[u8] = &[0xff, 0x00, 0xfe, b'\r', b'\n'];
+    client.simple_set("blob", blob).unwrap();
+
+    let value: Vec<u8> = client.get("blob").unwrap();
+    assert_eq!(value, blob.to_vec());
+
+    let value: [u8; 5] = client.get("blob").unwrap();
+    assert_eq!(&value, blob);
+
+    client.simple_set("blob", [0x80u8; 4]).unwrap();
+    client.append("blob", vec![0x81u8]).unwrap();
+
+    let value: Vec<u8> = client.get("blob").unwrap();
+    assert_eq!(value, vec![0x80, 0x80, 0x80, 0x80, 0x81]);
+
+    client.flushall().unwrap();
+}
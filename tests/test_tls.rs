@@ -0,0 +1,75 @@
+#![cfg(feature = "tls")]
+
+use std::path::PathBuf;
+
+use redisclient::config::{RedisConfig, TlsConfig};
+use redisclient::{ErrorKind, RedisClient};
+
+// The TLS tests need a server on 127.0.0.1:6380 (and one requiring a client certificate
+// on 127.0.0.1:6381), with `ca.crt`, `client.crt` and `client.key` in `$REDIS_TLS_DIR`.
+fn cert(name: &str) -> Option<PathBuf> {
+    let dir = std::env::var("REDIS_TLS_DIR").expect("REDIS_TLS_DIR isn't set");
+    Some(PathBuf::from(dir).join(name))
+}
+
+fn config(address: &str, tls: TlsConfig) -> RedisConfig {
+    RedisConfig {
+        address: address.to_string(),
+        tls: Some(tls),
+        ..RedisConfig::default()
+    }
+}
+
+#[test]
+#[ignore = "needs a TLS server"]
+pub fn test_tls() {
+    let tls = TlsConfig {
+        ca_file: cert("ca.crt"),
+        server_name: Some("localhost".to_string()),
+        ..TlsConfig::default()
+    };
+    let client = RedisClient::with_config(config("127.0.0.1:6380", tls)).unwrap();
+
+    client.simple_set("key", "Hello").unwrap();
+    let value: String = client.get("key").unwrap();
+    assert_eq!(value, "Hello");
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_tls_invalid_config() {
+    // the certificates are read once, when the client is created
+    let tls = TlsConfig {
+        ca_file: Some(PathBuf::from("/nonexistent/ca.crt")),
+        ..TlsConfig::default()
+    };
+    let result = RedisClient::with_config(config("127.0.0.1:6380", tls));
+    assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidConfig);
+}
+
+#[test]
+#[ignore = "needs a TLS server"]
+pub fn test_tls_untrusted_certificate() {
+    // the bundled web roots don't trust a self-signed certificate
+    let result =
+        RedisClient::with_config(config("127.0.0.1:6380", TlsConfig::default())).and_then(|client| client.ping());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Io);
+
+    let client = RedisClient::open("rediss://127.0.0.1:6380?insecure=true").unwrap();
+    client.ping().unwrap();
+}
+
+#[test]
+#[ignore = "needs a TLS server"]
+pub fn test_mutual_tls() {
+    let tls = TlsConfig {
+        ca_file: cert("ca.crt"),
+        client_cert_file: cert("client.crt"),
+        client_key_file: cert("client.key"),
+        ..TlsConfig::default()
+    };
+    let client = RedisClient::with_config(config("127.0.0.1:6381", tls)).unwrap();
+
+    client.ping().unwrap();
+}
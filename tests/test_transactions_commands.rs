@@ -0,0 +1,69 @@
+use redisclient::{ErrorKind, RedisClient, RedisError};
+
+#[test]
+pub fn test_transaction() {
+    let client = RedisClient::new().unwrap();
+    client.simple_set("counter", 1).unwrap();
+
+    let (value,): (i64,) = client
+        .transaction(vec!["counter"], |conn, pipe| {
+            let value: i64 = conn.get("counter")?;
+            pipe.incrby("counter", value * 10);
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(value, 11);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_transaction_retry_when_watched_key_modified() {
+    let client = RedisClient::new().unwrap();
+    let other = RedisClient::new().unwrap();
+    client.simple_set("counter", 1).unwrap();
+
+    let mut attempts = 0;
+    let ((), value): ((), i64) = client
+        .transaction(vec!["counter"], |conn, pipe| {
+            attempts += 1;
+            let value: i64 = conn.get("counter")?;
+            if attempts == 1 {
+                other.incr("counter")?;
+            }
+            pipe.simple_set("counter", value * 10).get("counter");
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(attempts, 2);
+    assert_eq!(value, 20);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_transaction_closure_error() {
+    let client = RedisClient::new().unwrap();
+
+    let err = client
+        .transaction(vec!["counter"], |_, pipe| {
+            pipe.incr("counter");
+            Err(RedisError::custom(ErrorKind::ClientError, "give up"))
+        })
+        .map(|(_,): (i64,)| ())
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ClientError);
+    assert_eq!(client.exists(vec!["counter"]).unwrap(), 0);
+
+    client.flushall().unwrap();
+}
+
+#[test]
+pub fn test_watch_and_unwatch() {
+    let client = RedisClient::new().unwrap();
+
+    client.watch(vec!["key1", "key2"]).unwrap();
+    client.unwatch().unwrap();
+
+    client.flushall().unwrap();
+}